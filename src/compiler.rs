@@ -3,17 +3,26 @@ use crate::get_version;
 use crate::docs::module::DocumentationModule;
 use crate::modules::block::Block;
 use crate::modules::prelude::{BlockFragment, FragmentRenderable};
+use crate::modules::statement::stmt::StmtType;
 use crate::modules::typecheck::TypeCheckModule;
 use crate::optimizer::optimize_fragments;
 use crate::translate::check_all_blocks;
+use crate::translate::compute::ArithType;
 use crate::translate::module::TranslateModule;
+use crate::utils::module_cache::SharedModuleCache;
+use crate::utils::symbol_table::SymbolTable;
 use crate::utils::{pluralize, ParserMetadata, TranslateMetadata};
 use crate::rules;
+use depfile::Dependency;
+use manifest::Manifest;
 use postprocessor::PostProcessor;
+use source_map::SourceMap;
+use target::{BashVersion, Target};
 use colored::Colorize;
 use heraclitus_compiler::prelude::*;
 use itertools::Itertools;
 use wildmatch::WildMatchPattern;
+use std::collections::BTreeSet;
 use std::env;
 use std::fs;
 use std::fs::File;
@@ -23,29 +32,111 @@ use std::path::PathBuf;
 use std::process::{exit, Command, ExitStatus};
 use std::time::Instant;
 
+pub mod depfile;
+pub mod manifest;
+pub mod message_format;
+pub mod package;
 pub mod postprocessor;
+pub mod source_map;
+pub mod target;
 
 const NO_CODE_PROVIDED: &str = "No code has been provided to the compiler";
 const AMBER_DEBUG_PARSER: &str = "AMBER_DEBUG_PARSER";
 const AMBER_DEBUG_TIME: &str = "AMBER_DEBUG_TIME";
 const AMBER_NO_OPTIMIZE: &str = "AMBER_NO_OPTIMIZE";
 
+/// Test block compiled to a Bash function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    pub name: String,
+    pub function: String,
+}
+
 pub struct CompilerOptions {
     pub no_proc: Vec<String>,
     pub minify: bool,
+    /// Collect symbol definitions and usages for editor tooling
+    pub collect_symbols: bool,
+    /// Shell dialect of the generated code
+    pub target: Target,
+    /// Oldest Bash version the generated code has to support (latest if not set)
+    pub bash_version: Option<BashVersion>,
+    /// Generate the source map relating the generated code to the Amber source
+    pub source_map: bool,
+    /// Print the Amber stack trace when a failure is not handled
+    pub trace_failures: bool,
+    /// Translate the test blocks instead of the main block
+    pub test_mode: bool,
+    /// Optimize the generated code
+    pub optimize: bool,
+    /// Program evaluating the math on numbers in the generated code
+    pub num_backend: ArithType,
+    /// File replacing the default header (overridden by 'AMBER_HEADER')
+    pub header: Option<PathBuf>,
+    /// File appended to the generated code (overridden by 'AMBER_FOOTER')
+    pub footer: Option<PathBuf>,
+    /// Modules parsed by the previous compilations
+    pub module_cache: Option<SharedModuleCache>,
 }
 
 impl Default for CompilerOptions {
     fn default() -> Self {
         let no_proc = vec![String::from("*")];
-        Self { no_proc, minify: false }
+        Self { no_proc, minify: false, collect_symbols: false, target: Target::Bash, bash_version: None, source_map: false, trace_failures: false, test_mode: false, optimize: true, num_backend: ArithType::BcSed, header: None, footer: None, module_cache: None }
     }
 }
 
 impl CompilerOptions {
     pub fn from_args(no_proc: &[String], minify: bool) -> Self {
         let no_proc = no_proc.to_owned();
-        Self { no_proc, minify }
+        Self { no_proc, minify, collect_symbols: false, target: Target::Bash, bash_version: None, source_map: false, trace_failures: false, test_mode: false, optimize: true, num_backend: ArithType::BcSed, header: None, footer: None, module_cache: None }
+    }
+
+    /// Takes the defaults from the project manifest
+    pub fn from_manifest(manifest: &Manifest) -> Self {
+        let header = manifest.build.header.as_ref().map(|path| manifest.resolve(path));
+        let footer = manifest.build.footer.as_ref().map(|path| manifest.resolve(path));
+        Self { header, footer, ..Self::from_args(&manifest.build.no_proc, manifest.build.minify) }
+    }
+
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_bash_version(mut self, bash_version: Option<BashVersion>) -> Self {
+        self.bash_version = bash_version;
+        self
+    }
+
+    pub fn with_source_map(mut self, source_map: bool) -> Self {
+        self.source_map = source_map;
+        self
+    }
+
+    pub fn with_trace_failures(mut self, trace_failures: bool) -> Self {
+        self.trace_failures = trace_failures;
+        self
+    }
+
+    pub fn with_test_mode(mut self, test_mode: bool) -> Self {
+        self.test_mode = test_mode;
+        self
+    }
+
+    pub fn with_optimize(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    pub fn with_num_backend(mut self, num_backend: ArithType) -> Self {
+        self.num_backend = num_backend;
+        self
+    }
+
+    pub fn with_module_cache(mut self, module_cache: SharedModuleCache) -> Self {
+        self.module_cache = Some(module_cache);
+        self
     }
 }
 
@@ -115,23 +206,39 @@ impl AmberCompiler {
 
     pub fn parse(&self, tokens: Vec<Token>) -> Result<(Block, ParserMetadata), Message> {
         let code = self.cc.code.as_ref().expect(NO_CODE_PROVIDED).clone();
-        let mut meta = ParserMetadata::new(tokens, self.path.clone(), Some(code));
+        let mut meta = ParserMetadata::new(tokens, self.path.clone(), Some(code.clone()));
+        meta.target = self.options.target;
+        meta.bash_version = self.options.bash_version;
+        if self.options.collect_symbols {
+            meta.symbol_table = Some(SymbolTable::new());
+        }
         if let Err(Failure::Loud(err)) = check_all_blocks(&meta) {
             return Err(err);
         }
         let mut block = Block::new().with_no_syntax();
         let time = Instant::now();
+        meta.module_cache = self.options.module_cache.clone();
+        let cached = meta.get_cached_module(&code);
+        let is_cached = cached.is_some();
         // Parse with debug or not
-        let result = if Self::env_flag_set(AMBER_DEBUG_PARSER) {
+        let result = if let Some(cached) = cached {
+            block = cached;
+            Ok(())
+        } else if Self::env_flag_set(AMBER_DEBUG_PARSER) {
             block.parse_debug(&mut meta)
         } else {
             block.parse(&mut meta)
         };
+        // Warnings are reported during parsing so only the modules without them can be reused
+        if result.is_ok() && !is_cached && meta.messages.is_empty() {
+            meta.cache_module(&code, &block);
+        }
         if Self::env_flag_set(AMBER_DEBUG_TIME) {
             let pathname = self.path.clone().unwrap_or(String::from("unknown"));
+            let label = if is_cached { "Reused" } else { "Parsed" };
             println!(
                 "[{}]\tin\t{}ms\t{pathname}",
-                "Parsed".blue(),
+                label.blue(),
                 time.elapsed().as_millis()
             );
         }
@@ -165,10 +272,11 @@ impl AmberCompiler {
         result
     }
 
-    fn gen_header(&self) -> String {
-        let header_template = if let Ok(dynamic) = env::var("AMBER_HEADER") {
+    fn gen_header(&self, required_commands: &BTreeSet<String>) -> String {
+        let dynamic = env::var("AMBER_HEADER").ok().map(PathBuf::from).or_else(|| self.options.header.clone());
+        let header_template = if let Some(dynamic) = dynamic {
             fs::read_to_string(&dynamic).unwrap_or_else(|_| {
-                let msg = format!("Couldn't read the dynamic header file from path '{dynamic}'");
+                let msg = format!("Couldn't read the dynamic header file from path '{}'", dynamic.display());
                 Message::new_err_msg(msg).show();
                 exit(1);
             })
@@ -176,13 +284,25 @@ impl AmberCompiler {
             include_str!("header.sh").trim_end().to_string()
         };
 
-        header_template.replace("{{ version }}", get_version())
+        let mut header = header_template
+            .replace("{{ version }}", get_version())
+            .replace("{{ shebang }}", self.options.target.shebang());
+        if self.options.trace_failures {
+            header = format!("{header}\n{}", include_str!("trace.sh").trim_end());
+        }
+        if !required_commands.is_empty() {
+            let check = include_str!("requires.sh").trim_end()
+                .replace("{{ commands }}", &required_commands.iter().join(" "));
+            header = format!("{header}\n{check}");
+        }
+        header
     }
 
     fn gen_footer(&self) -> String {
-        let footer_template = if let Ok(dynamic) = env::var("AMBER_FOOTER") {
+        let dynamic = env::var("AMBER_FOOTER").ok().map(PathBuf::from).or_else(|| self.options.footer.clone());
+        let footer_template = if let Some(dynamic) = dynamic {
             fs::read_to_string(&dynamic).unwrap_or_else(|_| {
-                let msg = format!("Couldn't read the dynamic footer file from path '{dynamic}'");
+                let msg = format!("Couldn't read the dynamic footer file from path '{}'", dynamic.display());
                 Message::new_err_msg(msg).show();
                 exit(1);
             })
@@ -193,12 +313,23 @@ impl AmberCompiler {
         footer_template.replace("{{ version }}", get_version())
     }
 
-    pub fn translate(&self, block: Block, meta: ParserMetadata) -> Result<String, Message> {
+    /// Translates the code and relates the generated lines to the Amber source if the source map is enabled
+    pub fn translate(&self, block: Block, meta: ParserMetadata) -> Result<(String, SourceMap), Message> {
+        self.translate_with_commands(block, meta)
+            .map(|(code, source_map, _)| (code, source_map))
+    }
+
+    /// Translates the code and lists the external commands that the generated code invokes
+    fn translate_with_commands(&self, block: Block, meta: ParserMetadata) -> Result<(String, SourceMap, BTreeSet<String>), Message> {
         let ast_forest = self.get_sorted_ast_forest(block, &meta);
         let mut meta_translate = TranslateMetadata::new(meta, &self.options);
+        meta_translate.optimize &= !Self::env_flag_set(AMBER_NO_OPTIMIZE);
         let time = Instant::now();
         let mut result = BlockFragment::new(Vec::new(), false);
-        for (_path, block) in ast_forest {
+        let mut sources = vec![];
+        for (index, (path, block)) in ast_forest.into_iter().enumerate() {
+            meta_translate.source_index = index;
+            sources.push(path);
             result.append(block.translate(&mut meta_translate));
         }
         if Self::env_flag_set(AMBER_DEBUG_TIME) {
@@ -211,7 +342,7 @@ impl AmberCompiler {
         }
 
         let mut result = result.to_frag();
-        if !Self::env_flag_set(AMBER_NO_OPTIMIZE) {
+        if self.options.optimize && !Self::env_flag_set(AMBER_NO_OPTIMIZE) {
             optimize_fragments(&mut result);
         }
 
@@ -235,7 +366,16 @@ impl AmberCompiler {
             };
         }
 
-        Ok(format!("{}\n{}\n{}", self.gen_header(), result, self.gen_footer()))
+        let header = self.gen_header(&meta_translate.required_commands);
+        let code = format!("{}\n{}\n{}", header, result, self.gen_footer());
+        let mut commands = meta_translate.commands;
+        commands.extend(meta_translate.required_commands);
+        let (code, source_map) = if self.options.source_map {
+            SourceMap::extract(&code, sources)
+        } else {
+            (code, SourceMap::default())
+        };
+        Ok((code, source_map, commands))
     }
 
     pub fn document(&self, block: Block, meta: ParserMetadata, output: Option<String>) {
@@ -316,18 +456,62 @@ impl AmberCompiler {
                 "Typecheck".green(),
                 time.elapsed().as_millis()
             );
+            if let Some(cache) = &self.options.module_cache {
+                let cache = cache.borrow();
+                println!("[{}]\t{} reused\t{} parsed\t{} imports reused after type checking", "Cache".blue(), cache.hits, cache.misses, cache.checked_hits);
+            }
         }
 
         Ok((block, meta))
     }
 
     pub fn compile(&self) -> Result<(Vec<Message>, String), Message> {
+        self.compile_with_source_map().map(|(messages, code, _)| (messages, code))
+    }
+
+    pub fn compile_with_source_map(&self) -> Result<(Vec<Message>, String, SourceMap), Message> {
+        self.compile_with_dependencies()
+            .map(|(messages, code, source_map, _)| (messages, code, source_map))
+    }
+
+    /// Compiles the code and lists the files that it was compiled from
+    pub fn compile_with_dependencies(&self) -> Result<(Vec<Message>, String, SourceMap, Vec<Dependency>), Message> {
         let tokens = self.tokenize()?;
         let (block, meta) = self.parse(tokens)?;
         let (block, meta) = self.typecheck(block, meta)?;
         let messages = meta.messages.clone();
-        let code = self.translate(block, meta)?;
-        Ok((messages, code))
+        let dependencies = Dependency::collect(&meta.import_cache);
+        let (code, source_map) = self.translate(block, meta)?;
+        Ok((messages, code, source_map, dependencies))
+    }
+
+    /// Compiles the code and lists the external commands that the generated script invokes
+    pub fn compile_with_commands(&self) -> Result<(Vec<Message>, BTreeSet<String>), Message> {
+        let tokens = self.tokenize()?;
+        let (block, meta) = self.parse(tokens)?;
+        let (block, meta) = self.typecheck(block, meta)?;
+        let messages = meta.messages.clone();
+        let (_, _, commands) = self.translate_with_commands(block, meta)?;
+        Ok((messages, commands))
+    }
+
+    /// Compiles the test blocks of the main file to functions that can be run one by one
+    pub fn compile_tests(&self) -> Result<(Vec<Message>, String, Vec<TestCase>), Message> {
+        let tokens = self.tokenize()?;
+        let (block, meta) = self.parse(tokens)?;
+        let (block, meta) = self.typecheck(block, meta)?;
+        let messages = meta.messages.clone();
+        let tests = block.statements.iter()
+            .filter_map(|statement| match &statement.value {
+                Some(StmtType::Test(test)) => Some(TestCase {
+                    name: test.name.clone(),
+                    function: test.function_name(),
+                }),
+                _ => None,
+            })
+            .collect();
+        let (code, _) = self.translate(block, meta)?;
+        Ok((messages, code, tests))
     }
 
     pub fn execute(mut code: String, args: Vec<String>) -> Result<ExitStatus, std::io::Error> {
@@ -358,6 +542,9 @@ impl AmberCompiler {
     #[cfg(test)]
     pub fn test_eval(&mut self) -> Result<String, Message> {
         self.options.no_proc = vec!["*".into()];
+        if let Ok(version) = env::var("AMBER_TEST_BASH_VERSION") {
+            self.options.bash_version = Some(version.parse().expect("Invalid AMBER_TEST_BASH_VERSION"));
+        }
         self.compile().map_or_else(Err, |(warnings, code)| {
             if let Some(mut command) = Self::find_bash() {
                 let child = command.arg("-c").arg::<&str>(code.as_ref()).output().unwrap();
@@ -399,6 +586,13 @@ impl AmberCompiler {
             let args: Vec<&str> = args_string.split_whitespace().collect();
             command.args(args);
             Some(command)
+        } else if env::var("AMBER_TEST_STRATEGY").is_ok_and(|value| value == "local") {
+            // Use a locally built Bash such as bash-3.2 to test the compatibility mode
+            let args_string = env::var("AMBER_TEST_ARGS").expect("Please pass the Bash binary path in AMBER_TEST_ARGS environment variable.");
+            let mut args = args_string.split_whitespace();
+            let mut command = Command::new(args.next().expect("Bash binary path expected in AMBER_TEST_ARGS"));
+            command.args(args);
+            Some(command)
         } else {
             let mut command = Command::new("/usr/bin/env");
             command.arg("bash");
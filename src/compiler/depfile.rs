@@ -0,0 +1,49 @@
+use crate::utils::import_cache::ImportCache;
+
+/// File that the generated code depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub path: String,
+    /// Module of the standard library built into the compiler instead of a file on disk
+    pub is_virtual: bool,
+}
+
+impl Dependency {
+    /// Lists the compiled files, the imported modules and the embedded files
+    pub fn collect(import_cache: &ImportCache) -> Vec<Dependency> {
+        let files = import_cache.files.iter().map(|file| &file.path);
+        let mut dependencies: Vec<Dependency> = vec![];
+        for path in files.chain(import_cache.embedded_files.iter()) {
+            // Code read from the standard input or given directly has no file
+            if path == "." || path == "-" || dependencies.iter().any(|dep| &dep.path == path) {
+                continue;
+            }
+            let is_virtual = path.starts_with("std/");
+            dependencies.push(Dependency { path: path.clone(), is_virtual });
+        }
+        dependencies
+    }
+}
+
+/// Escapes the characters that have a special meaning in Make rules
+fn escape(path: &str) -> String {
+    path.replace('$', "$$")
+        .replace('#', "\\#")
+        .replace(' ', "\\ ")
+}
+
+/// Renders a Make-style depfile for the target.
+/// Virtual dependencies have no file to depend on, so they are listed in comments after the rule.
+pub fn render_depfile(target: &str, dependencies: &[Dependency]) -> String {
+    let (virtual_deps, file_deps): (Vec<_>, Vec<_>) = dependencies.iter().partition(|dep| dep.is_virtual);
+    let mut result = format!("{}:", escape(target));
+    for dependency in file_deps {
+        result.push_str(" \\\n  ");
+        result.push_str(&escape(&dependency.path));
+    }
+    result.push('\n');
+    for dependency in virtual_deps {
+        result.push_str(&format!("# virtual: {}\n", dependency.path));
+    }
+    result
+}
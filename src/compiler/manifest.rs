@@ -0,0 +1,107 @@
+use heraclitus_compiler::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the project manifest file
+pub const MANIFEST_FILE: &str = "amber.toml";
+
+/// Build settings of the project
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BuildManifest {
+    /// Amber scripts compiled by 'amber build' without arguments
+    pub entries: Vec<PathBuf>,
+    /// Directory of the generated scripts (next to the entry points if not set)
+    pub output_dir: Option<PathBuf>,
+    /// Postprocessors disabled unless overridden on the command line
+    pub no_proc: Vec<String>,
+    /// Minify the generated scripts
+    pub minify: bool,
+    /// File replacing the default header of the generated scripts
+    pub header: Option<PathBuf>,
+    /// File appended to the generated scripts
+    pub footer: Option<PathBuf>,
+}
+
+/// Library shared with the project, imported with 'pkg:<name>/<path>'
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dependency {
+    /// URL of the git repository
+    pub git: Option<String>,
+    /// Revision checked out from the git repository (the default branch if not set)
+    pub rev: Option<String>,
+    /// Local directory containing the library
+    pub path: Option<PathBuf>,
+}
+
+/// Project manifest read from 'amber.toml'
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Manifest {
+    pub build: BuildManifest,
+    pub dependencies: BTreeMap<String, Dependency>,
+    /// Directory containing the manifest, the paths in the manifest are relative to it
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+impl Manifest {
+    /// Finds the manifest in the given directory or in any of its parents
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(MANIFEST_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Reads and parses the manifest at the given path
+    pub fn load(path: &Path) -> Result<Manifest, Message> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            Message::new_err_msg(format!("Couldn't read the manifest '{}': {err}", path.display()))
+        })?;
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::parse(&content, root).map_err(|err| {
+            Message::new_err_msg(format!("Invalid manifest '{}'", path.display()))
+                .comment(err.message())
+        })
+    }
+
+    /// Parses the content of the manifest located in the given directory
+    pub fn parse(content: &str, root: PathBuf) -> Result<Manifest, toml::de::Error> {
+        let manifest: Manifest = toml::from_str(content)?;
+        for (name, dependency) in manifest.dependencies.iter() {
+            let message = match dependency {
+                Dependency { git: Some(_), path: None, .. } => continue,
+                Dependency { git: None, path: Some(_), rev: None } => continue,
+                Dependency { git: None, path: Some(_), rev: Some(_) } => "'rev' can only be used with 'git'",
+                _ => "expected exactly one of 'git' or 'path'",
+            };
+            return Err(serde::de::Error::custom(format!("invalid dependency '{name}': {message}")));
+        }
+        Ok(Manifest { root, ..manifest })
+    }
+
+    /// Resolves a path given in the manifest
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Pairs every entry point with the path of its generated script
+    pub fn targets(&self) -> Vec<(PathBuf, PathBuf)> {
+        self.build.entries.iter()
+            .map(|entry| {
+                let input = self.resolve(entry);
+                let output = match &self.build.output_dir {
+                    Some(dir) => {
+                        let name = input.file_stem().map(PathBuf::from).unwrap_or_default();
+                        self.resolve(dir).join(name.with_extension("sh"))
+                    }
+                    None => input.with_extension("sh"),
+                };
+                (input, output)
+            })
+            .collect()
+    }
+}
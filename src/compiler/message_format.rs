@@ -0,0 +1,183 @@
+use crate::get_version;
+use crate::stdlib;
+use clap::ValueEnum;
+use heraclitus_compiler::prelude::*;
+use serde::Serialize;
+use serde_json::json;
+use std::fs;
+
+/// Format in which compiler messages are reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// Colored messages with code snippets
+    #[default]
+    Human,
+    /// One JSON object per message
+    Json,
+    /// Static Analysis Results Interchange Format (SARIF) 2.1.0 log
+    Sarif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: LineColumn,
+    pub end: LineColumn,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Location {
+    pub path: Option<String>,
+    pub span: Option<Span>,
+}
+
+/// Machine readable representation of a compiler message
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: &'static str,
+    pub path: Option<String>,
+    pub span: Option<Span>,
+    pub message: Option<String>,
+    pub comment: Option<String>,
+    /// Locations of the imports and calls leading to the message
+    pub trace: Vec<Location>,
+}
+
+impl Diagnostic {
+    pub fn from_message(message: &Message) -> Self {
+        let severity = match message.kind {
+            MessageType::Error => "error",
+            MessageType::Warning => "warning",
+            MessageType::Info => "info",
+        };
+        let mut locations = message.trace.iter().map(|position| {
+            let code = load_source(position.path.as_deref()).or_else(|| message.code.clone());
+            Location {
+                path: position.path.clone(),
+                span: get_span(position, code.as_deref()),
+            }
+        });
+        let location = locations.next();
+        Diagnostic {
+            severity,
+            path: location.as_ref().and_then(|location| location.path.clone()),
+            span: location.and_then(|location| location.span),
+            message: message.message.clone(),
+            comment: message.comment.clone(),
+            trace: locations.collect(),
+        }
+    }
+}
+
+/// Reads the source code of the file (or standard library module) at the path
+fn load_source(path: Option<&str>) -> Option<String> {
+    let path = path?;
+    match path.strip_prefix("std/") {
+        Some(module) => stdlib::resolve(module),
+        None => fs::read_to_string(path).ok(),
+    }
+}
+
+/// Calculates the one based span of the position in the code.
+/// The span covers `len` characters starting at the row and column of the position.
+pub fn get_span(position: &PositionInfo, code: Option<&str>) -> Option<Span> {
+    let Position::Pos(line, column) = position.position else {
+        return None;
+    };
+    let start = LineColumn { line, column };
+    let mut end = LineColumn { line, column: column.saturating_add(position.len) };
+    if let Some(code) = code {
+        let mut chars = code.lines()
+            .skip(line.saturating_sub(1))
+            .enumerate()
+            .flat_map(|(index, text)| {
+                text.chars()
+                    .chain(std::iter::once('\n'))
+                    .enumerate()
+                    .map(move |(col, chr)| (line + index, col + 1, chr))
+            })
+            .skip(column.saturating_sub(1));
+        let mut remaining = position.len;
+        end = start;
+        while remaining > 0 {
+            let Some((row, col, chr)) = chars.next() else {
+                break;
+            };
+            end = if chr == '\n' {
+                LineColumn { line: row + 1, column: 1 }
+            } else {
+                LineColumn { line: row, column: col + 1 }
+            };
+            remaining -= 1;
+        }
+    }
+    Some(Span { start, end })
+}
+
+/// Prints the messages to the standard error in the given format
+pub fn show_messages(messages: &[Message], format: MessageFormat) {
+    match format {
+        MessageFormat::Human => messages.iter().for_each(Message::show),
+        MessageFormat::Json => {
+            for message in messages {
+                let diagnostic = Diagnostic::from_message(message);
+                eprintln!("{}", serde_json::to_string(&diagnostic).unwrap());
+            }
+        }
+        MessageFormat::Sarif => {
+            let diagnostics = messages.iter().map(Diagnostic::from_message).collect::<Vec<_>>();
+            eprintln!("{}", serde_json::to_string_pretty(&render_sarif(&diagnostics)).unwrap());
+        }
+    }
+}
+
+/// Renders the diagnostics as a SARIF 2.1.0 log
+pub fn render_sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let results = diagnostics.iter().map(|diagnostic| {
+        let level = match diagnostic.severity {
+            "error" => "error",
+            "warning" => "warning",
+            _ => "note",
+        };
+        let text = [&diagnostic.message, &diagnostic.comment].iter()
+            .filter_map(|text| text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let locations = diagnostic.path.as_ref().map(|path| {
+            let mut location = json!({ "artifactLocation": { "uri": path } });
+            if let Some(span) = diagnostic.span {
+                location["region"] = json!({
+                    "startLine": span.start.line,
+                    "startColumn": span.start.column,
+                    "endLine": span.end.line,
+                    "endColumn": span.end.column,
+                });
+            }
+            vec![json!({ "physicalLocation": location })]
+        }).unwrap_or_default();
+        json!({
+            "level": level,
+            "message": { "text": text },
+            "locations": locations,
+        })
+    }).collect::<Vec<_>>();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "amber",
+                    "informationUri": "https://amber-lang.com/",
+                    "version": get_version(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
@@ -0,0 +1,242 @@
+use super::manifest::{Dependency, Manifest};
+use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Prefix of the imports resolved from the project dependencies
+pub const PACKAGE_PREFIX: &str = "pkg:";
+/// Directory of the fetched dependencies (relative to the manifest)
+pub const VENDOR_DIR: &str = "vendor";
+/// Name of the lockfile next to the manifest
+pub const LOCKFILE: &str = "amber.lock";
+
+/// Dependency pinned by the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    /// Where the dependency comes from ('git+<url>' or 'path+<path>')
+    pub source: String,
+    /// Revision requested in the manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Commit that the revision resolved to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Hash of the vendored files
+    pub hash: String,
+}
+
+/// Content of 'amber.lock'
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    /// Reads the lockfile of the project (empty if there is none yet)
+    pub fn load(manifest: &Manifest) -> Result<Lockfile, Message> {
+        let path = manifest.resolve(Path::new(LOCKFILE));
+        if !path.exists() {
+            return Ok(Lockfile::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|err| io_error(&path, err))?;
+        toml::from_str(&content).map_err(|err| {
+            Message::new_err_msg(format!("Invalid lockfile '{}'", path.display()))
+                .comment(err.message())
+        })
+    }
+
+    pub fn save(&self, manifest: &Manifest) -> Result<(), Message> {
+        let path = manifest.resolve(Path::new(LOCKFILE));
+        let content = toml::to_string(self).map_err(|err| Message::new_err_msg(err.to_string()))?;
+        let content = format!("# This file is generated by 'amber vendor', do not edit it by hand\n\n{content}");
+        fs::write(&path, content).map_err(|err| io_error(&path, err))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|package| package.name == name)
+    }
+}
+
+fn io_error(path: &Path, err: io::Error) -> Message {
+    Message::new_err_msg(format!("Couldn't access '{}'", path.display())).comment(err.to_string())
+}
+
+fn source_of(dependency: &Dependency) -> String {
+    match (&dependency.git, &dependency.path) {
+        (Some(url), _) => format!("git+{url}"),
+        (None, Some(path)) => format!("path+{}", path.display()),
+        (None, None) => String::new(),
+    }
+}
+
+/// Directory containing the vendored files of the package
+pub fn package_dir(manifest: &Manifest, name: &str) -> PathBuf {
+    manifest.resolve(Path::new(VENDOR_DIR)).join(name)
+}
+
+/// Fetches the dependencies into the vendor directory and pins them in the lockfile.
+/// The locked commits are reused unless `update` is set.
+pub fn vendor(manifest: &Manifest, update: bool) -> Result<Lockfile, Message> {
+    let previous = if update { Lockfile::default() } else { Lockfile::load(manifest)? };
+    let mut lockfile = Lockfile::default();
+    for (name, dependency) in manifest.dependencies.iter() {
+        let source = source_of(dependency);
+        let locked = previous.get(name)
+            .filter(|locked| locked.source == source && locked.rev == dependency.rev);
+        // The package is fetched next to the vendored one, which is kept until the new content is verified
+        let staging = manifest.resolve(Path::new(VENDOR_DIR)).join(format!(".{name}.partial"));
+        remove_dir(&staging)?;
+        let (commit, hash) = match fetch_package(manifest, name, dependency, locked, &staging) {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                remove_dir(&staging)?;
+                return Err(err);
+            }
+        };
+        let dir = package_dir(manifest, name);
+        remove_dir(&dir)?;
+        fs::rename(&staging, &dir).map_err(|err| io_error(&dir, err))?;
+        lockfile.packages.push(LockedPackage { name: name.clone(), source, rev: dependency.rev.clone(), commit, hash });
+    }
+    lockfile.save(manifest)?;
+    Ok(lockfile)
+}
+
+/// Fetches the package into the directory and returns its commit and the hash checked against the lockfile
+fn fetch_package(manifest: &Manifest, name: &str, dependency: &Dependency, locked: Option<&LockedPackage>, dir: &Path) -> Result<(Option<String>, String), Message> {
+    let commit = match (&dependency.git, &dependency.path) {
+        (Some(url), _) => {
+            let rev = locked.and_then(|locked| locked.commit.clone()).or(dependency.rev.clone());
+            Some(fetch_git(url, rev.as_deref(), dir)?)
+        }
+        (None, Some(path)) => {
+            copy_dir(&manifest.resolve(path), dir).map_err(|err| io_error(path, err))?;
+            None
+        }
+        (None, None) => None,
+    };
+    let hash = content_hash(dir).map_err(|err| io_error(dir, err))?;
+    // Local libraries change by design, only the fetched ones have to stay the same
+    if let Some(locked) = locked.filter(|_| dependency.git.is_some()) {
+        if locked.hash != hash {
+            return Err(Message::new_err_msg(format!("Content of package '{name}' does not match the lockfile"))
+                .comment(format!("Expected {}, found {hash}. Run 'amber vendor --update' to accept the change", locked.hash)));
+        }
+    }
+    Ok((commit, hash))
+}
+
+fn remove_dir(dir: &Path) -> Result<(), Message> {
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(|err| io_error(dir, err))?;
+    }
+    Ok(())
+}
+
+fn git(args: &[&str]) -> Result<String, Message> {
+    let output = Command::new("git").args(args).output()
+        .map_err(|err| Message::new_err_msg("Couldn't run git").comment(err.to_string()))?;
+    if !output.status.success() {
+        let msg = format!("Command 'git {}' failed", args.join(" "));
+        return Err(Message::new_err_msg(msg).comment(String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Checks out the revision of the repository without the git metadata and returns its commit
+fn fetch_git(url: &str, rev: Option<&str>, dir: &Path) -> Result<String, Message> {
+    let dir_str = dir.to_string_lossy();
+    git(&["clone", "--quiet", url, &dir_str])?;
+    if let Some(rev) = rev {
+        git(&["-C", &dir_str, "-c", "advice.detachedHead=false", "checkout", "--quiet", rev])?;
+    }
+    let commit = git(&["-C", &dir_str, "rev-parse", "HEAD"])?;
+    let git_dir = dir.join(".git");
+    fs::remove_dir_all(&git_dir).map_err(|err| io_error(&git_dir, err))?;
+    Ok(commit)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Hashes the relative paths and contents of all files in the directory
+pub fn content_hash(dir: &Path) -> io::Result<String> {
+    let mut files = vec![];
+    collect_files(dir, &mut files)?;
+    let mut files = files.into_iter()
+        .map(|path| {
+            let relative = path.strip_prefix(dir).unwrap_or(&path);
+            let name = relative.components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            (name, path)
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    let mut hasher = Sha256::new();
+    for (name, path) in files {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(fs::read(path)?);
+        hasher.update([0]);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+/// Resolves 'pkg:<name>/<path>' to the vendored file of the project owning the importing file
+pub fn resolve_import(import: &str, importer: Option<&str>) -> Result<PathBuf, Message> {
+    let import = import.strip_prefix(PACKAGE_PREFIX).unwrap_or(import);
+    let (name, path) = import.split_once('/').unwrap_or((import, "main"));
+    let dir = importer
+        .and_then(|importer| Path::new(importer).parent())
+        .unwrap_or(Path::new(""));
+    // Relative paths could hide the manifest of a parent directory
+    let dir = std::env::current_dir().map_or_else(|_| dir.to_path_buf(), |current| current.join(dir));
+    let Some(manifest_path) = Manifest::find(&dir) else {
+        return Err(Message::new_err_msg(format!("Couldn't find the manifest declaring package '{name}'")));
+    };
+    let manifest = Manifest::load(&manifest_path)?;
+    if !manifest.dependencies.contains_key(name) {
+        return Err(Message::new_err_msg(format!("Package '{name}' is not declared in '{}'", manifest_path.display())));
+    }
+    let package = package_dir(&manifest, name);
+    if !package.is_dir() {
+        return Err(Message::new_err_msg(format!("Package '{name}' is not vendored"))
+            .comment("Run 'amber vendor' to fetch the dependencies"));
+    }
+    let path = package.join(path);
+    Ok(if path.extension().is_none() { path.with_extension("ab") } else { path })
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the comments that mark the origin of the following lines in the generated code
+pub const SOURCE_MARKER: &str = "@amber-source ";
+
+/// Relates a line of the generated code to a line of the Amber source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mapping {
+    /// Line in the generated code (starting from 1)
+    pub line: usize,
+    /// Index of the source file in the list of sources
+    pub source: usize,
+    /// Line in the source file (starting from 1)
+    pub source_line: usize,
+}
+
+/// Source map of the generated code
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceMap {
+    pub sources: Vec<String>,
+    pub mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    /// Removes the source markers from the generated code and builds the source map out of them
+    pub fn extract(code: &str, sources: Vec<String>) -> (String, SourceMap) {
+        let mut lines = vec![];
+        let mut mappings = vec![];
+        let mut current = None;
+        for line in code.split('\n') {
+            let marker = line.trim_start()
+                .strip_prefix("# ")
+                .and_then(|comment| comment.strip_prefix(SOURCE_MARKER));
+            if let Some(marker) = marker {
+                current = Self::parse_marker(marker);
+                continue;
+            }
+            lines.push(line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some((source, source_line)) = current {
+                mappings.push(Mapping { line: lines.len(), source, source_line });
+            }
+        }
+        (lines.join("\n"), SourceMap { sources, mappings })
+    }
+
+    fn parse_marker(marker: &str) -> Option<(usize, usize)> {
+        let (source, line) = marker.split_once(':')?;
+        Some((source.parse().ok()?, line.parse().ok()?))
+    }
+
+    /// Renders the comment that marks the origin of the following lines
+    pub fn marker(source: usize, source_line: usize) -> String {
+        format!("{SOURCE_MARKER}{source}:{source_line}")
+    }
+
+    /// Finds the source file and line that the given line of the generated code originates from
+    pub fn resolve(&self, line: usize) -> Option<(&str, usize)> {
+        let mapping = self.mappings.iter().find(|mapping| mapping.line == line)?;
+        let source = self.sources.get(mapping.source)?;
+        Some((source.as_str(), mapping.source_line))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Source map can always be serialized")
+    }
+
+    pub fn from_json(json: &str) -> Result<SourceMap, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
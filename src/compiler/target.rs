@@ -0,0 +1,69 @@
+use clap::ValueEnum;
+use std::fmt;
+use std::str::FromStr;
+
+/// Shell dialect of the generated script
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Target {
+    /// Bash script
+    #[default]
+    Bash,
+    /// POSIX compliant shell such as `dash` or BusyBox `ash`.
+    /// Features that rely on Bash (like arrays) are reported as errors.
+    /// Commands written directly in the standard library are not checked.
+    Posix,
+}
+
+impl Target {
+    pub fn shebang(&self) -> &'static str {
+        match self {
+            Target::Bash => "#!/usr/bin/env bash",
+            Target::Posix => "#!/bin/sh",
+        }
+    }
+
+    pub fn is_posix(&self) -> bool {
+        *self == Target::Posix
+    }
+}
+
+/// Oldest Bash version that the generated code has to run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BashVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl BashVersion {
+    /// Version shipped with macOS and the oldest one that Amber supports
+    pub const OLDEST: BashVersion = BashVersion::new(3, 2);
+    /// Negative array indices count from the end of the array since this version
+    pub const NEGATIVE_INDEX: BashVersion = BashVersion::new(4, 3);
+    /// Associative arrays can be declared as global from within functions (`declare -gA`) since this version
+    pub const ASSOCIATIVE_ARRAY: BashVersion = BashVersion::new(4, 2);
+
+    pub const fn new(major: u32, minor: u32) -> Self {
+        BashVersion { major, minor }
+    }
+}
+
+impl FromStr for BashVersion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = value.split_once('.').unwrap_or((value, "0"));
+        let parse = |part: &str| part.parse::<u32>()
+            .map_err(|_| format!("Invalid Bash version '{value}'. Expected a version like '3.2'"));
+        let version = BashVersion::new(parse(major)?, parse(minor)?);
+        if version < BashVersion::OLDEST {
+            return Err(format!("Bash versions older than {} are not supported", BashVersion::OLDEST));
+        }
+        Ok(version)
+    }
+}
+
+impl fmt::Display for BashVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
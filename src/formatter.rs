@@ -0,0 +1,294 @@
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use heraclitus_compiler::prelude::*;
+use itertools::Itertools;
+
+const INDENT: &str = "    ";
+
+/// Keywords that are followed by an expression and therefore separated from parentheses
+const SPACED_KEYWORDS: [&str; 27] = [
+    "and", "as", "assert", "assert_eq", "const", "echo", "else", "fail", "for", "from",
+    "fun", "if", "import", "in", "is", "let", "loop", "match", "not",
+    "or", "pub", "ref", "return", "silent", "sudo", "then", "trust",
+];
+
+/// Operators after which an unfinished statement continues on the next line
+const BINARY_OPERATORS: [&str; 20] = [
+    "+", "-", "*", "/", "%", "=", "==", "!=", "<", ">", "<=", ">=",
+    "+=", "-=", "*=", "/=", "%=", "and", "or", "then",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum PieceKind {
+    Token,
+    /// Text or command literal copied from the source as is
+    Literal,
+    Comment,
+}
+
+#[derive(Debug, Clone)]
+struct Piece {
+    text: String,
+    kind: PieceKind,
+    row: usize,
+    end_row: usize,
+}
+
+impl Piece {
+    fn is(&self, word: &str) -> bool {
+        self.kind == PieceKind::Token && self.text == word
+    }
+
+    fn is_keyword(&self) -> bool {
+        self.kind == PieceKind::Token && SPACED_KEYWORDS.contains(&self.text.as_str())
+    }
+
+    /// Determines if an expression could end with this piece
+    fn is_value_end(&self) -> bool {
+        match self.kind {
+            PieceKind::Literal => true,
+            PieceKind::Comment => false,
+            PieceKind::Token => {
+                let is_word = self.text.chars().all(|chr| chr.is_alphanumeric() || chr == '_');
+                (is_word && !self.is_keyword()) || self.is(")") || self.is("]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    pieces: Vec<Piece>,
+    blank_before: bool,
+}
+
+/// Formats Amber source code in the canonical style
+pub fn format_code(code: String, path: Option<String>) -> Result<String, Message> {
+    let has_shebang = code.starts_with("#!");
+    let compiler = AmberCompiler::new(code, path, CompilerOptions::default());
+    let tokens = compiler.tokenize()?;
+    // Make sure that we only format valid code
+    compiler.parse(tokens.clone())?;
+    let source = compiler.cc.code.clone().unwrap_or_default().chars().collect::<Vec<_>>();
+    let mut pieces = collect_pieces(&tokens, &source);
+    if has_shebang {
+        if let Some(piece) = pieces.first_mut() {
+            piece.text = piece.text.trim_start_matches("// ").to_string();
+        }
+    }
+    let mut lines = split_lines(pieces);
+    sort_imports(&mut lines);
+    Ok(render_lines(&lines))
+}
+
+/// Converts tokens to pieces, joining literal regions into a single piece
+fn collect_pieces(tokens: &[Token], source: &[char]) -> Vec<Piece> {
+    let mut pieces: Vec<Piece> = vec![];
+    let mut consumed = 0;
+    for token in tokens {
+        if token.start < consumed || token.word == "\n" {
+            continue;
+        }
+        let (row, _) = token.pos;
+        let piece = if token.word.starts_with("//") {
+            Piece { text: token.word.trim_end().to_string(), kind: PieceKind::Comment, row, end_row: row }
+        } else if token.word.starts_with('"') || token.word.starts_with('$') {
+            let end = scan_literal(source, token.start);
+            consumed = end;
+            let text = source[token.start..end].iter().collect::<String>();
+            let end_row = row + text.matches('\n').count();
+            Piece { text, kind: PieceKind::Literal, row, end_row }
+        } else {
+            Piece { text: token.word.clone(), kind: PieceKind::Token, row, end_row: row }
+        };
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Finds the end of a text or command literal starting at the given index
+fn scan_literal(source: &[char], start: usize) -> usize {
+    let delimiter = source[start];
+    let mut index = start + 1;
+    while index < source.len() {
+        match source[index] {
+            '\\' => index += 2,
+            '{' => index = scan_interpolation(source, index),
+            chr if chr == delimiter => return index + 1,
+            _ => index += 1,
+        }
+    }
+    source.len()
+}
+
+/// Finds the end of an interpolation starting at the given index
+fn scan_interpolation(source: &[char], start: usize) -> usize {
+    let mut index = start + 1;
+    while index < source.len() {
+        match source[index] {
+            '"' | '$' => index = scan_literal(source, index),
+            '{' => index = scan_interpolation(source, index),
+            '}' => return index + 1,
+            _ => index += 1,
+        }
+    }
+    source.len()
+}
+
+fn split_lines(pieces: Vec<Piece>) -> Vec<Line> {
+    let mut lines: Vec<Line> = vec![];
+    let mut last_row = None;
+    for piece in pieces {
+        match last_row {
+            Some(row) if piece.row <= row => {
+                lines.last_mut().unwrap().pieces.push(piece.clone());
+            }
+            _ => {
+                let blank_before = last_row.is_some_and(|row| piece.row > row + 1);
+                lines.push(Line { pieces: vec![piece.clone()], blank_before });
+            }
+        }
+        last_row = Some(piece.end_row);
+    }
+    lines
+}
+
+fn is_import(line: &Line) -> bool {
+    let mut words = line.pieces.iter().skip_while(|piece| piece.is("pub"));
+    words.next().is_some_and(|piece| piece.is("import"))
+        && line.pieces.last().is_some_and(|piece| piece.kind == PieceKind::Literal)
+}
+
+/// Sorts groups of consecutive imports by path and their imported names
+fn sort_imports(lines: &mut [Line]) {
+    let mut depth: usize = 0;
+    let mut index = 0;
+    while index < lines.len() {
+        let group_end = (index..lines.len())
+            .take_while(|at| depth == 0 && is_import(&lines[*at]) && (*at == index || !lines[*at].blank_before))
+            .last()
+            .map_or(index, |at| at + 1);
+        if group_end > index {
+            let group = &mut lines[index..group_end];
+            let first_blank = group[0].blank_before;
+            group.iter_mut().for_each(sort_import_names);
+            group.sort_by_key(|line| line.pieces.last().unwrap().text.clone());
+            group.iter_mut().for_each(|line| line.blank_before = false);
+            group[0].blank_before = first_blank;
+            index = group_end;
+            continue;
+        }
+        for piece in lines[index].pieces.iter() {
+            match piece.text.as_str() {
+                "{" | "(" | "[" if piece.kind == PieceKind::Token => depth += 1,
+                "}" | ")" | "]" if piece.kind == PieceKind::Token => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+}
+
+fn sort_import_names(line: &mut Line) {
+    let Some(begin) = line.pieces.iter().position(|piece| piece.is("{")) else {
+        return;
+    };
+    let Some(end) = line.pieces.iter().position(|piece| piece.is("}")) else {
+        return;
+    };
+    let names = line.pieces[begin + 1..end].split(|piece| piece.is(","))
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_vec())
+        .sorted_by_key(|name| name[0].text.clone())
+        .collect::<Vec<_>>();
+    let row = line.pieces[begin].row;
+    let comma = Piece { text: ",".to_string(), kind: PieceKind::Token, row, end_row: row };
+    #[allow(unstable_name_collisions)]
+    let names = names.into_iter().intersperse(vec![comma]).flatten();
+    line.pieces.splice(begin + 1..end, names);
+}
+
+fn render_lines(lines: &[Line]) -> String {
+    let mut result = String::new();
+    let mut depth: usize = 0;
+    let mut continues = false;
+    let mut previous_opens = true;
+    for line in lines {
+        let closers = line.pieces.iter()
+            .take_while(|piece| piece.is("}") || piece.is(")") || piece.is("]"))
+            .count();
+        let indent = depth.saturating_sub(closers) + usize::from(continues || is_ternary_branch(line));
+        if line.blank_before && !previous_opens && closers == 0 {
+            result.push('\n');
+        }
+        result.push_str(&INDENT.repeat(indent));
+        result.push_str(&render_line(&line.pieces));
+        result.push('\n');
+        for piece in line.pieces.iter().filter(|piece| piece.kind == PieceKind::Token) {
+            match piece.text.as_str() {
+                "{" | "(" | "[" => depth += 1,
+                "}" | ")" | "]" => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        let last = line.pieces.iter().rev().find(|piece| piece.kind != PieceKind::Comment);
+        // Unfinished expressions and single statement blocks continue on the next line
+        continues = last.is_some_and(|piece| {
+            piece.kind == PieceKind::Token && (piece.text == ":" || BINARY_OPERATORS.contains(&piece.text.as_str()))
+        });
+        previous_opens = last.is_some_and(|piece| piece.is("{") || piece.is("(") || piece.is("["));
+    }
+    result
+}
+
+/// Determines if the line starts with a branch of a ternary expression
+fn is_ternary_branch(line: &Line) -> bool {
+    match line.pieces.first() {
+        Some(piece) if piece.is("then") => true,
+        Some(piece) if piece.is("else") => !line.pieces.iter().any(|piece| piece.is(":") || piece.is("{")),
+        _ => false,
+    }
+}
+
+fn render_line(pieces: &[Piece]) -> String {
+    let mut result = String::new();
+    for (index, piece) in pieces.iter().enumerate() {
+        if index > 0 && needs_space(&pieces[..index], piece) {
+            result.push(' ');
+        }
+        result.push_str(&piece.text);
+    }
+    result
+}
+
+/// Determines if there should be a space between the preceding pieces and the next one
+fn needs_space(before: &[Piece], next: &Piece) -> bool {
+    let prev = before.last().unwrap();
+    let before_prev = before.len().checked_sub(2).map(|index| &before[index]);
+    let is_unary_minus = prev.is("-") && before_prev.map_or(true, |piece| !piece.is_value_end());
+    let is_inclusive_range = prev.is("=") && before_prev.is_some_and(|piece| piece.is(".."));
+    if next.kind == PieceKind::Comment {
+        return true;
+    }
+    if is_unary_minus || is_inclusive_range || prev.is("(") || prev.is("[") || prev.is(".") || prev.is("..") {
+        return false;
+    }
+    if [")", "]", ",", ";", ":", "?", ".."].iter().any(|word| next.is(word)) || (prev.is("..") && next.is("=")) {
+        return false;
+    }
+    // Leading dot of a number such as `.5` is separated like any other value
+    if next.is(".") {
+        return !prev.is_value_end();
+    }
+    // Lambdas have their arguments right after the keyword like `fun(x: Int)`
+    if prev.is("fun") && next.is("(") {
+        return false;
+    }
+    if next.is("(") || next.is("[") {
+        let is_accessed = prev.is_value_end() && !(prev.kind == PieceKind::Literal && next.is("("));
+        return !is_accessed;
+    }
+    if prev.is("{") && next.is("}") {
+        return false;
+    }
+    true
+}
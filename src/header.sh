@@ -1,3 +1,3 @@
-#!/usr/bin/env bash
+{{ shebang }}
 # Written in [Amber](https://amber-lang.com/)
 # version: {{ version }}
@@ -0,0 +1,91 @@
+use crate::compiler::message_format::{get_span, LineColumn};
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::utils::symbol_table::SymbolTable;
+use heraclitus_compiler::prelude::*;
+use serde_json::{json, Value};
+
+/// Result of running the compiler frontend over a single document
+pub struct Analysis {
+    pub messages: Vec<Message>,
+    pub symbols: Option<SymbolTable>,
+    /// Code as seen by the compiler
+    pub source: String,
+}
+
+impl Analysis {
+    /// Tokenizes, parses and type checks the document
+    pub fn run(path: &str, code: &str) -> Self {
+        let options = CompilerOptions {
+            collect_symbols: true,
+            ..CompilerOptions::default()
+        };
+        let compiler = AmberCompiler::new(code.to_string(), Some(path.to_string()), options);
+        let source = compiler.cc.code.clone().unwrap_or_default();
+        let result = compiler.tokenize()
+            .and_then(|tokens| compiler.parse(tokens))
+            .and_then(|(block, meta)| compiler.typecheck(block, meta));
+        match result {
+            Ok((_, meta)) => Analysis {
+                messages: meta.messages,
+                symbols: meta.symbol_table,
+                source,
+            },
+            Err(error) => Analysis {
+                messages: vec![error],
+                symbols: None,
+                source,
+            },
+        }
+    }
+
+    /// Converts compiler messages to LSP diagnostics for the given document
+    pub fn diagnostics(&self, path: &str) -> Vec<Value> {
+        self.messages.iter()
+            .map(|message| {
+                // Errors in imported files are shown at the import in this document
+                let position = message.trace.iter()
+                    .find(|pos| pos.path.as_deref() == Some(path));
+                let range = match position {
+                    Some(position) => position_to_range(position, &self.source),
+                    None => json!({
+                        "start": { "line": 0, "character": 0 },
+                        "end": { "line": 0, "character": 0 },
+                    }),
+                };
+                let severity = match message.kind {
+                    MessageType::Error => 1,
+                    MessageType::Warning => 2,
+                    MessageType::Info => 3,
+                };
+                let text = [&message.message, &message.comment].iter()
+                    .filter_map(|text| text.as_deref())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                json!({
+                    "range": range,
+                    "severity": severity,
+                    "source": "amber",
+                    "message": text,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Converts a position in the source code to an LSP range
+pub fn position_to_range(position: &PositionInfo, code: &str) -> Value {
+    let (begin, end) = match get_span(position, Some(code)) {
+        Some(span) => (span.start, span.end),
+        None => {
+            // Point at the end of the file
+            let line = code.lines().count().max(1);
+            let column = code.lines().last().map_or(0, |text| text.chars().count()) + 1;
+            let end = LineColumn { line, column };
+            (end, end)
+        }
+    };
+    json!({
+        "start": { "line": begin.line - 1, "character": begin.column - 1 },
+        "end": { "line": end.line - 1, "character": end.column - 1 },
+    })
+}
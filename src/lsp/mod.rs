@@ -0,0 +1,221 @@
+use crate::modules::variable::variable_name_keywords;
+use crate::utils::symbol_table::{SymbolId, SymbolTable};
+use analysis::{position_to_range, Analysis};
+use protocol::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+pub mod analysis;
+pub mod protocol;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const COMPLETION_KIND_FUNCTION: u8 = 3;
+const COMPLETION_KIND_VARIABLE: u8 = 6;
+const COMPLETION_KIND_KEYWORD: u8 = 14;
+
+struct Document {
+    path: String,
+    /// Symbols from the last document version that type checked successfully
+    symbols: Option<SymbolTable>,
+}
+
+/// Language server communicating over JSON-RPC
+pub struct LanguageServer {
+    documents: HashMap<String, Document>,
+    is_shutdown: bool,
+}
+
+impl LanguageServer {
+    pub fn new() -> Self {
+        LanguageServer {
+            documents: HashMap::new(),
+            is_shutdown: false,
+        }
+    }
+
+    /// Serves requests until the client sends the `exit` notification
+    pub fn run(&mut self, reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<()> {
+        while let Some(message) = read_message(reader)? {
+            let method = message["method"].as_str().unwrap_or_default().to_string();
+            if method == "exit" {
+                break;
+            }
+            let params = &message["params"];
+            match message.get("id").cloned() {
+                // Requests expect a response
+                Some(id) if !method.is_empty() => {
+                    let response = match self.handle_request(&method, params) {
+                        Some(result) => response(id, result),
+                        None => error_response(id, METHOD_NOT_FOUND, &format!("Method '{method}' is not supported")),
+                    };
+                    write_message(writer, &response)?;
+                }
+                // Responses to our requests are ignored
+                Some(_) => {}
+                None => {
+                    for message in self.handle_notification(&method, params) {
+                        write_message(writer, &message)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, method: &str, params: &Value) -> Option<Value> {
+        match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "completionProvider": { "triggerCharacters": [] },
+                },
+                "serverInfo": { "name": "amber", "version": crate::get_version() },
+            })),
+            "shutdown" => {
+                self.is_shutdown = true;
+                Some(Value::Null)
+            }
+            "textDocument/hover" => Some(self.hover(params).unwrap_or(Value::Null)),
+            "textDocument/definition" => Some(self.definition(params).unwrap_or(Value::Null)),
+            "textDocument/completion" => Some(self.completion(params)),
+            _ => None,
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        if self.is_shutdown {
+            return vec![];
+        }
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                vec![self.update_document(&uri, text)]
+            }
+            "textDocument/didChange" => {
+                // We only advertise full document synchronization
+                match params["contentChanges"].as_array().and_then(|changes| changes.last()) {
+                    Some(change) => {
+                        let text = change["text"].as_str().unwrap_or_default();
+                        vec![self.update_document(&uri, text)]
+                    }
+                    None => vec![],
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                vec![notification("textDocument/publishDiagnostics", json!({
+                    "uri": uri,
+                    "diagnostics": [],
+                }))]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn update_document(&mut self, uri: &str, text: &str) -> Value {
+        let path = uri_to_path(uri);
+        let analysis = Analysis::run(&path, text);
+        let diagnostics = analysis.diagnostics(&path);
+        let document = self.documents.entry(uri.to_string()).or_insert_with(|| Document {
+            path: path.clone(),
+            symbols: None,
+        });
+        if analysis.symbols.is_some() {
+            document.symbols = analysis.symbols;
+        }
+        notification("textDocument/publishDiagnostics", json!({
+            "uri": uri,
+            "diagnostics": diagnostics,
+        }))
+    }
+
+    /// Gets the document and the (row, column) position in Amber's one based coordinates
+    fn get_document_position(&self, params: &Value) -> Option<(&Document, (usize, usize))> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let line = params["position"]["line"].as_u64()? as usize;
+        let character = params["position"]["character"].as_u64()? as usize;
+        let document = self.documents.get(uri)?;
+        Some((document, (line + 1, character + 1)))
+    }
+
+    fn hover(&self, params: &Value) -> Option<Value> {
+        let (document, position) = self.get_document_position(params)?;
+        let symbols = document.symbols.as_ref()?;
+        let (_, definition) = symbols.find_at(&document.path, position)?;
+        Some(json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("```amber\n{}\n```", definition.signature),
+            }
+        }))
+    }
+
+    fn definition(&self, params: &Value) -> Option<Value> {
+        let (document, position) = self.get_document_position(params)?;
+        let symbols = document.symbols.as_ref()?;
+        let (_, definition) = symbols.find_at(&document.path, position)?;
+        let path = definition.position.path.as_ref()?;
+        // Standard library modules are embedded in the compiler
+        if !Path::new(path).exists() {
+            return None;
+        }
+        let code = std::fs::read_to_string(path).ok()?;
+        Some(json!({
+            "uri": path_to_uri(path),
+            "range": position_to_range(&definition.position, &code),
+        }))
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let mut items = vec![];
+        if let Some((document, position)) = self.get_document_position(params) {
+            let scope = document.symbols.as_ref()
+                .and_then(|symbols| symbols.scope_at(&document.path, position).map(|scope| (symbols, scope)));
+            if let Some((symbols, scope)) = scope {
+                for (name, kind) in scope.vars.iter() {
+                    items.push(json!({
+                        "label": name,
+                        "kind": COMPLETION_KIND_VARIABLE,
+                        "detail": kind.to_string(),
+                    }));
+                }
+                for name in scope.funs.iter() {
+                    let signature = symbols.definitions.iter()
+                        .find(|(id, definition)| matches!(id, SymbolId::Function(_)) && definition.name == *name)
+                        .map(|(_, definition)| definition.signature.clone());
+                    items.push(json!({
+                        "label": name,
+                        "kind": COMPLETION_KIND_FUNCTION,
+                        "detail": signature,
+                    }));
+                }
+            }
+        }
+        for keyword in variable_name_keywords() {
+            items.push(json!({
+                "label": keyword,
+                "kind": COMPLETION_KIND_KEYWORD,
+            }));
+        }
+        json!({ "isIncomplete": false, "items": items })
+    }
+}
+
+/// Runs the language server over the standard input and output
+pub fn run_stdio() -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    LanguageServer::new().run(&mut stdin.lock(), &mut stdout.lock())
+}
+
+impl Default for LanguageServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
@@ -0,0 +1,79 @@
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+/// Reads a single JSON-RPC message framed with a `Content-Length` header.
+/// Returns `None` when the input stream has been closed.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(length) = content_length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header"));
+    };
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Writes a single JSON-RPC message framed with a `Content-Length` header
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+}
+
+pub fn response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Converts a `file://` URI to a file system path
+pub fn uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut result = Vec::with_capacity(path.len());
+    let mut bytes = path.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next(), bytes.next()];
+            if let [Some(high), Some(low)] = hex {
+                if let Ok(value) = u8::from_str_radix(&format!("{}{}", high as char, low as char), 16) {
+                    result.push(value);
+                    continue;
+                }
+            }
+        }
+        result.push(byte);
+    }
+    String::from_utf8_lossy(&result).to_string()
+}
+
+/// Converts a file system path to a `file://` URI
+pub fn path_to_uri(path: &str) -> String {
+    let encoded = path.bytes().map(|byte| match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+        _ => format!("%{byte:02X}"),
+    }).collect::<String>();
+    format!("file://{encoded}")
+}
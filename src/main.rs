@@ -1,11 +1,15 @@
 mod compiler;
 mod docs;
+mod formatter;
+mod lsp;
 mod modules;
 mod rules;
 mod stdlib;
 mod translate;
 mod utils;
 mod optimizer;
+mod repl;
+mod watch;
 
 pub mod built_info {
     include!(concat!(env!("OUT_DIR"), "/built.rs"));
@@ -14,14 +18,22 @@ pub mod built_info {
 #[cfg(test)]
 pub mod tests;
 
+use crate::compiler::depfile::{render_depfile, Dependency};
+use crate::compiler::manifest::{Manifest, MANIFEST_FILE};
+use crate::compiler::message_format::{show_messages, MessageFormat};
+use crate::compiler::package;
+use crate::compiler::source_map::SourceMap;
+use crate::compiler::target::{BashVersion, Target};
+use crate::translate::compute::ArithType;
 use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::utils::module_cache::ModuleCache;
 use clap::{Args, CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
 use colored::Colorize;
 use heraclitus_compiler::prelude::*;
 use std::error::Error;
 use std::io::{prelude::*, stdin};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::{fs, io};
 
@@ -29,8 +41,13 @@ fn get_version() -> &'static str {
     built_info::GIT_VERSION.unwrap_or(built_info::PKG_VERSION)
 }
 
+const ENVIRONMENT_HELP: &str = "\
+Environment variables:
+  AMBER_CACHE_DIR  Directory of the cache of parsed modules (defaults to $XDG_CACHE_HOME/amber or ~/.cache/amber)
+  AMBER_NO_CACHE   Set to '1' or 'true' to disable the cache of parsed modules";
+
 #[derive(Parser, Clone, Debug)]
-#[command(version(get_version()), arg_required_else_help(true))]
+#[command(version(get_version()), arg_required_else_help(true), after_help = ENVIRONMENT_HELP)]
 struct Cli {
     #[command(subcommand)]
     command: Option<CommandKind>,
@@ -54,6 +71,8 @@ struct Cli {
 enum CommandKind {
     /// Execute Amber code fragment
     Eval(EvalCommand),
+    /// Start an interactive session
+    Repl,
     /// Execute Amber script
     Run(RunCommand),
     /// Check Amber script for errors
@@ -62,8 +81,18 @@ enum CommandKind {
     Build(BuildCommand),
     /// Generate Amber script documentation
     Docs(DocsCommand),
+    /// Format Amber scripts
+    Fmt(FmtCommand),
+    /// Resolve a line of the generated Bash code back to the Amber source
+    Trace(TraceCommand),
+    /// Run the test blocks of Amber scripts
+    Test(TestCommand),
+    /// Fetch the dependencies of the project into the vendor directory
+    Vendor(VendorCommand),
     /// Generate Bash completion script
     Completion,
+    /// Start the language server over standard input and output
+    Lsp,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -73,6 +102,7 @@ struct EvalCommand {
 }
 
 #[derive(Args, Clone, Debug)]
+#[command(after_help = ENVIRONMENT_HELP)]
 struct RunCommand {
     /// Input filename ('-' to read from stdin)
     input: PathBuf,
@@ -87,9 +117,26 @@ struct RunCommand {
     /// Argument also supports a wildcard match, like "*" or "b*chk"
     #[arg(long, verbatim_doc_comment)]
     no_proc: Vec<String>,
+
+    /// Format of the compiler messages
+    #[arg(long, value_enum, default_value_t)]
+    message_format: MessageFormat,
+
+    /// Program evaluating the math on numbers in the generated code
+    #[arg(long, value_enum, default_value_t)]
+    num_backend: ArithType,
+
+    /// Print the Amber stack trace when a failure is not handled
+    #[arg(long)]
+    trace_failures: bool,
+
+    /// Run again whenever the input or one of its imports changes
+    #[arg(long)]
+    watch: bool,
 }
 
 #[derive(Args, Clone, Debug)]
+#[command(after_help = ENVIRONMENT_HELP)]
 struct CheckCommand {
     /// Input filename ('-' to read from stdin)
     input: PathBuf,
@@ -100,12 +147,33 @@ struct CheckCommand {
     /// Argument also supports a wildcard match, like "*" or "b*chk"
     #[arg(long, verbatim_doc_comment)]
     no_proc: Vec<String>,
+
+    /// Format of the compiler messages
+    #[arg(long, value_enum, default_value_t)]
+    message_format: MessageFormat,
+
+    /// Shell dialect of the generated code
+    #[arg(long, value_enum, default_value_t)]
+    target: Target,
+
+    /// Oldest Bash version the generated code has to run on (e.g. '3.2' for macOS)
+    #[arg(long)]
+    bash_version: Option<BashVersion>,
+
+    /// Print the external commands that the script invokes instead of checking it
+    #[arg(long, conflicts_with = "watch")]
+    list_commands: bool,
+
+    /// Check again whenever the input or one of its imports changes
+    #[arg(long)]
+    watch: bool,
 }
 
 #[derive(Args, Clone, Debug)]
+#[command(after_help = ENVIRONMENT_HELP)]
 struct BuildCommand {
-    /// Input filename ('-' to read from stdin)
-    input: PathBuf,
+    /// Input filename ('-' to read from stdin, all entries of 'amber.toml' if not set)
+    input: Option<PathBuf>,
 
     /// Output filename ('-' to output to stdout)
     output: Option<PathBuf>,
@@ -117,9 +185,41 @@ struct BuildCommand {
     #[arg(long, verbatim_doc_comment)]
     no_proc: Vec<String>,
 
+    /// Format of the compiler messages
+    #[arg(long, value_enum, default_value_t)]
+    message_format: MessageFormat,
+
     /// Minify the output file
     #[arg(long)]
     minify: bool,
+
+    /// Shell dialect of the generated code
+    #[arg(long, value_enum, default_value_t)]
+    target: Target,
+
+    /// Oldest Bash version the generated code has to run on (e.g. '3.2' for macOS)
+    #[arg(long)]
+    bash_version: Option<BashVersion>,
+
+    /// Program evaluating the math on numbers in the generated code
+    #[arg(long, value_enum, default_value_t)]
+    num_backend: ArithType,
+
+    /// Write a source map relating the output lines to the Amber source
+    #[arg(long, requires = "input")]
+    source_map: Option<PathBuf>,
+
+    /// Print the Amber stack trace when a failure is not handled
+    #[arg(long)]
+    trace_failures: bool,
+
+    /// Write a Make-style depfile listing the files that the output was compiled from
+    #[arg(long, requires = "input")]
+    emit_deps: Option<PathBuf>,
+
+    /// Build again whenever the input or one of its imports changes
+    #[arg(long, requires = "input")]
+    watch: bool,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -135,6 +235,54 @@ struct DocsCommand {
     usage: bool,
 }
 
+#[derive(Args, Clone, Debug)]
+struct FmtCommand {
+    /// Input files or directories ('-' to read from stdin and write to stdout)
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Check if the files are formatted without modifying them
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+struct TestCommand {
+    /// Input file or directory (current directory by default)
+    input: Option<PathBuf>,
+
+    /// Run only the tests whose names contain this text
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Disable a postprocessor
+    /// Available postprocessors: 'bshchk'
+    /// To select multiple, pass multiple times with different values
+    /// Argument also supports a wildcard match, like "*" or "b*chk"
+    #[arg(long, verbatim_doc_comment)]
+    no_proc: Vec<String>,
+
+    /// Program evaluating the math on numbers in the generated code
+    #[arg(long, value_enum, default_value_t)]
+    num_backend: ArithType,
+}
+
+#[derive(Args, Clone, Debug)]
+struct VendorCommand {
+    /// Ignore the lockfile and fetch the latest matching revisions
+    #[arg(long)]
+    update: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+struct TraceCommand {
+    /// Source map generated with 'amber build --source-map'
+    map: PathBuf,
+
+    /// Line of the generated Bash code
+    line: usize,
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
     if let Some(command) = cli.command {
@@ -142,70 +290,296 @@ fn main() -> Result<(), Box<dyn Error>> {
             CommandKind::Eval(command) => {
                 handle_eval(command)?;
             }
+            CommandKind::Repl => {
+                repl::run()?;
+            }
             CommandKind::Run(command) => {
-                let options = CompilerOptions::from_args(&command.no_proc, false);
-                let (code, messages) = compile_input(command.input, options);
-                execute_output(code, command.args, messages)?;
+                handle_run(command)?;
             }
             CommandKind::Check(command) => {
-                let options = CompilerOptions::from_args(&command.no_proc, false);
-                compile_input(command.input, options);
+                handle_check(command);
             }
             CommandKind::Build(command) => {
-                let output = create_output(&command);
-                let options = CompilerOptions::from_args(&command.no_proc, command.minify);
-                let (code, _) = compile_input(command.input, options);
-                write_output(output, code);
+                handle_build(command)?;
             }
             CommandKind::Docs(command) => {
                 handle_docs(command)?;
             }
+            CommandKind::Fmt(command) => {
+                handle_fmt(command)?;
+            }
+            CommandKind::Trace(command) => {
+                handle_trace(command)?;
+            }
+            CommandKind::Test(command) => {
+                handle_test(command)?;
+            }
+            CommandKind::Vendor(command) => {
+                handle_vendor(command);
+            }
             CommandKind::Completion => {
                 handle_completion();
             }
+            CommandKind::Lsp => {
+                lsp::run_stdio()?;
+            }
         }
     } else if let Some(input) = cli.input {
         let options = CompilerOptions::from_args(&cli.no_proc, false);
-        let (code, messages) = compile_input(input, options);
+        let (code, messages) = compile_input(input, options, MessageFormat::Human);
         execute_output(code, cli.args, messages)?;
     }
     Ok(())
 }
 
-fn create_output(command: &BuildCommand) -> PathBuf {
-    if let Some(output) = &command.output {
+fn create_output(input: &Path, output: Option<&PathBuf>) -> PathBuf {
+    if let Some(output) = output {
         output.clone()
-    } else if command.input.as_os_str() == "-" {
-        command.input.clone()
+    } else if input.as_os_str() == "-" {
+        input.to_path_buf()
     } else {
-        command.input.with_extension("sh")
+        input.with_extension("sh")
     }
 }
 
-fn compile_input(input: PathBuf, options: CompilerOptions) -> (String, bool) {
-    let input = input.to_string_lossy().to_string();
+fn handle_build(command: BuildCommand) -> Result<(), Box<dyn Error>> {
+    let manifest = load_manifest();
+    let Some(input) = command.input.clone() else {
+        let Some(manifest) = manifest else {
+            let msg = format!("No input file given and no '{MANIFEST_FILE}' found");
+            Message::new_err_msg(msg).show();
+            std::process::exit(1);
+        };
+        if manifest.build.entries.is_empty() {
+            let msg = format!("No entries to build in '{}'", manifest.resolve(Path::new(MANIFEST_FILE)).display());
+            Message::new_err_msg(msg).show();
+            std::process::exit(1);
+        }
+        if let Some(dir) = &manifest.build.output_dir {
+            fs::create_dir_all(manifest.resolve(dir))?;
+        }
+        let cache = ModuleCache::new_shared_on_disk();
+        for (input, output) in manifest.targets() {
+            let options = build_options(&command, Some(&manifest)).with_module_cache(cache.clone());
+            let (code, _) = compile_input(input, options, command.message_format);
+            write_output(output, code);
+        }
+        return Ok(());
+    };
+    if !command.watch {
+        let options = build_options(&command, manifest.as_ref()).with_module_cache(ModuleCache::new_shared_on_disk());
+        if build_file(&command, input, options).is_none() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    check_watch_input(&input);
+    let cache = ModuleCache::new_shared_on_disk();
+    watch::watch(input.clone(), || {
+        let options = build_options(&command, manifest.as_ref()).with_module_cache(cache.clone());
+        let dependencies = build_file(&command, input.clone(), options)?;
+        println!("{}", format!("Built {}", create_output(&input, command.output.as_ref()).display()).green());
+        Some(watched_files(dependencies))
+    });
+    Ok(())
+}
+
+/// Writes the output of a single input with its source map and depfile
+fn build_file(command: &BuildCommand, input: PathBuf, options: CompilerOptions) -> Option<Vec<Dependency>> {
+    let output = create_output(&input, command.output.as_ref());
+    let (code, source_map, dependencies, _) = try_compile_input(input, options, command.message_format)?;
+    if let Some(path) = &command.emit_deps {
+        let depfile = render_depfile(&output.to_string_lossy(), &dependencies);
+        if let Err(err) = fs::write(path, depfile) {
+            handle_err(err);
+        }
+    }
+    write_output(output, code);
+    if let Some(path) = &command.source_map {
+        if let Err(err) = fs::write(path, source_map.to_json()) {
+            handle_err(err);
+        }
+    }
+    Some(dependencies)
+}
+
+fn handle_vendor(command: VendorCommand) {
+    let Some(manifest) = load_manifest() else {
+        Message::new_err_msg(format!("Couldn't find '{MANIFEST_FILE}'")).show();
+        std::process::exit(1);
+    };
+    match package::vendor(&manifest, command.update) {
+        Ok(lockfile) => {
+            for package in lockfile.packages.iter() {
+                let commit = package.commit.as_ref().map(|commit| format!(" ({commit})")).unwrap_or_default();
+                println!("Vendored {}{commit}", package.name);
+            }
+        }
+        Err(err) => {
+            err.show();
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Finds the project manifest in the current directory or its parents
+fn load_manifest() -> Option<Manifest> {
+    let path = Manifest::find(&std::env::current_dir().ok()?)?;
+    match Manifest::load(&path) {
+        Ok(manifest) => Some(manifest),
+        Err(err) => {
+            err.show();
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Takes the defaults from the manifest, the command line flags take precedence
+fn build_options(command: &BuildCommand, manifest: Option<&Manifest>) -> CompilerOptions {
+    let mut options = match manifest {
+        Some(manifest) => CompilerOptions::from_manifest(manifest),
+        None => CompilerOptions::from_args(&command.no_proc, command.minify),
+    };
+    if !command.no_proc.is_empty() {
+        options.no_proc = command.no_proc.clone();
+    }
+    options.minify |= command.minify;
+    options.with_target(command.target)
+        .with_bash_version(command.bash_version)
+        .with_num_backend(command.num_backend)
+        .with_source_map(command.source_map.is_some())
+        .with_trace_failures(command.trace_failures)
+}
+
+fn compile_input(input: PathBuf, options: CompilerOptions, format: MessageFormat) -> (String, bool) {
+    let (code, _, _, messages) = compile_input_with_dependencies(input, options, format);
+    (code, messages)
+}
+
+fn compile_input_with_dependencies(input: PathBuf, options: CompilerOptions, format: MessageFormat) -> (String, SourceMap, Vec<Dependency>, bool) {
+    try_compile_input(input, options, format).unwrap_or_else(|| std::process::exit(1))
+}
+
+/// Compiles the input and shows the messages, returns nothing if the compilation failed
+/// Reads the Amber code from the file or from stdin if the input is '-'
+fn read_input(input: &str) -> Option<String> {
     let amber_code = if input == "-" {
         let mut code = String::new();
-        match stdin().read_to_string(&mut code) {
-            Ok(_) => code,
-            Err(err) => handle_err(err),
-        }
+        stdin().read_to_string(&mut code).map(|_| code)
     } else {
-        match fs::read_to_string(&input) {
-            Ok(code) => code,
-            Err(err) => handle_err(err),
-        }
+        fs::read_to_string(input)
     };
+    match amber_code {
+        Ok(code) => Some(code),
+        Err(err) => {
+            Message::new_err_msg(err.to_string()).show();
+            None
+        }
+    }
+}
+
+fn try_compile_input(input: PathBuf, options: CompilerOptions, format: MessageFormat) -> Option<(String, SourceMap, Vec<Dependency>, bool)> {
+    let input = input.to_string_lossy().to_string();
+    let amber_code = read_input(&input)?;
     let compiler = AmberCompiler::new(amber_code, Some(input), options);
-    let (messages, bash_code) = match compiler.compile() {
+    let (messages, bash_code, source_map, dependencies) = match compiler.compile_with_dependencies() {
         Ok(result) => result,
         Err(err) => {
-            err.show();
-            std::process::exit(1);
+            show_messages(&[err], format);
+            return None;
+        }
+    };
+    show_messages(&messages, format);
+    // Only separate human readable messages from the script output
+    Some((bash_code, source_map, dependencies, !messages.is_empty() && format == MessageFormat::Human))
+}
+
+/// Files on disk that the code was compiled from
+fn watched_files(dependencies: Vec<Dependency>) -> Vec<PathBuf> {
+    dependencies.into_iter()
+        .filter(|dependency| !dependency.is_virtual)
+        .map(|dependency| PathBuf::from(dependency.path))
+        .collect()
+}
+
+fn check_watch_input(input: &Path) {
+    if input.as_os_str() == "-" {
+        Message::new_err_msg("Cannot watch the standard input for changes").show();
+        std::process::exit(1);
+    }
+}
+
+fn handle_run(command: RunCommand) -> Result<(), Box<dyn Error>> {
+    let options = || CompilerOptions::from_args(&command.no_proc, false)
+        .with_num_backend(command.num_backend)
+        .with_trace_failures(command.trace_failures);
+    if !command.watch {
+        let (code, messages) = compile_input(command.input, options(), command.message_format);
+        return execute_output(code, command.args, messages);
+    }
+    check_watch_input(&command.input);
+    let cache = ModuleCache::new_shared_on_disk();
+    watch::watch(command.input.clone(), || {
+        let options = options().with_module_cache(cache.clone());
+        let (code, _, dependencies, messages) = try_compile_input(command.input.clone(), options, command.message_format)?;
+        if messages {
+            render_dash();
+        }
+        match AmberCompiler::execute(code, command.args.clone()) {
+            Ok(status) if !status.success() => {
+                let code = status.code().map_or(String::from("signal"), |code| code.to_string());
+                println!("{}", format!("Exited with {code}").dimmed());
+            }
+            Ok(_) => (),
+            Err(err) => Message::new_err_msg(err.to_string()).show(),
         }
+        Some(watched_files(dependencies))
+    });
+    Ok(())
+}
+
+/// Prints the external commands that the compiled script invokes, one per line
+fn list_commands(input: PathBuf, options: CompilerOptions, format: MessageFormat) {
+    let input = input.to_string_lossy().to_string();
+    let Some(amber_code) = read_input(&input) else {
+        std::process::exit(1);
     };
-    messages.iter().for_each(|m| m.show());
-    (bash_code, !messages.is_empty())
+    let compiler = AmberCompiler::new(amber_code, Some(input), options);
+    match compiler.compile_with_commands() {
+        Ok((messages, commands)) => {
+            show_messages(&messages, format);
+            for command in commands {
+                println!("{command}");
+            }
+        }
+        Err(err) => {
+            show_messages(&[err], format);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_check(command: CheckCommand) {
+    let options = || CompilerOptions::from_args(&command.no_proc, false)
+        .with_target(command.target)
+        .with_bash_version(command.bash_version);
+    if command.list_commands {
+        list_commands(command.input, options(), command.message_format);
+        return;
+    }
+    if !command.watch {
+        let options = options().with_module_cache(ModuleCache::new_shared_on_disk());
+        compile_input(command.input, options, command.message_format);
+        return;
+    }
+    check_watch_input(&command.input);
+    let cache = ModuleCache::new_shared_on_disk();
+    watch::watch(command.input.clone(), || {
+        let options = options().with_module_cache(cache.clone());
+        let (_, _, dependencies, _) = try_compile_input(command.input.clone(), options, command.message_format)?;
+        println!("{}", "No errors found".green());
+        Some(watched_files(dependencies))
+    });
 }
 
 fn execute_output(code: String, args: Vec<String>, messages: bool) -> Result<(), Box<dyn Error>> {
@@ -274,6 +648,134 @@ fn handle_docs(command: DocsCommand) -> Result<(), Box<dyn Error>> {
     }
 }
 
+fn handle_fmt(command: FmtCommand) -> Result<(), Box<dyn Error>> {
+    let mut unformatted = vec![];
+    let mut failed = false;
+    for input in collect_amber_files(&command.inputs)? {
+        let path = input.to_string_lossy().to_string();
+        let code = if path == "-" {
+            let mut code = String::new();
+            stdin().read_to_string(&mut code)?;
+            code
+        } else {
+            fs::read_to_string(&input)?
+        };
+        let formatted = match formatter::format_code(code.clone(), Some(path.clone())) {
+            Ok(formatted) => formatted,
+            Err(err) => {
+                err.show();
+                failed = true;
+                continue;
+            }
+        };
+        if command.check {
+            if formatted != code {
+                unformatted.push(path);
+            }
+        } else if path == "-" {
+            print!("{formatted}");
+        } else if formatted != code {
+            fs::write(&input, formatted)?;
+        }
+    }
+    for path in unformatted.iter() {
+        println!("{path}");
+    }
+    if failed || !unformatted.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn handle_test(command: TestCommand) -> Result<(), Box<dyn Error>> {
+    let input = command.input.unwrap_or_else(|| PathBuf::from("."));
+    let (mut passed, mut failed) = (0, 0);
+    for file in collect_amber_files(&[input])? {
+        let path = file.to_string_lossy().to_string();
+        let code = fs::read_to_string(&file)?;
+        // Failing tests show where the failure happened
+        let options = CompilerOptions::from_args(&command.no_proc, false)
+            .with_test_mode(true)
+            .with_num_backend(command.num_backend)
+            .with_trace_failures(true);
+        let compiler = AmberCompiler::new(code, Some(path.clone()), options);
+        let (messages, code, tests) = match compiler.compile_tests() {
+            Ok(result) => result,
+            Err(err) => {
+                err.show();
+                failed += 1;
+                continue;
+            }
+        };
+        messages.iter().for_each(|message| message.show());
+        let tests = tests.into_iter()
+            .filter(|test| command.filter.as_ref().map_or(true, |filter| test.name.contains(filter)));
+        for test in tests {
+            // Every test runs in its own shell so that it cannot affect the others
+            let status = AmberCompiler::execute(format!("{code}\n{}", test.function), vec![])?;
+            if status.success() {
+                println!("test {path} '{}' ... {}", test.name, "ok".green());
+                passed += 1;
+            } else {
+                println!("test {path} '{}' ... {}", test.name, "FAILED".red());
+                failed += 1;
+            }
+        }
+    }
+    let result = if failed == 0 { "ok".green() } else { "FAILED".red() };
+    println!("\ntest result: {result}. {passed} passed; {failed} failed");
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn handle_trace(command: TraceCommand) -> Result<(), Box<dyn Error>> {
+    let json = match fs::read_to_string(&command.map) {
+        Ok(json) => json,
+        Err(err) => handle_err(err),
+    };
+    let source_map = match SourceMap::from_json(&json) {
+        Ok(source_map) => source_map,
+        Err(err) => {
+            Message::new_err_msg(format!("Invalid source map: {err}")).show();
+            std::process::exit(1);
+        }
+    };
+    let Some((path, line)) = source_map.resolve(command.line) else {
+        Message::new_err_msg(format!("Line {} does not originate from Amber code", command.line)).show();
+        std::process::exit(1);
+    };
+    println!("{path}:{line}");
+    // Show the source line when the file is still available
+    if let Ok(code) = fs::read_to_string(path) {
+        if let Some(source) = code.lines().nth(line - 1) {
+            println!("{}", source.trim());
+        }
+    }
+    Ok(())
+}
+
+/// Expands directories to the Amber files they contain
+fn collect_amber_files(inputs: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for input in inputs {
+        if input.is_dir() {
+            let mut entries = fs::read_dir(input)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+            let entries = entries.into_iter()
+                .filter(|path| path.is_dir() || path.extension().is_some_and(|ext| ext == "ab"))
+                .collect::<Vec<_>>();
+            files.extend(collect_amber_files(&entries)?);
+        } else {
+            files.push(input.clone());
+        }
+    }
+    Ok(files)
+}
+
 fn handle_completion() {
     let mut command = Cli::command();
     let name = command.get_name().to_string();
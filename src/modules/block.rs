@@ -5,14 +5,18 @@ use crate::modules::prelude::*;
 use heraclitus_compiler::prelude::*;
 use itertools::Itertools;
 use super::statement::stmt::Statement;
+use crate::utils::symbol_table::ScopeSnapshot;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub statements: Vec<Statement>,
     pub should_indent: bool,
     pub needs_noop: bool,
     pub parses_syntax: bool,
     pub is_conditional: bool,
+    /// Tokens opening and closing the block
+    pub range_tok: (Option<Token>, Option<Token>),
 }
 
 impl Block {
@@ -40,6 +44,25 @@ impl Block {
         self.parses_syntax = false;
         self
     }
+
+    // Save the names visible at the end of this block for completion
+    fn add_scope_snapshot(&self, meta: &mut ParserMetadata) {
+        if meta.symbol_table.is_none() {
+            return;
+        }
+        let range = match &self.range_tok {
+            (Some(begin), Some(end)) => Some((begin.pos, end.pos)),
+            _ => None,
+        };
+        let vars = meta.get_var_names().into_iter()
+            .filter_map(|name| meta.get_var(name).map(|var| (name.clone(), var.kind.clone())))
+            .collect();
+        let funs = meta.get_fun_names().into_iter().cloned().collect();
+        let scope = ScopeSnapshot { path: meta.context.path.clone(), range, vars, funs };
+        if let Some(table) = meta.symbol_table.as_mut() {
+            table.add_scope(scope);
+        }
+    }
 }
 
 impl SyntaxModule<ParserMetadata> for Block {
@@ -52,11 +75,13 @@ impl SyntaxModule<ParserMetadata> for Block {
             parses_syntax: true,
             needs_noop: false,
             is_conditional: false,
+            range_tok: (None, None),
         }
     }
 
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         let is_single_line = if self.parses_syntax {
+            self.range_tok.0 = meta.get_current_token();
             let parsed_word = token_by(meta, |word| [":", "{"].contains(&word.as_str()))?;
             parsed_word == ":"
         } else {
@@ -70,6 +95,7 @@ impl SyntaxModule<ParserMetadata> for Block {
             }
             // Handle block end
             if !is_single_line && self.parses_syntax && token(meta, "}").is_ok() {
+                self.range_tok.1 = meta.get_token_at(meta.get_index() - 1);
                 break;
             }
             let mut statement = Statement::new();
@@ -84,6 +110,7 @@ impl SyntaxModule<ParserMetadata> for Block {
             token(meta, ";").ok();
             // Handle single line
             if is_single_line {
+                self.range_tok.1 = meta.get_token_at(meta.get_index() - 1);
                 break;
             }
         }
@@ -99,6 +126,7 @@ impl TypeCheckModule for Block {
             for statement in &mut self.statements {
                 statement.typecheck(meta)?;
             }
+            self.add_scope_snapshot(meta);
             Ok(())
         })
     }
@@ -112,7 +140,9 @@ impl TranslateModule for Block {
         let result = {
             let mut statements = vec![];
             for statement in &self.statements {
+                let marker = statement.translate_source_marker(meta);
                 let statement = statement.translate(meta);
+                statements.extend(marker);
                 statements.extend(meta.stmt_queue.drain(..));
                 statements.push(statement);
             }
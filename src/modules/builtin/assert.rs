@@ -0,0 +1,135 @@
+use heraclitus_compiler::prelude::*;
+use crate::{fragments, raw_fragment};
+use crate::modules::condition::failure_handler::trace_location;
+use crate::modules::expression::binop::BinOp;
+use crate::modules::expression::binop::eq::Eq;
+use crate::modules::expression::expr::Expr;
+use crate::modules::prelude::*;
+use crate::modules::types::{Type, Typed};
+use crate::translate::compare::translate_array_equality;
+use crate::translate::compute::{translate_float_computation, ArithOp};
+use serde::{Deserialize, Serialize};
+
+/// Exits the script when the condition does not hold or the values differ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Assert {
+    value: Box<Expr>,
+    expected: Option<Box<Expr>>,
+    kind: Type,
+    location: String,
+}
+
+impl Assert {
+    fn translate_failure(&self, values: Vec<FragmentKind>) -> FragmentKind {
+        let location = self.location.replace('\'', "'\\''");
+        let format = if values.is_empty() {
+            "Assertion failed at %s\\n"
+        } else {
+            "Assertion failed at %s\\n  left: %s\\n  right: %s\\n"
+        };
+        let mut printf = vec![raw_fragment!("printf '{format}' '{location}'")];
+        for value in values {
+            printf.push(fragments!(" ", value));
+        }
+        printf.push(raw_fragment!(" >&2"));
+        BlockFragment::new(vec![
+            ListFragment::new(printf).to_frag(),
+            fragments!("exit 1"),
+        ], true).to_frag()
+    }
+
+    /// Compares the values stored in the variables and evaluates to '1' if they are equal
+    fn translate_equality(&self, meta: &mut TranslateMetadata, left: VarExprFragment, right: VarExprFragment) -> FragmentKind {
+        match self.kind {
+            Type::Int => ArithmeticFragment::new(left.to_frag(), ArithOp::Eq, right.to_frag()).to_frag(),
+            Type::Num => translate_float_computation(meta, ArithOp::Eq, Some(left.to_frag()), Some(right.to_frag())),
+            Type::Array(_) | Type::Struct(_) => translate_array_equality(left, right, false),
+            _ => SubprocessFragment::new(fragments!(
+                "[ \"_", left.with_quotes(false).to_frag(), "\" != \"_", right.with_quotes(false).to_frag(), "\" ]; echo $?"
+            )).to_frag(),
+        }
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for Assert {
+    syntax_name!("Assert");
+
+    fn new() -> Self {
+        Assert {
+            value: Box::new(Expr::new()),
+            expected: None,
+            kind: Type::Bool,
+            location: String::new(),
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let tok = meta.get_current_token();
+        if token(meta, "assert_eq").is_ok() {
+            syntax(meta, &mut *self.value)?;
+            token(meta, ",")?;
+            let mut expected = Expr::new();
+            syntax(meta, &mut expected)?;
+            self.expected = Some(Box::new(expected));
+        } else {
+            token(meta, "assert")?;
+            syntax(meta, &mut *self.value)?;
+        }
+        self.location = trace_location(&PositionInfo::from_token(meta, tok));
+        Ok(())
+    }
+}
+
+impl TypeCheckModule for Assert {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.value.typecheck(meta)?;
+        if let Some(expected) = &mut self.expected {
+            expected.typecheck(meta)?;
+            self.kind = Eq::typecheck_equality(meta, &self.value, expected)?;
+        } else if self.value.get_type() != Type::Bool {
+            let position = self.value.get_position();
+            return error_pos!(meta, position => {
+                message: "Builtin function `assert` can only be used with values of type Bool",
+                comment: format!("Given type: {}, expected type: {}", self.value.get_type(), Type::Bool)
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TranslateModule for Assert {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let value = self.value.translate(meta);
+        let Some(expected) = &self.expected else {
+            return BlockFragment::new(vec![
+                fragments!("if [ ", value, " = 0 ]; then"),
+                self.translate_failure(vec![]),
+                fragments!("fi"),
+            ], false).to_frag()
+        };
+        // Store the values so that they are evaluated only once
+        let expected = expected.translate(meta);
+        let left = VarStmtFragment::new("__assert_left", self.kind.clone(), value);
+        let right = VarStmtFragment::new("__assert_right", self.kind.clone(), expected);
+        let left_expr = VarExprFragment::from_stmt(&left);
+        let right_expr = VarExprFragment::from_stmt(&right);
+        let equality = self.translate_equality(meta, left_expr.clone(), right_expr.clone());
+        let values = vec![
+            left_expr.with_array_to_string(true).to_frag(),
+            right_expr.with_array_to_string(true).to_frag(),
+        ];
+        BlockFragment::new(vec![
+            left.to_frag(),
+            right.to_frag(),
+            fragments!("if [ ", equality, " = 0 ]; then"),
+            self.translate_failure(values),
+            fragments!("fi"),
+        ], false).to_frag()
+    }
+}
+
+impl DocumentationModule for Assert {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -3,8 +3,9 @@ use crate::modules::expression::expr::Expr;
 use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::modules::types::{Type, Typed};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cd {
     value: Expr,
 }
@@ -2,8 +2,9 @@ use crate::fragments;
 use crate::modules::expression::expr::Expr;
 use crate::modules::prelude::*;
 use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Echo {
     value: Box<Expr>,
 }
@@ -32,7 +33,12 @@ impl TypeCheckModule for Echo {
 
 impl TranslateModule for Echo {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
-        fragments!("echo ", self.value.translate(meta))
+        // POSIX `echo` may interpret backslashes in the printed value
+        if meta.target.is_posix() {
+            fragments!("printf '%s\\n' ", self.value.translate(meta))
+        } else {
+            fragments!("echo ", self.value.translate(meta))
+        }
     }
 }
 
@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::Path;
+use crate::raw_fragment;
+use crate::modules::prelude::*;
+use crate::modules::types::{Type, Typed};
+use crate::translate::module::TranslateModule;
+use crate::utils::{ParserMetadata, TranslateMetadata};
+use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Inlines the content of a file into the generated code at compile time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Embed {
+    path: String,
+    content: String,
+    token: Option<Token>,
+}
+
+impl Embed {
+    /// Finds a heredoc delimiter that does not occur as a line of the content
+    fn delimiter(&self) -> String {
+        let mut delimiter = String::from("__AMBER_EMBED");
+        while self.content.lines().any(|line| line == delimiter) {
+            delimiter.push('_');
+        }
+        delimiter
+    }
+}
+
+impl Typed for Embed {
+    fn get_type(&self) -> Type {
+        Type::Text
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for Embed {
+    syntax_name!("Embed");
+
+    fn new() -> Self {
+        Embed {
+            path: String::new(),
+            content: String::new(),
+            token: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        token(meta, "embed")?;
+        token(meta, "(")?;
+        self.token = meta.get_current_token();
+        let value = token_by(meta, |word| word.starts_with('"'))?;
+        if value.len() < 2 || !value.ends_with('"') {
+            return error!(meta, self.token.clone(), "Embedded path cannot interpolate expressions")
+        }
+        token(meta, ")")?;
+        // The path is relative to the file containing the embed
+        let mut path = meta.context.path.as_ref()
+            .map_or_else(|| Path::new("."), |path| Path::new(path))
+            .to_path_buf();
+        path.pop();
+        path.push(&value[1..value.len() - 1]);
+        self.path = path.to_string_lossy().to_string();
+        Ok(())
+    }
+}
+
+impl TypeCheckModule for Embed {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) => return error!(meta, self.token.clone() => {
+                message: format!("Could not read file '{}'", self.path),
+                comment: err.to_string()
+            })
+        };
+        meta.import_cache.add_embedded_file(self.path.clone());
+        Ok(())
+    }
+}
+
+impl TranslateModule for Embed {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let id = meta.gen_value_id();
+        let var_stmt = VarStmtFragment::new("__embed", Type::Text, FragmentKind::Empty).with_global_id(id);
+        let var_expr = meta.push_ephemeral_variable(var_stmt);
+        let name = var_expr.get_name();
+        let delimiter = self.delimiter();
+        let content = &self.content;
+        if meta.target.is_posix() {
+            // The dot keeps the command substitution from trimming the trailing newlines
+            meta.stmt_queue.push_back(raw_fragment!("{name}=$(cat <<'{delimiter}'\n{content}.\n{delimiter}\n)"));
+            meta.stmt_queue.push_back(raw_fragment!("{name}=\"${{{name}%.}}\""));
+        } else if content.ends_with('\n') {
+            meta.stmt_queue.push_back(raw_fragment!("IFS= read -r -d '' {name} <<'{delimiter}' || true\n{content}{delimiter}"));
+        } else {
+            // The heredoc has to end with a newline that is not part of the file
+            meta.stmt_queue.push_back(raw_fragment!("IFS= read -r -d '' {name} <<'{delimiter}' || true\n{content}\n{delimiter}"));
+            meta.stmt_queue.push_back(raw_fragment!("{name}=\"${{{name}%?}}\""));
+        }
+        var_expr.to_frag()
+    }
+}
+
+impl DocumentationModule for Embed {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
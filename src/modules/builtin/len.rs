@@ -6,8 +6,9 @@ use crate::modules::typecheck::TypeCheckModule;
 use crate::translate::module::TranslateModule;
 use crate::utils::{ParserMetadata, TranslateMetadata};
 use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Len {
     value: Box<Expr>,
 }
@@ -48,11 +49,11 @@ impl TypeCheckModule for Len {
         // Typecheck the expression first
         self.value.typecheck(meta)?;
         
-        if !matches!(self.value.get_type(), Type::Text | Type::Array(_)) {
+        if !matches!(self.value.get_type(), Type::Text | Type::Array(_) | Type::Map(_)) {
             let msg = self
                 .value
                 .get_error_message(meta)
-                .message("Length can only be applied to text or array types");
+                .message("Length can only be applied to text, array or map types");
             return Err(Failure::Loud(msg));
         }
         Ok(())
@@ -61,7 +62,13 @@ impl TypeCheckModule for Len {
 
 impl TranslateModule for Len {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
-        let value = self.value.translate(meta);
+        let value = match self.value.translate(meta) {
+            // Maps are counted in place instead of being copied first
+            FragmentKind::VarExpr(var) if var.kind.is_map() && var.index.is_none() => {
+                return var.with_length_getter(true).to_frag();
+            }
+            value => value,
+        };
         let id = meta.gen_value_id();
         let var_stmt = VarStmtFragment::new("__length", self.value.get_type(), value).with_global_id(id);
         meta.push_ephemeral_variable(var_stmt).with_length_getter(true).to_frag()
@@ -1,7 +1,9 @@
 pub mod cd;
 pub mod echo;
+pub mod embed;
 pub mod mv;
 pub mod nameof;
 pub mod exit;
 pub mod len;
 pub mod lines;
+pub mod assert;
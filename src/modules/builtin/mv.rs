@@ -7,8 +7,9 @@ use crate::modules::expression::expr::Expr;
 use crate::modules::prelude::*;
 use crate::modules::types::{Type, Typed};
 use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Mv {
     source: Box<Expr>,
     destination: Box<Expr>,
@@ -77,6 +78,7 @@ impl TranslateModule for Mv {
         swap(&mut is_silent, &mut meta.silenced);
         let silent = meta.gen_silent().to_frag();
         swap(&mut is_silent, &mut meta.silenced);
+        meta.use_command("mv");
         BlockFragment::new(vec![
             fragments!("mv ", source, " ", destination, silent),
             handler,
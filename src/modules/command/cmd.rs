@@ -3,13 +3,18 @@ use crate::modules::condition::failure_handler::FailureHandler;
 use crate::modules::expression::expr::Expr;
 use crate::modules::expression::interpolated_region::{InterpolatedRegionType, parse_interpolated_region};
 use super::modifier::CommandModifier;
+use super::lint::lint_command;
 use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
+use crate::utils::commands::get_invoked_commands;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Command {
     strings: Vec<String>,
     interps: Vec<Expr>,
+    /// Tokens of the strings used for the warnings
+    tokens: Vec<Token>,
     modifier: CommandModifier,
     failure_handler: FailureHandler
 }
@@ -27,6 +32,7 @@ impl SyntaxModule<ParserMetadata> for Command {
         Command {
             strings: vec![],
             interps: vec![],
+            tokens: vec![],
             modifier: CommandModifier::new_expr(),
             failure_handler: FailureHandler::new()
         }
@@ -36,7 +42,7 @@ impl SyntaxModule<ParserMetadata> for Command {
         syntax(meta, &mut self.modifier)?;
         self.modifier.use_modifiers(meta, |_this, meta| {
             let tok = meta.get_current_token();
-            (self.strings, self.interps) = parse_interpolated_region(meta, &InterpolatedRegionType::Command)?;
+            (self.strings, self.interps, self.tokens) = parse_interpolated_region(meta, &InterpolatedRegionType::Command)?;
 
             // Set position for failure handler
             let position = PositionInfo::from_between_tokens(meta, tok.clone(), meta.get_current_token());
@@ -63,6 +69,7 @@ impl TypeCheckModule for Command {
         for interp in self.interps.iter_mut() {
             interp.typecheck(meta)?;
         }
+        lint_command(meta, &self.strings, &self.interps, &self.tokens);
         self.failure_handler.typecheck(meta)
     }
 }
@@ -76,6 +83,9 @@ impl TranslateModule for Command {
                         .map(|item| item.translate(meta).with_quotes(false))
                         .collect::<Vec<FragmentKind>>();
 
+                    for command in get_invoked_commands(&self.strings) {
+                        meta.use_command(&command);
+                    }
                     let translation = InterpolableFragment::new(
                         self.strings.clone(),
                         interps,
@@ -0,0 +1,327 @@
+use heraclitus_compiler::prelude::*;
+use std::collections::HashSet;
+use crate::modules::expression::expr::Expr;
+use crate::utils::cc_flags::{CCFlags, get_ccflag_name};
+use crate::utils::metadata::ParserMetadata;
+
+/// Words that can precede the command name
+const PREFIXES: [&str; 11] = ["!", "{", "do", "elif", "else", "exec", "if", "then", "time", "until", "while"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+/// Index of the string and the offset of the letter in it
+type Location = (usize, usize);
+
+/// Part of a shell word written in the command literal
+#[derive(Debug, Clone)]
+enum Piece {
+    /// Letter coming from the given location
+    Letter(char, Location),
+    /// Interpolation with the given index
+    Interp(usize),
+}
+
+/// Shell word written in the command literal
+#[derive(Debug, Clone, Default)]
+struct Word {
+    pieces: Vec<Piece>,
+}
+
+impl Word {
+    /// Returns the text of the word if it does not contain interpolations
+    fn as_text(&self) -> Option<String> {
+        self.pieces.iter()
+            .map(|piece| match piece {
+                Piece::Letter(letter, _) => Some(*letter),
+                Piece::Interp(_) => None,
+            })
+            .collect()
+    }
+
+    fn get_interps(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pieces.iter().filter_map(|piece| match piece {
+            Piece::Letter(..) => None,
+            Piece::Interp(index) => Some(*index),
+        })
+    }
+
+    fn first_letter(&self) -> Option<Location> {
+        self.pieces.iter().find_map(|piece| match piece {
+            Piece::Letter(_, location) => Some(*location),
+            Piece::Interp(_) => None,
+        })
+    }
+}
+
+/// Simple command written in the command literal
+#[derive(Debug)]
+struct Segment {
+    words: Vec<Word>,
+    /// Whether the command runs in a subshell or a command substitution
+    is_subshell: bool,
+}
+
+/// Result of splitting a command literal the way the shell does
+#[derive(Debug, Default)]
+struct Script {
+    /// Simple commands separated by `|`, `;`, `&`, new lines and parentheses
+    segments: Vec<Segment>,
+    /// Interpolations that are not wrapped in quotes
+    unquoted: Vec<usize>,
+    /// Locations of the backticks
+    backticks: Vec<Location>,
+}
+
+impl Script {
+    fn parse(strings: &[String], interps_len: usize) -> Self {
+        let mut script = Script::default();
+        let mut segment: Vec<Word> = vec![];
+        let mut word = Word::default();
+        // Quotes opened so far, where `Quote::None` stands for a subshell or a command substitution
+        let mut quotes: Vec<Quote> = vec![];
+        let mut escaped = false;
+        let mut in_backticks = false;
+        for (index, string) in strings.iter().enumerate() {
+            let mut letters = string.chars().enumerate().peekable();
+            while let Some((offset, letter)) = letters.next() {
+                let location = (index, offset);
+                if escaped {
+                    escaped = false;
+                    word.pieces.push(Piece::Letter(letter, location));
+                    continue;
+                }
+                let quote = quotes.last().copied().unwrap_or(Quote::None);
+                match (quote, letter) {
+                    (Quote::Single, '\'') | (Quote::Double, '"') => {
+                        quotes.pop();
+                    }
+                    (Quote::Single, _) => word.pieces.push(Piece::Letter(letter, location)),
+                    (_, '\\') => escaped = true,
+                    (_, '`') => {
+                        if !in_backticks {
+                            script.backticks.push(location);
+                        }
+                        in_backticks = !in_backticks;
+                    }
+                    (_, '$') if letters.peek().is_some_and(|(_, next)| *next == '(') => {
+                        letters.next();
+                        push_word(&mut segment, &mut word);
+                        script.push_segment(&mut segment, &quotes);
+                        quotes.push(Quote::None);
+                    }
+                    (Quote::Double, _) => word.pieces.push(Piece::Letter(letter, location)),
+                    (Quote::None, '\'') => quotes.push(Quote::Single),
+                    (Quote::None, '"') => quotes.push(Quote::Double),
+                    (Quote::None, _) if letter.is_whitespace() || "|;&()".contains(letter) => {
+                        push_word(&mut segment, &mut word);
+                        if letter != ' ' && letter != '\t' {
+                            script.push_segment(&mut segment, &quotes);
+                        }
+                        match letter {
+                            '(' => quotes.push(Quote::None),
+                            ')' if quotes.last() == Some(&Quote::None) => {
+                                quotes.pop();
+                            }
+                            _ => {}
+                        }
+                    }
+                    (Quote::None, _) => word.pieces.push(Piece::Letter(letter, location)),
+                }
+            }
+            if index < interps_len {
+                escaped = false;
+                if matches!(quotes.last(), None | Some(Quote::None)) {
+                    script.unquoted.push(index);
+                }
+                word.pieces.push(Piece::Interp(index));
+            }
+        }
+        push_word(&mut segment, &mut word);
+        script.push_segment(&mut segment, &quotes);
+        script
+    }
+
+    fn push_segment(&mut self, words: &mut Vec<Word>, quotes: &[Quote]) {
+        if !words.is_empty() {
+            let is_subshell = quotes.contains(&Quote::None);
+            self.segments.push(Segment { words: std::mem::take(words), is_subshell });
+        }
+    }
+}
+
+fn push_word(segment: &mut Vec<Word>, word: &mut Word) {
+    if !word.pieces.is_empty() {
+        segment.push(std::mem::take(word));
+    }
+}
+
+/// Returns the name of the command and its arguments
+fn split_command(segment: &[Word]) -> Option<(&Word, &[Word])> {
+    let position = segment.iter().position(|word| {
+        // Skip the variables set for the command (eg. `LANG=C sort`)
+        word.as_text().map_or(true, |text| !text.contains('=') && !PREFIXES.contains(&text.as_str()))
+    })?;
+    Some((&segment[position], &segment[position + 1..]))
+}
+
+fn is_recursive_flag(word: &Word) -> bool {
+    match word.as_text() {
+        Some(text) if text.starts_with("--") => text == "--recursive",
+        Some(text) => text.starts_with('-') && text.contains(['r', 'R']),
+        None => false,
+    }
+}
+
+/// Returns the interpolation that starts the path (eg. `{dir}/`)
+fn get_leading_interp(word: &Word) -> Option<usize> {
+    match word.pieces.as_slice() {
+        [Piece::Interp(index), Piece::Letter('/', _), ..] => Some(*index),
+        _ => None,
+    }
+}
+
+/// Returns the position of the letters at the given location of the command literal
+fn letter_pos(meta: &ParserMetadata, tokens: &[Token], (index, offset): Location, len: usize) -> PositionInfo {
+    let token = tokens.get(index).or(tokens.last()).cloned();
+    let (Some(token), Some(code)) = (token, meta.get_code()) else {
+        return PositionInfo::from_token(meta, tokens.last().cloned());
+    };
+    // The first string does not contain the leading `$` of its token
+    let mut letters = token.word.chars().enumerate().skip(usize::from(index == 0)).peekable();
+    let mut start = token.start + token.word.chars().count();
+    let mut count = 0;
+    while let Some((letter_start, letter)) = letters.next() {
+        if count == offset {
+            start = token.start + letter_start;
+            break;
+        }
+        // Escape sequences are written with two letters (see `parse_escaped_string`)
+        match (letter, letters.peek().map(|(_, next)| *next)) {
+            ('\\', Some('\n')) => {
+                letters.next();
+                continue;
+            }
+            ('\\', Some('\\' | 'n' | 't' | 'r' | '0' | '{' | '$')) => {
+                letters.next();
+            }
+            _ => {}
+        }
+        count += 1;
+    }
+    let before = code.chars().take(start).collect::<String>();
+    let row = before.matches('\n').count() + 1;
+    let col = before.chars().rev().take_while(|letter| *letter != '\n').count() + 1;
+    PositionInfo::at_pos(meta.get_path(), (row, col), start, len)
+}
+
+fn warn(meta: &mut ParserMetadata, pos: PositionInfo, flag: CCFlags, message: &str) {
+    if !meta.reported_lints.insert((pos.path.clone(), pos.start, flag)) {
+        return
+    }
+    let flag_name = get_ccflag_name(flag);
+    let message = Message::new_warn_at_position(meta, pos)
+        .message(message)
+        .comment(format!("To suppress this warning, use '{flag_name}' compiler flag"));
+    meta.add_message(message);
+}
+
+/// Warns about the common mistakes in the command literal
+pub fn lint_command(meta: &mut ParserMetadata, strings: &[String], interps: &[Expr], tokens: &[Token]) {
+    let script = Script::parse(strings, interps.len());
+    let is_allowed = |meta: &ParserMetadata, flag| meta.context.cc_flags.contains(&flag);
+
+    if !is_allowed(meta, CCFlags::AllowUnquotedInterpolation) {
+        // Words are not split in the conditional expressions
+        let conditional_interps = script.segments.iter()
+            .filter(|segment| split_command(&segment.words).is_some_and(|(name, _)| name.as_text().as_deref() == Some("[[")))
+            .flat_map(|segment| segment.words.iter())
+            .flat_map(Word::get_interps)
+            .collect::<HashSet<usize>>();
+        for &index in script.unquoted.iter() {
+            // Only the values that are known to contain whitespace or glob characters are reported
+            if interps[index].splits_into_words(meta) && !conditional_interps.contains(&index) {
+                let pos = interps[index].get_position();
+                warn(meta, pos, CCFlags::AllowUnquotedInterpolation,
+                    "Unquoted text interpolation is split into words by the shell, wrap it in double quotes");
+            }
+        }
+    }
+    if !is_allowed(meta, CCFlags::AllowBackticks) {
+        for &location in script.backticks.iter() {
+            let pos = letter_pos(meta, tokens, location, 1);
+            warn(meta, pos, CCFlags::AllowBackticks,
+                "Backticks are deprecated in favor of '$(...)' which can be nested and quoted safely");
+        }
+    }
+    for segment in script.segments.iter() {
+        let Some((name, args)) = split_command(&segment.words) else {
+            continue
+        };
+        match name.as_text().as_deref() {
+            // Changing the directory of a subshell does not affect the script
+            Some("cd") if !is_allowed(meta, CCFlags::AllowCdInCommand) && !segment.is_subshell => {
+                let pos = letter_pos(meta, tokens, name.first_letter().unwrap_or_default(), 2);
+                warn(meta, pos, CCFlags::AllowCdInCommand,
+                    "Changing the directory in a command literal, use the 'cd' builtin instead");
+            }
+            Some("rm") if !is_allowed(meta, CCFlags::AllowUnsafeRm) && args.iter().any(is_recursive_flag) => {
+                for index in args.iter().filter_map(get_leading_interp) {
+                    let pos = interps[index].get_position();
+                    warn(meta, pos, CCFlags::AllowUnsafeRm,
+                        "Recursive removal of a path starting with an interpolation, it removes the root directory if the value is empty");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(strings: &[&str]) -> Script {
+        let strings = strings.iter().map(|string| string.to_string()).collect::<Vec<_>>();
+        Script::parse(&strings, strings.len() - 1)
+    }
+
+    fn names(script: &Script) -> Vec<String> {
+        script.segments.iter()
+            .filter_map(|segment| split_command(&segment.words))
+            .map(|(name, _)| name.as_text().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_unquoted_interps() {
+        assert_eq!(parse(&["echo ", " \"", "\" '", "'"]).unquoted, [0]);
+        assert_eq!(parse(&["echo \"it's ", "\" \\\"", "\\\""]).unquoted, [1]);
+        assert_eq!(parse(&["echo \"`date`\" '`' ", " `pwd`"]).backticks, [(0, 6), (1, 1)]);
+    }
+
+    #[test]
+    fn test_command_names() {
+        assert_eq!(names(&parse(&["LANG=C cd dir && if true; then rm -rf dir; fi"])), ["cd", "true", "rm", "fi"]);
+        assert_eq!(names(&parse(&["echo 'a | b' \"c ; d\" && (cd dir)"])), ["echo", "cd"]);
+    }
+
+    #[test]
+    fn test_subshells() {
+        let script = parse(&["cd a && (cd b; ls) && echo \"$(cd c)\" && cd d"]);
+        let subshells = script.segments.iter().map(|segment| segment.is_subshell).collect::<Vec<_>>();
+        assert_eq!(subshells, [false, true, true, false, true, false]);
+    }
+
+    #[test]
+    fn test_unsafe_rm() {
+        let script = parse(&["rm -rf ", "/ \"", "\"/file ", "x/"]);
+        let (_, args) = split_command(&script.segments[0].words).unwrap();
+        assert!(is_recursive_flag(&args[0]));
+        assert_eq!(args.iter().filter_map(get_leading_interp).collect::<Vec<_>>(), [0, 1]);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod cmd;
+pub mod lint;
 pub mod modifier;
@@ -2,8 +2,9 @@ use std::mem::swap;
 use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::modules::block::Block;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandModifier {
     pub block: Option<Box<Block>>,
     pub is_trust: bool,
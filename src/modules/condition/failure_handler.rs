@@ -1,5 +1,5 @@
 use heraclitus_compiler::prelude::*;
-use heraclitus_compiler::compiling::failing::position_info::PositionInfo;
+use heraclitus_compiler::compiling::failing::position_info::{Position, PositionInfo};
 use crate::{fragments, raw_fragment};
 use crate::modules::prelude::*;
 use crate::modules::block::Block;
@@ -7,8 +7,30 @@ use crate::modules::types::Type;
 use crate::modules::variable::variable_name_extensions;
 use crate::utils::context::{VariableDecl, VariableDeclWarn};
 use crate::utils::metadata::ParserMetadata;
+use crate::utils::symbol_table::SymbolId;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq)]
+/// Renders the location of a failure in the Amber stack trace
+pub fn trace_location(position: &PositionInfo) -> String {
+    match position.position {
+        Position::Pos(row, col) => format!("{}:{row}:{col}", position.get_path()),
+        Position::EOF => position.get_path(),
+    }
+}
+
+/// Records the current function in the Amber stack trace of the propagated failure
+pub fn translate_trace_frame(meta: &TranslateMetadata, location: &str, is_origin: bool) -> FragmentKind {
+    let name = meta.fun_meta.as_ref().map_or("main", |fun_meta| fun_meta.get_name());
+    let frame = format!("{name} ({location})").replace('\'', "'\\''");
+    let push = raw_fragment!("__amber_trace_push '{frame}'");
+    if is_origin {
+        BlockFragment::new(vec![raw_fragment!("__amber_trace=\"\""), push], false).to_frag()
+    } else {
+        push
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FailureType {
     Failed,
     Succeeded,
@@ -25,7 +47,7 @@ impl FailureType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FailureHandler {
     pub is_parsed: bool,
     pub failure_type: FailureType,
@@ -36,7 +58,8 @@ pub struct FailureHandler {
     block: Box<Block>,
     param_name: String,
     param_name_tok: Option<Token>,
-    param_global_id: Option<usize>
+    param_global_id: Option<usize>,
+    trace_location: String,
 }
 
 impl FailureHandler {
@@ -63,7 +86,8 @@ impl SyntaxModule<ParserMetadata> for FailureHandler {
             block: Box::new(Block::new().with_needs_noop().with_condition()),
             param_name: String::new(),
             param_name_tok: None,
-            param_global_id: None
+            param_global_id: None,
+            trace_location: String::new(),
         }
     }
 
@@ -77,6 +101,9 @@ impl SyntaxModule<ParserMetadata> for FailureHandler {
             }
             self.is_question_mark = true;
             self.failure_type = FailureType::Failed;
+            let position = self.error_position.clone()
+                .unwrap_or_else(|| PositionInfo::from_token(meta, tok.clone()));
+            self.trace_location = trace_location(&position);
         } else {
             let keyword = ["failed", "succeeded", "exited"].iter().fold(None, |acc, keyword| {
                 acc.or_else(|| token(meta, keyword).ok().map(|_| *keyword))
@@ -204,6 +231,10 @@ impl TypeCheckModule for FailureHandler {
                 let var = VariableDecl::new(self.param_name.clone(), Type::Num)
                     .with_warn(VariableDeclWarn::from_token(meta, self.param_name_tok.clone()));
                 self.param_global_id = meta.add_var(var);
+                if let Some(id) = self.param_global_id {
+                    let signature = format!("{}: Num", self.param_name);
+                    meta.add_symbol_definition(SymbolId::Variable(id), &self.param_name, signature, self.param_name_tok.clone());
+                }
                 self.block.typecheck(meta)
             })
         } else {
@@ -236,6 +267,12 @@ impl TranslateModule for FailureHandler {
             } else {
                 FragmentKind::Empty
             };
+            // Failed commands start a new stack trace while failed functions have already recorded theirs
+            let trace = if meta.trace_failures {
+                translate_trace_frame(meta, &self.trace_location, self.function_name.is_none())
+            } else {
+                FragmentKind::Empty
+            };
             let ret = if self.is_main { "exit" } else { "return" };
             let ret = fragments!(raw_fragment!("{ret} "), status_variable_expr.clone().to_frag());
             return BlockFragment::new(vec![
@@ -243,13 +280,14 @@ impl TranslateModule for FailureHandler {
                 fragments!("if [ ", status_variable_expr.to_frag(), " != 0 ]; then"),
                 BlockFragment::new(vec![
                     clear_return,
+                    trace,
                     ret,
                 ], true).to_frag(),
                 fragments!("fi"),
             ], false).to_frag();
         }
 
-        match &block {
+        let handler = match &block {
             FragmentKind::Empty => {
                 status_variable_stmt.to_frag()
             },
@@ -309,6 +347,12 @@ impl TranslateModule for FailureHandler {
                     }
                 }
             }
+        };
+        // The failure of a function is handled here so its stack trace is discarded
+        if meta.trace_failures && self.function_name.is_some() {
+            BlockFragment::new(vec![handler, raw_fragment!("__amber_trace=\"\"")], false).to_frag()
+        } else {
+            handler
         }
     }
 }
@@ -5,8 +5,9 @@ use crate::modules::expression::expr::Expr;
 use crate::utils::cc_flags::{CCFlags, get_ccflag_name};
 use crate::modules::statement::stmt::{Statement, StmtType};
 use crate::modules::block::Block;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IfCondition {
     expr: Box<Expr>,
     true_block: Box<Block>,
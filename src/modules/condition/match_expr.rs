@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use std::mem;
+use heraclitus_compiler::prelude::*;
+use crate::modules::prelude::*;
+use crate::raw_fragment;
+use crate::modules::condition::match_stmt::{check_exhaustive, translate_pattern, typecheck_matched_value, typecheck_patterns};
+use crate::modules::expression::expr::Expr;
+use crate::modules::types::{Type, Typed};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchExpr {
+    expr: Box<Expr>,
+    arms: Vec<(Vec<Expr>, Expr)>,
+    else_expr: Option<Box<Expr>>,
+    kind: Type,
+    tok: Option<Token>,
+}
+
+impl MatchExpr {
+    fn skip_empty_lines(meta: &mut ParserMetadata) {
+        while token_by(meta, |token| token.starts_with("//") || token.starts_with('\n')).is_ok() {}
+    }
+
+    /// Evaluates the value only when its arm matches, along with the statements that it needs
+    fn translate_arm(meta: &mut TranslateMetadata, value: &Expr, var_stmt: &VarStmtFragment) -> FragmentKind {
+        let outer_queue = mem::take(&mut meta.stmt_queue);
+        let value = value.translate(meta);
+        let mut statements = Vec::from(mem::replace(&mut meta.stmt_queue, outer_queue));
+        statements.push(var_stmt.clone().with_value(value).to_frag());
+        BlockFragment::new(statements, true).with_condition(true).to_frag()
+    }
+
+    fn get_values(&self) -> impl Iterator<Item = &Expr> {
+        self.arms.iter().map(|(_, value)| value).chain(self.else_expr.as_deref())
+    }
+}
+
+impl Typed for MatchExpr {
+    fn get_type(&self) -> Type {
+        self.kind.clone()
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for MatchExpr {
+    syntax_name!("Match Expression");
+
+    fn new() -> Self {
+        MatchExpr {
+            expr: Box::new(Expr::new()),
+            arms: vec![],
+            else_expr: None,
+            kind: Type::Null,
+            tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        token(meta, "match")?;
+        syntax(meta, &mut *self.expr)?;
+        token(meta, "{")?;
+        loop {
+            Self::skip_empty_lines(meta);
+            // Handle else keyword
+            if token(meta, "else").is_ok() {
+                token(meta, ":")?;
+                let mut else_expr = Expr::new();
+                syntax(meta, &mut else_expr)?;
+                self.else_expr = Some(Box::new(else_expr));
+                Self::skip_empty_lines(meta);
+                if token(meta, "}").is_err() {
+                    return error!(meta, meta.get_current_token(), "Expected `else` arm to be the last in the match")
+                }
+                return Ok(())
+            }
+            // Handle end of the match
+            if token(meta, "}").is_ok() {
+                return Ok(())
+            }
+            let mut patterns = vec![];
+            loop {
+                let mut pattern = Expr::new();
+                syntax(meta, &mut pattern)?;
+                patterns.push(pattern);
+                if token(meta, ",").is_err() {
+                    break
+                }
+            }
+            token(meta, ":")?;
+            let mut value = Expr::new();
+            syntax(meta, &mut value)?;
+            self.arms.push((patterns, value));
+        }
+    }
+}
+
+impl TypeCheckModule for MatchExpr {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let kind = typecheck_matched_value(meta, &mut self.expr)?;
+        let mut matched = HashSet::new();
+        for (patterns, value) in &mut self.arms {
+            typecheck_patterns(meta, patterns, &kind, &mut matched)?;
+            value.typecheck(meta)?;
+        }
+        match &mut self.else_expr {
+            Some(value) => value.typecheck(meta)?,
+            // Text can always be left unmatched by the patterns
+            None if kind == Type::Text => {
+                return error!(meta, self.tok.clone() => {
+                    message: "Match expression on text must have an 'else' arm",
+                    comment: "The expression needs a value when none of the patterns matches"
+                })
+            }
+            None => check_exhaustive(meta, &kind, &matched, self.tok.clone())?,
+        }
+        // There is at least one arm as enums have variants and text needs the `else` arm
+        let value_type = self.get_values().next().map(Expr::get_type).unwrap_or(Type::Null);
+        if let Some(value) = self.get_values().find(|value| value.get_type() != value_type) {
+            let pos = value.get_position();
+            return error_pos!(meta, pos => {
+                message: "Match expression can only evaluate to value of one type",
+                comment: format!("Expected '{value_type}', found '{}'", value.get_type())
+            })
+        }
+        if value_type.is_map() {
+            return error!(meta, self.tok.clone(), "Match expression cannot evaluate to a map")
+        }
+        self.kind = value_type;
+        Ok(())
+    }
+}
+
+impl TranslateModule for MatchExpr {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let mut case = CaseFragment::new(self.expr.translate(meta));
+        // The variable is declared before the `case` and each arm assigns its own value to it
+        let id = meta.gen_value_id();
+        let var_stmt = VarStmtFragment::new("match", self.kind.clone(), FragmentKind::Empty).with_global_id(id);
+        let var_expr = meta.push_ephemeral_variable(var_stmt.clone());
+        for (patterns, value) in &self.arms {
+            let patterns = patterns.iter().map(|pattern| translate_pattern(pattern, meta)).collect();
+            case.add_arm(patterns, Self::translate_arm(meta, value, &var_stmt));
+        }
+        if let Some(value) = &self.else_expr {
+            case.add_arm(vec![raw_fragment!("*")], Self::translate_arm(meta, value, &var_stmt));
+        }
+        meta.stmt_queue.push_back(case.to_frag());
+        var_expr.to_frag()
+    }
+}
+
+impl DocumentationModule for MatchExpr {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use heraclitus_compiler::prelude::*;
+use itertools::Itertools;
+use crate::modules::prelude::*;
+use crate::raw_fragment;
+use crate::modules::block::Block;
+use crate::modules::expression::expr::{Expr, ExprType};
+use crate::modules::types::{Type, Typed};
+use crate::utils::pluralize;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Match {
+    expr: Box<Expr>,
+    arms: Vec<(Vec<Expr>, Block)>,
+    else_block: Option<Block>,
+    tok: Option<Token>,
+}
+
+/// Returns the name of the matched variant if the pattern is a variant of the given enum
+fn get_variant_name(pattern: &Expr, enum_name: &str) -> Option<String> {
+    match &pattern.value {
+        Some(ExprType::EnumVariant(variant)) if variant.name == enum_name => Some(variant.variant.clone()),
+        _ => None
+    }
+}
+
+/// Typechecks the matched value and returns its type if it can be matched
+pub fn typecheck_matched_value(meta: &mut ParserMetadata, expr: &mut Expr) -> Result<Type, Failure> {
+    expr.typecheck(meta)?;
+    match expr.get_type() {
+        kind @ (Type::Enum(_) | Type::Text) => Ok(kind),
+        kind => {
+            let pos = expr.get_position();
+            error_pos!(meta, pos => {
+                message: format!("Cannot match a value of type '{kind}'"),
+                comment: "Only values of text and enum types can be matched"
+            })
+        }
+    }
+}
+
+/// Typechecks the patterns of an arm and collects the enum variants that they match
+pub fn typecheck_patterns(meta: &mut ParserMetadata, patterns: &mut [Expr], kind: &Type, matched: &mut HashSet<String>) -> SyntaxResult {
+    for pattern in patterns.iter_mut() {
+        pattern.typecheck(meta)?;
+        if let Type::Enum(enum_name) = kind {
+            let Some(variant) = get_variant_name(pattern, enum_name) else {
+                let pos = pattern.get_position();
+                return error_pos!(meta, pos, format!("Expected a variant of enum '{enum_name}'"))
+            };
+            if !matched.insert(variant.clone()) {
+                let pos = pattern.get_position();
+                return error_pos!(meta, pos, format!("Variant '{enum_name}.{variant}' is already matched"))
+            }
+        } else if pattern.get_type() != Type::Text {
+            let pos = pattern.get_position();
+            return error_pos!(meta, pos, format!("Expected a pattern of type 'Text', found '{}'", pattern.get_type()))
+        }
+    }
+    Ok(())
+}
+
+/// Checks that all variants of the matched enum are handled when there is no `else` arm
+pub fn check_exhaustive(meta: &mut ParserMetadata, kind: &Type, matched: &HashSet<String>, tok: Option<Token>) -> SyntaxResult {
+    let Type::Enum(enum_name) = kind else {
+        return Ok(())
+    };
+    let variants = meta.get_enum_declaration(enum_name)
+        .map(|decl| decl.variants.clone())
+        .unwrap_or_default();
+    let missing = variants.iter()
+        .filter(|variant| !matched.contains(*variant))
+        .map(|variant| format!("'{enum_name}.{variant}'"))
+        .collect_vec();
+    if !missing.is_empty() {
+        let label = pluralize(missing.len(), "variant", "variants");
+        return error!(meta, tok => {
+            message: format!("Match on enum '{enum_name}' is not exhaustive"),
+            comment: format!("Missing {label} {}", missing.join(", "))
+        })
+    }
+    Ok(())
+}
+
+pub fn translate_pattern(pattern: &Expr, meta: &mut TranslateMetadata) -> FragmentKind {
+    match &pattern.value {
+        // Text literals can contain glob patterns
+        Some(ExprType::Text(text)) => text.translate_glob_pattern(meta),
+        _ => pattern.translate(meta),
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for Match {
+    syntax_name!("Match");
+
+    fn new() -> Self {
+        Match {
+            expr: Box::new(Expr::new()),
+            arms: vec![],
+            else_block: None,
+            tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        token(meta, "match")?;
+        syntax(meta, &mut *self.expr)?;
+        token(meta, "{")?;
+        loop {
+            // Handle comments and empty lines
+            if token_by(meta, |token| token.starts_with("//") || token.starts_with('\n')).is_ok() {
+                continue
+            }
+            // Handle else keyword
+            if token(meta, "else").is_ok() {
+                let mut else_block = Block::new().with_needs_noop().with_condition();
+                syntax(meta, &mut else_block)?;
+                self.else_block = Some(else_block);
+                while token_by(meta, |token| token.starts_with("//") || token.starts_with('\n')).is_ok() {}
+                if token(meta, "}").is_err() {
+                    return error!(meta, meta.get_current_token(), "Expected `else` arm to be the last in the match")
+                }
+                return Ok(())
+            }
+            // Handle end of the match
+            if token(meta, "}").is_ok() {
+                return Ok(())
+            }
+            let mut patterns = vec![];
+            loop {
+                let mut pattern = Expr::new();
+                syntax(meta, &mut pattern)?;
+                patterns.push(pattern);
+                if token(meta, ",").is_err() {
+                    break
+                }
+            }
+            let mut block = Block::new().with_needs_noop().with_condition();
+            syntax(meta, &mut block)?;
+            self.arms.push((patterns, block));
+        }
+    }
+}
+
+impl TypeCheckModule for Match {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let kind = typecheck_matched_value(meta, &mut self.expr)?;
+        let mut matched = HashSet::new();
+        for (patterns, block) in &mut self.arms {
+            typecheck_patterns(meta, patterns, &kind, &mut matched)?;
+            block.typecheck(meta)?;
+        }
+        match &mut self.else_block {
+            // The `else` arm handles all remaining variants
+            Some(block) => block.typecheck(meta)?,
+            None => check_exhaustive(meta, &kind, &matched, self.tok.clone())?,
+        }
+        Ok(())
+    }
+}
+
+impl TranslateModule for Match {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let mut case = CaseFragment::new(self.expr.translate(meta));
+        for (patterns, block) in &self.arms {
+            let patterns = patterns.iter().map(|pattern| translate_pattern(pattern, meta)).collect();
+            case.add_arm(patterns, block.translate(meta));
+        }
+        if let Some(block) = &self.else_block {
+            case.add_arm(vec![raw_fragment!("*")], block.translate(meta));
+        }
+        case.to_frag()
+    }
+}
+
+impl DocumentationModule for Match {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -1,3 +1,5 @@
 pub mod ifcond;
 pub mod ifchain;
+pub mod match_stmt;
+pub mod match_expr;
 pub mod failure_handler;
@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+use heraclitus_compiler::prelude::*;
+use crate::modules::prelude::*;
+use crate::modules::types::is_type_name;
+use crate::modules::variable::variable_name_extensions;
+use crate::utils::context::EnumDecl;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumDeclaration {
+    pub name: String,
+    pub variants: Vec<(String, Option<Token>)>,
+    pub is_public: bool,
+    tok: Option<Token>,
+    name_tok: Option<Token>,
+}
+
+impl EnumDeclaration {
+    fn skip_empty_lines(meta: &mut ParserMetadata) {
+        while token_by(meta, |word| word.starts_with('\n') || word.starts_with("//")).is_ok() {}
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for EnumDeclaration {
+    syntax_name!("Enum Declaration");
+
+    fn new() -> Self {
+        EnumDeclaration {
+            name: String::new(),
+            variants: vec![],
+            is_public: false,
+            tok: None,
+            name_tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        self.is_public = token(meta, "pub").is_ok();
+        token(meta, "enum")?;
+        self.name_tok = meta.get_current_token();
+        self.name = variable(meta, variable_name_extensions())?;
+        if !is_type_name(&self.name) {
+            return error!(meta, self.name_tok.clone() => {
+                message: format!("Enum name '{}' must be in pascal case", self.name),
+                comment: "Enum names start with an uppercase letter and contain only letters and digits, eg. 'JobState'"
+            })
+        }
+        context!({
+            token(meta, "{")?;
+            loop {
+                Self::skip_empty_lines(meta);
+                if token(meta, "}").is_ok() {
+                    break;
+                }
+                let variant_tok = meta.get_current_token();
+                let variant = variable(meta, variable_name_extensions())?;
+                self.variants.push((variant, variant_tok));
+                Self::skip_empty_lines(meta);
+                if token(meta, "}").is_ok() {
+                    break;
+                }
+                token(meta, ",")?;
+            }
+            Ok(())
+        }, |pos| {
+            error_pos!(meta, pos, format!("Failed to parse enum declaration '{}'", self.name))
+        })
+    }
+}
+
+impl TypeCheckModule for EnumDeclaration {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        if !meta.is_global_scope() {
+            return error!(meta, self.tok.clone(), "Enums can only be declared in the global scope")
+        }
+        if self.variants.is_empty() {
+            return error!(meta, self.name_tok.clone(), format!("Enum '{}' must have at least one variant", self.name))
+        }
+        let mut seen_variants = HashSet::new();
+        for (variant, tok) in &self.variants {
+            if !seen_variants.insert(variant.clone()) {
+                return error!(meta, tok.clone(), format!("Variant '{variant}' is already defined"))
+            }
+        }
+        let decl = EnumDecl {
+            name: self.name.clone(),
+            variants: self.variants.iter().map(|(variant, _)| variant.clone()).collect(),
+            is_public: self.is_public,
+        };
+        if meta.get_struct_declaration(&self.name).is_some() || !meta.add_enum_declaration(decl) {
+            return error!(meta, self.name_tok.clone(), format!("Enum '{}' already exists", self.name))
+        }
+        Ok(())
+    }
+}
+
+impl TranslateModule for EnumDeclaration {
+    fn translate(&self, _meta: &mut TranslateMetadata) -> FragmentKind {
+        // Enum values are the names of their variants
+        FragmentKind::Empty
+    }
+}
+
+impl DocumentationModule for EnumDeclaration {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
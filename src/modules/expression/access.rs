@@ -2,25 +2,42 @@ use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::modules::expression::expr::{Expr, ExprType};
 use crate::modules::types::{Typed, Type};
-use crate::modules::variable::validate_index_accessor;
+use crate::modules::variable::{validate_index_accessor, variable_name_extensions};
 use crate::modules::typecheck::TypeCheckModule;
 use crate::translate::module::TranslateModule;
 use crate::utils::{ParserMetadata, TranslateMetadata};
+use crate::translate::fragments::var_expr::VarIndexValue;
 use crate::translate::fragments::var_stmt::VarStmtFragment;
+use crate::raw_fragment;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Access {
     pub left: Box<Expr>,
     pub index: Box<Option<Expr>>,
+    /// Struct field accessed with a dot like `host.name`
+    pub field: Option<StructField>,
     pub kind: Type
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructField {
+    pub name: String,
+    pub tok: Option<Token>,
+    /// Position of the field in the Bash array
+    pub index: usize,
+    pub kind: Type,
+}
+
 impl Typed for Access {
     fn get_type(&self) -> Type {
-        if let Some(index) = self.index.as_ref() {
+        if let Some(field) = self.field.as_ref() {
+            field.kind.clone()
+        } else if let Some(index) = self.index.as_ref() {
             match (&index.value, &self.kind) {
                 (Some(ExprType::Range(_)), _) => self.kind.clone(),
                 (Some(_), Type::Array(item_type)) => *item_type.clone(),
+                (Some(_), Type::Map(value_type)) => *value_type.clone(),
                 _ => self.kind.clone(),
             }
         } else {
@@ -34,6 +51,7 @@ impl Default for Access {
         Access {
             left: Box::new(Expr::new()),
             index: Box::new(None),
+            field: None,
             kind: Type::Null
         }
     }
@@ -49,9 +67,39 @@ impl Access {
     }
 
     pub fn parse_operator(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        if token(meta, ".").is_ok() {
+            self.field = Some(StructField {
+                name: String::new(),
+                tok: meta.get_current_token(),
+                index: 0,
+                kind: Type::Null,
+            });
+            return Ok(());
+        }
         token(meta, "[")?;
         Ok(())
     }
+
+    fn typecheck_field(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let field = self.field.as_mut().unwrap();
+        let Type::Struct(name) = &self.kind else {
+            return error!(meta, field.tok.clone(), format!("Cannot access field '{}' of a non-struct expression of type '{}'", field.name, self.kind))
+        };
+        let Some(decl) = meta.get_struct_declaration(name) else {
+            return error!(meta, field.tok.clone() => {
+                message: format!("Struct '{name}' is not defined"),
+                comment: "Import the struct to access its fields"
+            })
+        };
+        match decl.get_field(&field.name) {
+            Some((index, kind)) => {
+                field.index = index;
+                field.kind = kind.clone();
+                Ok(())
+            }
+            None => error!(meta, field.tok.clone(), format!("Struct '{name}' has no field '{}'", field.name))
+        }
+    }
 }
 
 impl SyntaxModule<ParserMetadata> for Access {
@@ -62,6 +110,10 @@ impl SyntaxModule<ParserMetadata> for Access {
     }
 
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        if let Some(field) = self.field.as_mut() {
+            field.name = variable(meta, variable_name_extensions())?;
+            return Ok(());
+        }
         let mut index = Expr::new();
         syntax(meta, &mut index)?;
         token(meta, "]")?;
@@ -75,8 +127,20 @@ impl TypeCheckModule for Access {
         self.left.typecheck(meta)?;
         self.kind = self.left.get_type();
 
+        if self.field.is_some() {
+            return self.typecheck_field(meta);
+        }
+
         if let Some(ref mut index_expr) = self.index.as_mut() {
             let pos = self.left.get_position();
+            if self.kind.is_map() {
+                index_expr.typecheck(meta)?;
+                if index_expr.get_type() != Type::Text {
+                    let pos = index_expr.get_position();
+                    return error_pos!(meta, pos, format!("Map keys must be of type 'Text', found '{}'", index_expr.get_type()));
+                }
+                return Ok(());
+            }
             if !matches!(self.kind, Type::Array(_)) {
                 return error_pos!(meta, pos, format!("Cannot index a non-array expression of type '{}'", self.kind));
             }
@@ -89,13 +153,26 @@ impl TypeCheckModule for Access {
     }
 }
 
+impl Access {
+    fn with_index(&self, meta: &mut TranslateMetadata, var: VarExprFragment) -> VarExprFragment {
+        match (self.field.as_ref(), self.index.as_ref()) {
+            (Some(field), _) => var.with_index_by_value(VarIndexValue::Index(raw_fragment!("{}", field.index))),
+            (None, Some(key)) if self.kind.is_map() => {
+                let key = key.translate_eval(meta, false);
+                var.with_index_by_value(VarIndexValue::Key(key))
+            }
+            (None, _) => var.with_index_by_expr(meta, *self.index.clone()),
+        }
+    }
+}
+
 impl TranslateModule for Access {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
         let left_frag = self.left.translate(meta);
         match left_frag {
             FragmentKind::VarExpr(mut var) => {
                 var.kind = self.get_type();
-                var.with_index_by_expr(meta, *self.index.clone()).to_frag()
+                self.with_index(meta, var).to_frag()
             },
             _ => {
                 let id = meta.gen_value_id();
@@ -105,8 +182,7 @@ impl TranslateModule for Access {
                 meta.stmt_queue.push_back(stmt.clone().to_frag());
                 let mut var = VarExprFragment::from_stmt(&stmt);
                 var.kind = self.get_type();
-                var.with_index_by_expr(meta, *self.index.clone())
-                    .to_frag()
+                self.with_index(meta, var).to_frag()
             }
         }
     }
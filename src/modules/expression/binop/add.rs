@@ -6,8 +6,11 @@ use crate::translate::compute::translate_float_computation;
 use crate::modules::types::{Typed, Type};
 
 use super::BinOp;
+use crate::translate::constant::Constant;
+use crate::translate::compute::ArithOp;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Add {
     left: Box<Expr>,
     right: Box<Expr>,
@@ -84,6 +87,17 @@ impl TranslateModule for Add {
     }
 }
 
+impl Add {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        match (&self.kind, left, right) {
+            (Type::Text, Constant::Text(left), Constant::Text(right)) => Some(Constant::Text(left + &right)),
+            (kind, left, right) => Constant::compute(ArithOp::Add, kind, Some(&left), &right, meta.arith_module),
+        }
+    }
+}
+
 impl DocumentationModule for Add {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
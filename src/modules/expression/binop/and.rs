@@ -4,9 +4,11 @@ use crate::modules::expression::expr::Expr;
 use crate::modules::types::{Typed, Type};
 
 use super::BinOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct And {
     left: Box<Expr>,
     right: Box<Expr>
@@ -65,6 +67,14 @@ impl TranslateModule for And {
     }
 }
 
+impl And {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?.as_bool()?;
+        let right = self.right.get_constant(meta)?.as_bool()?;
+        Some(Constant::Bool(left && right))
+    }
+}
+
 impl DocumentationModule for And {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
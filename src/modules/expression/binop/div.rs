@@ -6,8 +6,10 @@ use crate::translate::compute::translate_float_computation;
 use crate::modules::types::{Typed, Type};
 
 use super::BinOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Div {
     left: Box<Expr>,
     right: Box<Expr>,
@@ -75,6 +77,14 @@ impl TranslateModule for Div {
     }
 }
 
+impl Div {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        Constant::compute(ArithOp::Div, &self.kind, Some(&left), &right, meta.arith_module)
+    }
+}
+
 impl DocumentationModule for Div {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
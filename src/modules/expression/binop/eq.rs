@@ -6,8 +6,10 @@ use crate::modules::expression::expr::Expr;
 use crate::translate::compute::{ArithOp, translate_float_computation};
 use super::BinOp;
 use crate::modules::types::{Typed, Type};
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Eq {
     left: Box<Expr>,
     right: Box<Expr>
@@ -65,11 +67,11 @@ impl TranslateModule for Eq {
         match self.left.get_type() {
             Type::Int => ArithmeticFragment::new(left, ArithOp::Eq, right).to_frag(),
             Type::Num => translate_float_computation(meta, ArithOp::Eq, Some(left), Some(right)),
-            Type::Array(_) => {
+            Type::Array(_) | Type::Struct(_) => {
                 if let (FragmentKind::VarExpr(left), FragmentKind::VarExpr(right)) = (left, right) {
                     translate_array_equality(left, right, false)
                 } else {
-                    unreachable!("Arrays are always represented as variable expressions when used as values")
+                    unreachable!("Arrays and structs are always represented as variable expressions when used as values")
                 }
             }
             _ => SubprocessFragment::new(fragments!("[ \"_", left, "\" != \"_", right, "\" ]; echo $?")).to_frag()
@@ -77,6 +79,18 @@ impl TranslateModule for Eq {
     }
 }
 
+impl Eq {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        match (left, right) {
+            (Constant::Text(left), Constant::Text(right)) => Some(Constant::Bool(left == right)),
+            (Constant::Bool(left), Constant::Bool(right)) => Some(Constant::Bool(left == right)),
+            (left, right) => Constant::compare(ArithOp::Eq, &self.left.get_type(), &left, &right, meta.arith_module),
+        }
+    }
+}
+
 impl DocumentationModule for Eq {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
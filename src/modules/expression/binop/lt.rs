@@ -5,8 +5,10 @@ use crate::translate::compare::{translate_lexical_comparison, translate_array_le
 use crate::translate::compute::{translate_float_computation, ArithOp};
 use crate::modules::types::{Typed, Type};
 use super::BinOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Lt {
     left: Box<Expr>,
     right: Box<Expr>
@@ -60,6 +62,7 @@ impl TypeCheckModule for Lt {
             Type::array_of(Type::Int),
             Type::array_of(Type::Text),
         ])?;
+        Self::typecheck_text_comparison(meta, &self.left, &self.right)?;
         Ok(())
     }
 }
@@ -88,6 +91,15 @@ impl TranslateModule for Lt {
     }
 }
 
+impl Lt {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        // Text is compared by the locale of the shell so it is left for the runtime
+        Constant::compare(ArithOp::Lt, &self.left.get_type(), &left, &right, meta.arith_module)
+    }
+}
+
 impl DocumentationModule for Lt {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
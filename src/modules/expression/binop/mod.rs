@@ -49,6 +49,19 @@ pub trait BinOp: SyntaxModule<ParserMetadata> + TypeCheckModule {
         }
     }
 
+    /// Text is compared in the lexical order only by Bash's `[[ ]]`
+    fn typecheck_text_comparison(
+        meta: &mut ParserMetadata,
+        left: &Expr,
+        right: &Expr,
+    ) -> SyntaxResult {
+        if left.get_type() == Type::Text {
+            let pos = get_binop_position_info(meta, left, right);
+            meta.require_bash(pos, "Text comparisons")?;
+        }
+        Ok(())
+    }
+
     fn typecheck_equality(
         meta: &mut ParserMetadata,
         left: &Expr,
@@ -58,6 +71,12 @@ pub trait BinOp: SyntaxModule<ParserMetadata> + TypeCheckModule {
             (Type::Int, Type::Num) | (Type::Num, Type::Int) => {
                 Ok(Type::Num)
             }
+            (Type::Map(_), _) | (_, Type::Map(_)) => {
+                let pos = get_binop_position_info(meta, left, right);
+                let message = Message::new_err_at_position(meta, pos)
+                    .message("Maps cannot be compared");
+                Err(Failure::Loud(message))
+            }
             (left_type, right_type) => {
                 if left_type != right_type {
                     let pos = get_binop_position_info(meta, left, right);
@@ -4,8 +4,10 @@ use crate::translate::compute::{ArithOp, translate_float_computation};
 use crate::modules::expression::expr::Expr;
 use crate::modules::types::{Typed, Type};
 use super::BinOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Modulo {
     left: Box<Expr>,
     right: Box<Expr>,
@@ -73,6 +75,14 @@ impl TranslateModule for Modulo {
     }
 }
 
+impl Modulo {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        Constant::compute(ArithOp::Modulo, &self.kind, Some(&left), &right, meta.arith_module)
+    }
+}
+
 impl DocumentationModule for Modulo {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
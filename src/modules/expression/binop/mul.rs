@@ -5,8 +5,10 @@ use crate::modules::expression::expr::Expr;
 use crate::modules::types::{Typed, Type};
 
 use super::BinOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Mul {
     left: Box<Expr>,
     right: Box<Expr>,
@@ -74,6 +76,14 @@ impl TranslateModule for Mul {
     }
 }
 
+impl Mul {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        Constant::compute(ArithOp::Mul, &self.kind, Some(&left), &right, meta.arith_module)
+    }
+}
+
 impl DocumentationModule for Mul {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
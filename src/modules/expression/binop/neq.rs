@@ -6,8 +6,10 @@ use crate::translate::compute::{translate_float_computation, ArithOp};
 use crate::modules::expression::expr::Expr;
 use super::BinOp;
 use crate::modules::types::{Typed, Type};
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Neq {
     left: Box<Expr>,
     right: Box<Expr>
@@ -65,11 +67,11 @@ impl TranslateModule for Neq {
         match self.left.get_type() {
             Type::Int => ArithmeticFragment::new(left, ArithOp::Neq, right).to_frag(),
             Type::Num => translate_float_computation(meta, ArithOp::Neq, Some(left), Some(right)),
-            Type::Array(_) => {
+            Type::Array(_) | Type::Struct(_) => {
                 if let (FragmentKind::VarExpr(left), FragmentKind::VarExpr(right)) = (left, right) {
                     translate_array_equality(left, right, true)
                 } else {
-                    unreachable!("Arrays are always represented as variable expressions when used as values")
+                    unreachable!("Arrays and structs are always represented as variable expressions when used as values")
                 }
             }
             _ => SubprocessFragment::new(fragments!("[ \"_", left, "\" == \"_", right, "\" ]; echo $?")).to_frag()
@@ -77,6 +79,18 @@ impl TranslateModule for Neq {
     }
 }
 
+impl Neq {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        match (left, right) {
+            (Constant::Text(left), Constant::Text(right)) => Some(Constant::Bool(left != right)),
+            (Constant::Bool(left), Constant::Bool(right)) => Some(Constant::Bool(left != right)),
+            (left, right) => Constant::compare(ArithOp::Neq, &self.left.get_type(), &left, &right, meta.arith_module),
+        }
+    }
+}
+
 impl DocumentationModule for Neq {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
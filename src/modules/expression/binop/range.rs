@@ -6,8 +6,9 @@ use crate::modules::types::{Type, Typed};
 use crate::translate::compute::{translate_float_computation, ArithOp};
 use heraclitus_compiler::prelude::*;
 use std::cmp::max;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
     pub from: Box<Expr>,
     pub to: Box<Expr>,
@@ -63,6 +64,7 @@ impl TypeCheckModule for Range {
 
 impl TranslateModule for Range {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        meta.use_command("seq");
         // Try compile-time optimization first
         if let (Some(from_val), Some(to_val)) = (self.from.get_integer_value(), self.to.get_integer_value()) {
             return self.generate_compile_time_range(from_val, to_val);
@@ -5,8 +5,10 @@ use crate::modules::expression::expr::Expr;
 use crate::modules::types::{Typed, Type};
 
 use super::BinOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sub {
     left: Box<Expr>,
     right: Box<Expr>,
@@ -74,6 +76,14 @@ impl TranslateModule for Sub {
     }
 }
 
+impl Sub {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let left = self.left.get_constant(meta)?;
+        let right = self.right.get_constant(meta)?;
+        Constant::compute(ArithOp::Sub, &self.kind, Some(&left), &right, meta.arith_module)
+    }
+}
+
 impl DocumentationModule for Sub {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
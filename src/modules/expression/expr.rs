@@ -1,11 +1,14 @@
 use heraclitus_compiler::prelude::*;
 use crate::docs::module::DocumentationModule;
+use crate::modules::builtin::embed::Embed;
 use crate::modules::builtin::len::Len;
 use crate::modules::command::cmd::Command;
 use crate::modules::expression::binop::BinOp;
-use crate::modules::prelude::FragmentKind;
+use crate::compiler::target::BashVersion;
+use crate::modules::prelude::{FragmentKind, FragmentRenderable, RawFragment};
 use crate::modules::types::{Typed, Type};
 use crate::modules::typecheck::TypeCheckModule;
+use crate::translate::constant::Constant;
 use crate::translate::module::TranslateModule;
 use crate::utils::{ParserMetadata, TranslateMetadata};
 use crate::modules::expression::typeop::TypeOp;
@@ -18,6 +21,9 @@ use super::literal::{
     integer::Integer,
     text::Text,
     array::Array,
+    map::Map,
+    struct_literal::StructLiteral,
+    enum_variant::EnumVariant,
     null::Null,
     status::Status,
 };
@@ -50,8 +56,10 @@ use super::parentheses::Parentheses;
 use crate::modules::variable::get::VariableGet;
 use super::ternop::ternary::Ternary;
 use crate::modules::function::invocation::FunctionInvocation;
+use crate::modules::function::lambda::Lambda;
 use crate::modules::builtin::lines::LinesInvocation;
 use crate::modules::builtin::nameof::Nameof;
+use crate::modules::condition::match_expr::MatchExpr;
 use crate::{
     document_expression,
     parse_expression,
@@ -59,8 +67,9 @@ use crate::{
     typecheck_expression,
     translate_expression
 };
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExprType {
     Bool(Bool),
     Number(Number),
@@ -85,9 +94,14 @@ pub enum ExprType {
     Not(Not),
     Ternary(Ternary),
     LinesInvocation(LinesInvocation),
+    Embed(Embed),
     FunctionInvocation(FunctionInvocation),
+    Lambda(Lambda),
     Command(Command),
     Array(Array),
+    Map(Map),
+    StructLiteral(StructLiteral),
+    EnumVariant(EnumVariant),
     Range(Range),
     Null(Null),
     Cast(Cast),
@@ -96,9 +110,10 @@ pub enum ExprType {
     Len(Len),
     Is(Is),
     Access(Access),
+    MatchExpr(MatchExpr),
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Expr {
     pub value: Option<ExprType>,
     pub kind: Type,
@@ -121,6 +136,70 @@ impl Expr {
         }
     }
 
+    /// Checks whether the value is known to be split into words when interpolated without quotes
+    pub fn splits_into_words(&self, meta: &ParserMetadata) -> bool {
+        match &self.value {
+            Some(ExprType::Text(value)) => value.splits_into_words(meta),
+            Some(ExprType::VariableGet(value)) => value.splits_into_words(meta),
+            Some(ExprType::Parentheses(value)) => value.splits_into_words(meta),
+            _ => false,
+        }
+    }
+
+    /// Returns the array index written as a number if it is known at compile time and the targeted Bash
+    /// needs it to count the negative indices from the end of the array without a runtime check
+    pub fn get_static_array_index(&self, meta: &TranslateMetadata) -> Option<FragmentKind> {
+        if !meta.is_bash_older_than(BashVersion::NEGATIVE_INDEX) {
+            return None;
+        }
+        match self.get_constant(meta)? {
+            Constant::Int(value) => Some(RawFragment::from(value.to_string()).to_frag()),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the expression at compile time if it only depends on the constant values
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        match self.value.as_ref()? {
+            ExprType::Bool(value) => value.get_constant(),
+            ExprType::Integer(value) => value.get_constant(),
+            ExprType::Number(value) => value.get_constant(),
+            ExprType::Text(value) => value.get_constant(meta),
+            ExprType::VariableGet(value) => value.get_constant(meta),
+            ExprType::Parentheses(value) => value.get_constant(meta),
+            ExprType::Add(value) => value.get_constant(meta),
+            ExprType::Sub(value) => value.get_constant(meta),
+            ExprType::Mul(value) => value.get_constant(meta),
+            ExprType::Div(value) => value.get_constant(meta),
+            ExprType::Modulo(value) => value.get_constant(meta),
+            ExprType::Neg(value) => value.get_constant(meta),
+            ExprType::And(value) => value.get_constant(meta),
+            ExprType::Or(value) => value.get_constant(meta),
+            ExprType::Not(value) => value.get_constant(meta),
+            ExprType::Gt(value) => value.get_constant(meta),
+            ExprType::Ge(value) => value.get_constant(meta),
+            ExprType::Lt(value) => value.get_constant(meta),
+            ExprType::Le(value) => value.get_constant(meta),
+            ExprType::Eq(value) => value.get_constant(meta),
+            ExprType::Neq(value) => value.get_constant(meta),
+            ExprType::Ternary(value) => value.get_constant(meta),
+            _ => None,
+        }
+    }
+
+    /// Literals and variables are translated as they are since they are already values
+    /// (and variables passed by reference have to stay variables)
+    fn is_foldable(&self) -> bool {
+        match &self.value {
+            Some(ExprType::Text(text)) => text.has_interpolations(),
+            Some(
+                ExprType::Bool(_) | ExprType::Integer(_) | ExprType::Number(_)
+                | ExprType::VariableGet(_) | ExprType::Parentheses(_)
+            ) => false,
+            _ => true,
+        }
+    }
+
     pub fn get_position(&self) -> PositionInfo {
         self.position.clone().expect("Expr position wasn't set in the parsing stage")
     }
@@ -158,11 +237,13 @@ impl SyntaxModule<ParserMetadata> for Expr {
             literals @ Literal => [
                 // Literals
                 Parentheses, Bool, Number, Integer, Text,
-                Array, Null, Status, Nameof,
+                Map, Array, StructLiteral, EnumVariant, Null, Status, Nameof,
                 // Builtin invocation
-                LinesInvocation,
+                LinesInvocation, Embed,
                 // Function invocation
-                FunctionInvocation, Command,
+                Lambda, FunctionInvocation, Command,
+                // Conditions
+                MatchExpr,
                 // Variable access
                 VariableGet
             ]
@@ -174,10 +255,10 @@ impl SyntaxModule<ParserMetadata> for Expr {
 impl TypeCheckModule for Expr {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         typecheck_expression!(self, meta, self.value.as_mut().unwrap(), [
-            Add, And, Array, Bool, Cast, Command, Div, Eq, FunctionInvocation,
-            Ge, Gt, Integer, Is, Le, Len, LinesInvocation, Lt, Modulo,
+            Add, And, Array, Bool, Cast, Command, Div, Embed, EnumVariant, Eq, FunctionInvocation,
+            Ge, Gt, Integer, Is, Lambda, Le, Len, LinesInvocation, Lt, Map, Modulo,
             Mul, Nameof, Neg, Neq, Not, Null, Number, Or, Parentheses,
-            Range, Status, Sub, Ternary, Text, VariableGet, Access
+            Range, Status, StructLiteral, Sub, Ternary, Text, VariableGet, Access, MatchExpr
         ]);
         Ok(())
     }
@@ -185,12 +266,17 @@ impl TypeCheckModule for Expr {
 
 impl TranslateModule for Expr {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        if meta.optimize && self.is_foldable() {
+            if let Some(constant) = self.get_constant(meta) {
+                return constant.translate();
+            }
+        }
         meta.with_expr_ctx(true, |meta| {
             translate_expression!(meta, self.value.as_ref().unwrap(), [
-                Add, And, Array, Bool, Cast, Command, Div, Eq, FunctionInvocation,
-                Ge, Gt, Integer, Is, Le, Len, LinesInvocation, Lt, Modulo,
+                Add, And, Array, Bool, Cast, Command, Div, Embed, EnumVariant, Eq, FunctionInvocation,
+                Ge, Gt, Integer, Is, Lambda, Le, Len, LinesInvocation, Lt, Map, Modulo,
                 Mul, Nameof, Neg, Neq, Not, Null, Number, Or, Parentheses,
-                Range, Status, Sub, Ternary, Text, VariableGet, Access
+                Range, Status, StructLiteral, Sub, Ternary, Text, VariableGet, Access, MatchExpr
             ])
         })
     }
@@ -199,10 +285,10 @@ impl TranslateModule for Expr {
 impl DocumentationModule for Expr {
     fn document(&self, meta: &ParserMetadata) -> String {
         document_expression!(meta, self.value.as_ref().unwrap(), [
-            Add, And, Array, Bool, Cast, Command, Div, Eq, FunctionInvocation,
-            Ge, Gt, Integer, Is, Le, Len, LinesInvocation, Lt, Modulo,
+            Add, And, Array, Bool, Cast, Command, Div, Embed, EnumVariant, Eq, FunctionInvocation,
+            Ge, Gt, Integer, Is, Lambda, Le, Len, LinesInvocation, Lt, Map, Modulo,
             Mul, Nameof, Neg, Neq, Not, Null, Number, Or, Parentheses,
-            Range, Status, Sub, Ternary, Text, VariableGet, Access
+            Range, Status, StructLiteral, Sub, Ternary, Text, VariableGet, Access, MatchExpr
         ])
     }
 }
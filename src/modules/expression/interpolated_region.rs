@@ -1,9 +1,10 @@
 use heraclitus_compiler::prelude::*;
 use crate::utils::metadata::ParserMetadata;
 use crate::modules::expression::expr::Expr;
+use serde::{Deserialize, Serialize};
 
 /// Represents a literal text or a command.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InterpolatedRegionType {
     Text,
     Command,
@@ -80,10 +81,15 @@ fn parse_escaped_string(string: String, region_type: &InterpolatedRegionType) ->
     result
 }
 
-pub fn parse_interpolated_region(meta: &mut ParserMetadata, interpolated_type: &InterpolatedRegionType) -> Result<(Vec<String>, Vec<Expr>), Failure> {
+/// Strings and interpolations of the region along with the tokens that the strings come from
+pub type InterpolatedRegion = (Vec<String>, Vec<Expr>, Vec<Token>);
+
+pub fn parse_interpolated_region(meta: &mut ParserMetadata, interpolated_type: &InterpolatedRegionType) -> Result<InterpolatedRegion, Failure> {
     let mut strings = vec![];
     let mut interps = vec![];
+    let mut tokens = vec![];
     let letter = interpolated_type.to_char();
+    let start_tok = meta.get_current_token();
     // Handle full string
     if let Ok(word) = token_by(meta, |word| {
         word.starts_with(letter)
@@ -93,12 +99,14 @@ pub fn parse_interpolated_region(meta: &mut ParserMetadata, interpolated_type: &
     }) {
         let stripped = word.chars().take(word.chars().count() - 1).skip(1).collect::<String>();
         strings.push(parse_escaped_string(stripped, interpolated_type));
-        Ok((strings, interps))
+        tokens.extend(start_tok);
+        Ok((strings, interps, tokens))
     } else {
         let mut is_interp = false;
         // Initialize string
         let start = token_by(meta, |word| word.starts_with(letter))?;
         strings.push(parse_escaped_string(start.chars().skip(1).collect::<String>(), interpolated_type));
+        tokens.extend(start_tok);
         // Factor rest of the interpolation
         while let Some(tok) = meta.get_current_token() {
             // Track interpolations
@@ -114,6 +122,7 @@ pub fn parse_interpolated_region(meta: &mut ParserMetadata, interpolated_type: &
                 }
                 else {
                     strings.push(parse_escaped_string(tok.word.clone(), interpolated_type));
+                    tokens.push(tok.clone());
                     if tok.word.ends_with(letter) && !is_escaped(&tok.word, letter) {
                         meta.increment_index();
                         // Right trim the symbol
@@ -121,7 +130,7 @@ pub fn parse_interpolated_region(meta: &mut ParserMetadata, interpolated_type: &
                             .chars().take(parse_escaped_string(tok.word, interpolated_type).chars().count() - 1).collect::<String>();
                         // replace the last string
                         *strings.last_mut().unwrap() = trimmed;
-                        return Ok((strings, interps))
+                        return Ok((strings, interps, tokens))
                     }
                 }
             }
@@ -1,12 +1,14 @@
 use heraclitus_compiler::prelude::*;
 use crate::modules::expression::expr::Expr;
-use crate::modules::types::{try_parse_type, Type, Typed};
+use crate::modules::types::{resolve_type, try_parse_type, Type, Typed};
 use crate::modules::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Array {
     exprs: Vec<Expr>,
-    kind: Type
+    kind: Type,
+    tok: Option<Token>
 }
 
 impl Typed for Array {
@@ -21,30 +23,38 @@ impl SyntaxModule<ParserMetadata> for Array {
     fn new() -> Self {
         Array {
             exprs: vec![],
-            kind: Type::Generic
+            kind: Type::Generic,
+            tok: None
         }
     }
 
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
         token(meta, "[")?;
         let tok = meta.get_current_token();
+        // Element type of an untyped empty array is inferred from the values added to it
         if token(meta, "]").is_ok() {
-            return error!(meta, tok, "Expected array type or value before ']'", "Eg. insert 'Num' for empty array or '1, 2, 3' for array with values")
+            self.kind = Type::array_of(Type::Generic);
+            return Ok(());
         }
         // Try to parse array type
+        let index = meta.get_index();
         match try_parse_type(meta) {
-            Ok(kind) => {
+            Ok(kind) if token(meta, "]").is_ok() => {
                 if matches!(kind, Type::Array(_)) {
                     return error!(meta, tok, "Arrays cannot be nested due to the Bash limitations")
                 }
+                if kind.is_map() {
+                    return error!(meta, tok, "Maps cannot be stored in arrays due to the Bash limitations")
+                }
                 self.kind = Type::Array(Box::new(kind));
-                token(meta, "]")?;
             },
             Err(Failure::Loud(err)) => {
                 return Err(Failure::Loud(err))
             },
-            // Parse the array values
-            Err(Failure::Quiet(_)) => {
+            // Parse the array values (enum variants start with a type name as well)
+            _ => {
+                meta.set_index(index);
                 loop {
                     if token(meta, "]").is_ok() {
                         break;
@@ -71,6 +81,8 @@ impl SyntaxModule<ParserMetadata> for Array {
 
 impl TypeCheckModule for Array {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let pos = PositionInfo::from_token(meta, self.tok.clone());
+        meta.require_bash(pos, "Arrays")?;
         // First type-check all the expressions
         for expr in &mut self.exprs {
             expr.typecheck(meta)?;
@@ -79,11 +91,20 @@ impl TypeCheckModule for Array {
                 let pos = expr.get_position();
                 return error_pos!(meta, pos, "Arrays cannot be nested due to the Bash limitations")
             }
+            if matches!(expr.get_type(), Type::Struct(_)) {
+                let pos = expr.get_position();
+                return error_pos!(meta, pos, "Structs cannot be stored in arrays due to the Bash limitations")
+            }
+            if expr.get_type().is_map() {
+                let pos = expr.get_position();
+                return error_pos!(meta, pos, "Maps cannot be stored in arrays due to the Bash limitations")
+            }
         }
 
         // Then determine the array type
         if self.exprs.is_empty() {
             // Empty array keeps its existing type (from explicit type annotation or default)
+            self.kind = resolve_type(meta, &self.kind, self.tok.clone())?;
             return Ok(());
         }
 
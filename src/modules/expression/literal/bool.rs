@@ -2,8 +2,10 @@ use heraclitus_compiler::prelude::*;
 use crate::raw_fragment;
 use crate::modules::prelude::*;
 use crate::modules::types::{Type, Typed};
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bool {
     value: bool
 }
@@ -42,6 +44,12 @@ impl TranslateModule for Bool {
     }
 }
 
+impl Bool {
+    pub fn get_constant(&self) -> Option<Constant> {
+        Some(Constant::Bool(self.value))
+    }
+}
+
 impl DocumentationModule for Bool {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
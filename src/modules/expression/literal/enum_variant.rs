@@ -0,0 +1,69 @@
+use heraclitus_compiler::prelude::*;
+use crate::modules::types::{is_type_name, Type, Typed};
+use crate::modules::variable::variable_name_extensions;
+use crate::modules::prelude::*;
+use crate::raw_fragment;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub name: String,
+    pub variant: String,
+    tok: Option<Token>,
+    variant_tok: Option<Token>,
+}
+
+impl Typed for EnumVariant {
+    fn get_type(&self) -> Type {
+        Type::Enum(self.name.clone())
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for EnumVariant {
+    syntax_name!("Enum Variant");
+
+    fn new() -> Self {
+        EnumVariant {
+            name: String::new(),
+            variant: String::new(),
+            tok: None,
+            variant_tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        self.name = variable(meta, variable_name_extensions())?;
+        if !is_type_name(&self.name) {
+            return Err(Failure::Quiet(PositionInfo::from_metadata(meta)));
+        }
+        token(meta, ".")?;
+        self.variant_tok = meta.get_current_token();
+        self.variant = variable(meta, variable_name_extensions())?;
+        Ok(())
+    }
+}
+
+impl TypeCheckModule for EnumVariant {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let Some(decl) = meta.get_enum_declaration(&self.name) else {
+            return error!(meta, self.tok.clone(), format!("Enum '{}' is not defined", self.name))
+        };
+        if !decl.variants.contains(&self.variant) {
+            return error!(meta, self.variant_tok.clone(), format!("Enum '{}' has no variant '{}'", self.name, self.variant))
+        }
+        Ok(())
+    }
+}
+
+impl TranslateModule for EnumVariant {
+    fn translate(&self, _meta: &mut TranslateMetadata) -> FragmentKind {
+        raw_fragment!("{}", self.variant)
+    }
+}
+
+impl DocumentationModule for EnumVariant {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -3,8 +3,10 @@ use crate::modules::prelude::*;
 use crate::docs::module::DocumentationModule;
 use crate::modules::types::{Type, Typed};
 use crate::translate::module::TranslateModule;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Integer {
     pub value: String
 }
@@ -46,6 +48,12 @@ impl TranslateModule for Integer {
     }
 }
 
+impl Integer {
+    pub fn get_constant(&self) -> Option<Constant> {
+        Constant::from_integer(&self.value)
+    }
+}
+
 impl DocumentationModule for Integer {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
@@ -0,0 +1,132 @@
+use heraclitus_compiler::prelude::*;
+use crate::compiler::target::BashVersion;
+use crate::fragments;
+use crate::modules::expression::expr::Expr;
+use crate::modules::types::{parse_map_value_type, resolve_type, try_parse_type, Type, Typed};
+use crate::modules::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Map {
+    entries: Vec<(Expr, Expr)>,
+    kind: Type,
+    tok: Option<Token>
+}
+
+impl Typed for Map {
+    fn get_type(&self) -> Type {
+        self.kind.clone()
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for Map {
+    syntax_name!("Map");
+
+    fn new() -> Self {
+        Map {
+            entries: vec![],
+            kind: Type::Generic,
+            tok: None
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        token(meta, "[")?;
+        // Try to parse the map type of an empty map
+        let tok = meta.get_current_token();
+        match try_parse_type(meta) {
+            Ok(key_type) => {
+                // Type without a value type is an empty array
+                token(meta, ":")?;
+                let value_type = parse_map_value_type(meta, key_type, tok)?;
+                token(meta, "]")?;
+                self.kind = Type::map_of(value_type);
+                return Ok(());
+            }
+            Err(Failure::Loud(err)) => return Err(Failure::Loud(err)),
+            Err(Failure::Quiet(_)) => {}
+        }
+        // Brackets without a type are an empty array
+        if token(meta, "]").is_ok() {
+            return Err(Failure::Quiet(PositionInfo::from_token(meta, tok)));
+        }
+        // Parse the map entries
+        loop {
+            if token(meta, "]").is_ok() {
+                break;
+            }
+            let mut key = Expr::new();
+            syntax(meta, &mut key)?;
+            // Array elements are not followed by a colon
+            token(meta, ":")?;
+            let mut value = Expr::new();
+            syntax(meta, &mut value)?;
+            self.entries.push((key, value));
+            let tok = meta.get_current_token();
+            if token(meta, "]").is_ok() {
+                break;
+            }
+            if token(meta, ",").is_err() {
+                return error!(meta, tok, "Expected ',' or ']' after map entry");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TypeCheckModule for Map {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let pos = PositionInfo::from_token(meta, self.tok.clone());
+        meta.require_bash_version(pos, "Maps", BashVersion::ASSOCIATIVE_ARRAY)?;
+        if self.entries.is_empty() {
+            self.kind = resolve_type(meta, &self.kind, self.tok.clone())?;
+        }
+        for (key, value) in &mut self.entries {
+            key.typecheck(meta)?;
+            value.typecheck(meta)?;
+            if key.get_type() != Type::Text {
+                let pos = key.get_position();
+                return error_pos!(meta, pos, format!("Map keys must be of type 'Text', found '{}'", key.get_type()))
+            }
+            let value_type = value.get_type();
+            if value_type.is_bash_array() || value_type.is_map() {
+                let pos = value.get_position();
+                return error_pos!(meta, pos, format!("Map values cannot be of type '{value_type}' due to the Bash limitations"))
+            }
+        }
+        // Infer the value type from the first entry
+        if let Some((_, value)) = self.entries.first() {
+            self.kind = Type::map_of(value.get_type());
+        }
+        if let Type::Map(ref value_type) = self.kind {
+            for (_, value) in self.entries.iter().skip(1) {
+                if value.get_type() != **value_type {
+                    let pos = value.get_position();
+                    return error_pos!(meta, pos, format!("Map values must have the same type. Expected '{}', found '{}'", value_type, value.get_type()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TranslateModule for Map {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let id = meta.gen_value_id();
+        let entries = self.entries.iter().map(|(key, value)| {
+            let key = key.translate_eval(meta, false);
+            let value = value.translate_eval(meta, false);
+            fragments!("[", key, "]=", value)
+        }).collect::<Vec<FragmentKind>>();
+        let entries = ListFragment::new(entries).with_spaces().to_frag();
+        let var_stmt = VarStmtFragment::new("map", self.kind.clone(), entries).with_global_id(id);
+        meta.push_ephemeral_variable(var_stmt).to_frag()
+    }
+}
+
+impl DocumentationModule for Map {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
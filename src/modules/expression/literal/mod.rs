@@ -4,4 +4,7 @@ pub mod integer;
 pub mod text;
 pub mod null;
 pub mod array;
+pub mod map;
+pub mod struct_literal;
+pub mod enum_variant;
 pub mod status;
@@ -3,8 +3,9 @@ use crate::fragments;
 use crate::modules::prelude::*;
 use crate::{docs::module::DocumentationModule, modules::types::{Type, Typed}};
 use crate::translate::module::TranslateModule;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Null {}
 
 impl Typed for Null {
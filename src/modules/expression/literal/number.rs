@@ -3,8 +3,10 @@ use crate::modules::prelude::*;
 use crate::docs::module::DocumentationModule;
 use crate::modules::types::{Type, Typed};
 use crate::translate::module::TranslateModule;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Number {
     value: String
 }
@@ -55,6 +57,10 @@ impl Number {
         let value = self.value.parse().unwrap_or_default();
         Some(value)
     }
+
+    pub fn get_constant(&self) -> Option<Constant> {
+        Some(Constant::Num(self.value.clone()))
+    }
 }
 
 impl DocumentationModule for Number {
@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use heraclitus_compiler::prelude::*;
+use crate::modules::expression::expr::Expr;
+use crate::modules::types::{is_type_name, Type, Typed};
+use crate::modules::variable::variable_name_extensions;
+use crate::modules::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructLiteral {
+    name: String,
+    fields: Vec<(String, Expr, Option<Token>)>,
+    tok: Option<Token>,
+}
+
+impl Typed for StructLiteral {
+    fn get_type(&self) -> Type {
+        Type::Struct(self.name.clone())
+    }
+}
+
+impl StructLiteral {
+    fn skip_empty_lines(meta: &mut ParserMetadata) {
+        while token_by(meta, |word| word.starts_with('\n') || word.starts_with("//")).is_ok() {}
+    }
+
+    /// Checks if the opening brace starts field initializers and not a block
+    /// (structs have at least one field, so empty braces are the block of a condition like `if Flag {}`)
+    fn is_field_list(meta: &mut ParserMetadata) -> bool {
+        let index = meta.get_index();
+        Self::skip_empty_lines(meta);
+        let result = variable(meta, variable_name_extensions()).is_ok() && token(meta, ":").is_ok();
+        meta.set_index(index);
+        result
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for StructLiteral {
+    syntax_name!("Struct");
+
+    fn new() -> Self {
+        StructLiteral {
+            name: String::new(),
+            fields: vec![],
+            tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        self.name = variable(meta, variable_name_extensions())?;
+        if !is_type_name(&self.name) {
+            return Err(Failure::Quiet(PositionInfo::from_metadata(meta)));
+        }
+        token(meta, "{")?;
+        if !Self::is_field_list(meta) {
+            return Err(Failure::Quiet(PositionInfo::from_metadata(meta)));
+        }
+        loop {
+            Self::skip_empty_lines(meta);
+            if token(meta, "}").is_ok() {
+                break;
+            }
+            let field_tok = meta.get_current_token();
+            let field = variable(meta, variable_name_extensions())?;
+            token(meta, ":")?;
+            let mut value = Expr::new();
+            syntax(meta, &mut value)?;
+            self.fields.push((field, value, field_tok));
+            Self::skip_empty_lines(meta);
+            let tok = meta.get_current_token();
+            if token(meta, "}").is_ok() {
+                break;
+            }
+            if token(meta, ",").is_err() {
+                return error!(meta, tok, "Expected ',' or '}' after struct field");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TypeCheckModule for StructLiteral {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let Some(decl) = meta.get_struct_declaration(&self.name).cloned() else {
+            return error!(meta, self.tok.clone(), format!("Struct '{}' is not defined", self.name))
+        };
+        let mut seen_fields = HashSet::new();
+        for (field, value, tok) in &mut self.fields {
+            value.typecheck(meta)?;
+            if !seen_fields.insert(field.clone()) {
+                return error!(meta, tok.clone(), format!("Field '{field}' is already initialized"))
+            }
+            let Some((_, kind)) = decl.get_field(field) else {
+                return error!(meta, tok.clone(), format!("Struct '{}' has no field '{field}'", self.name))
+            };
+            if !value.get_type().is_allowed_in(kind) {
+                let pos = value.get_position();
+                return error_pos!(meta, pos, format!("Expected value of type '{kind}' for field '{field}', found '{}'", value.get_type()))
+            }
+        }
+        let missing = decl.fields.iter()
+            .filter(|(field, _)| !seen_fields.contains(field))
+            .map(|(field, _)| format!("'{field}'"))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let noun = if missing.len() == 1 { "field" } else { "fields" };
+            return error!(meta, self.tok.clone(), format!("Missing {noun} {} in struct '{}'", missing.join(", "), self.name))
+        }
+        // Store the values in the declaration order
+        self.fields.sort_by_key(|(field, _, _)| decl.get_field(field).map(|(index, _)| index));
+        Ok(())
+    }
+}
+
+impl TranslateModule for StructLiteral {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let id = meta.gen_value_id();
+        let values = self.fields.iter().map(|(_, value, _)| value.translate_eval(meta, false)).collect::<Vec<FragmentKind>>();
+        let values = ListFragment::new(values).with_spaces().to_frag();
+        let var_stmt = VarStmtFragment::new("struct", self.get_type(), values).with_global_id(id);
+        meta.push_ephemeral_variable(var_stmt).to_frag()
+    }
+}
+
+impl DocumentationModule for StructLiteral {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -1,12 +1,15 @@
 use heraclitus_compiler::prelude::*;
 use crate::docs::module::DocumentationModule;
 use crate::modules::prelude::*;
+use crate::raw_fragment;
 use crate::modules::types::{Type, Typed};
 use crate::translate::module::TranslateModule;
 use crate::modules::expression::expr::Expr;
 use crate::modules::expression::interpolated_region::{InterpolatedRegionType, parse_interpolated_region};
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Text {
     strings: Vec<String>,
     interps: Vec<Expr>,
@@ -29,7 +32,7 @@ impl SyntaxModule<ParserMetadata> for Text {
     }
 
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
-        (self.strings, self.interps) = parse_interpolated_region(meta, &InterpolatedRegionType::Text)?;
+        (self.strings, self.interps, _) = parse_interpolated_region(meta, &InterpolatedRegionType::Text)?;
         Ok(())
     }
 }
@@ -54,6 +57,84 @@ impl TranslateModule for Text {
     }
 }
 
+impl Text {
+    pub fn has_interpolations(&self) -> bool {
+        !self.interps.is_empty()
+    }
+
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let mut result = String::new();
+        for (index, string) in self.strings.iter().enumerate() {
+            result.push_str(string);
+            if let Some(interp) = self.interps.get(index) {
+                result.push_str(&interp.get_constant(meta)?.to_text());
+            }
+        }
+        Some(Constant::Text(result))
+    }
+
+    /// Checks whether the shell splits the text into words or expands it as a glob
+    pub fn splits_into_words(&self, meta: &ParserMetadata) -> bool {
+        self.strings.iter().any(|string| string.contains(|letter: char| letter.is_whitespace() || "*?[".contains(letter)))
+            || self.interps.iter().any(|interp| interp.splits_into_words(meta))
+    }
+
+    /// Translates the text to a `case` pattern where the glob characters keep their meaning.
+    /// Interpolated values are always matched literally.
+    pub fn translate_glob_pattern(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        let mut interps = self.interps.iter();
+        let mut result = vec![];
+        for string in &self.strings {
+            for (is_glob, part) in split_glob(string) {
+                if is_glob {
+                    result.push(RawFragment::from(part).to_frag());
+                } else {
+                    result.push(InterpolableFragment::new(vec![part], vec![], InterpolableRenderType::StringLiteral).to_frag());
+                }
+            }
+            if let Some(interp) = interps.next() {
+                result.push(interp.translate(meta));
+            }
+        }
+        if result.is_empty() {
+            return raw_fragment!("\"\"");
+        }
+        ListFragment::new(result).to_frag()
+    }
+}
+
+/// Splits the string into literal parts and glob parts (`*`, `?` and simple `[...]` classes)
+fn split_glob(string: &str) -> Vec<(bool, String)> {
+    let chars = string.chars().collect::<Vec<_>>();
+    let mut result: Vec<(bool, String)> = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let glob = match chars[index] {
+            '*' | '?' => Some(chars[index].to_string()),
+            '[' => chars[index + 1..].iter()
+                .position(|chr| *chr == ']')
+                .map(|end| chars[index..=index + end + 1].iter().collect::<String>())
+                .filter(|class| class.len() > 2 && class[1..class.len() - 1].chars()
+                    .all(|chr| chr.is_alphanumeric() || "-!^_.".contains(chr))),
+            _ => None,
+        };
+        match glob {
+            Some(glob) => {
+                index += glob.chars().count();
+                result.push((true, glob));
+            }
+            None => {
+                match result.last_mut() {
+                    Some((false, literal)) => literal.push(chars[index]),
+                    _ => result.push((false, chars[index].to_string())),
+                }
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
 impl DocumentationModule for Text {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
@@ -6,8 +6,11 @@ use crate::modules::typecheck::TypeCheckModule;
 use crate::utils::metadata::ParserMetadata;
 use crate::translate::module::TranslateModule;
 use super::expr::Expr;
+use crate::translate::constant::Constant;
+use crate::utils::TranslateMetadata;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parentheses {
     value: Box<Expr>,
     kind: Type
@@ -46,11 +49,21 @@ impl TypeCheckModule for Parentheses {
 }
 
 impl TranslateModule for Parentheses {
-    fn translate(&self, meta: &mut crate::utils::TranslateMetadata) -> FragmentKind {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
         self.value.translate(meta)
     }
 }
 
+impl Parentheses {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        self.value.get_constant(meta)
+    }
+
+    pub fn splits_into_words(&self, meta: &ParserMetadata) -> bool {
+        self.value.splits_into_words(meta)
+    }
+}
+
 impl DocumentationModule for Parentheses {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
@@ -6,8 +6,10 @@ use crate::modules::expression::binop::get_binop_position_info;
 use crate::modules::types::{Type, Typed};
 use crate::modules::expression::expr::Expr;
 use super::TernOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ternary {
     cond: Box<Expr>,
     true_expr: Box<Expr>,
@@ -80,13 +82,19 @@ impl TypeCheckModule for Ternary {
                     self.false_expr.get_type()));
             return Err(Failure::Loud(msg));
         }
+        if self.true_expr.get_type().is_map() {
+            let pos = get_binop_position_info(meta, &self.true_expr, &self.false_expr);
+            let msg = Message::new_err_at_position(meta, pos)
+                .message("Ternary operation cannot evaluate to a map");
+            return Err(Failure::Loud(msg));
+        }
         Ok(())
     }
 }
 
 impl TranslateModule for Ternary {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
-        let is_array = self.true_expr.get_type().is_array();
+        let is_array = self.true_expr.get_type().is_bash_array();
         let cond = self.cond.translate(meta);
         let true_expr = self.true_expr.translate(meta);
         let false_expr = self.false_expr.translate(meta);
@@ -102,6 +110,21 @@ impl TranslateModule for Ternary {
     }
 }
 
+impl Ternary {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let value = if self.cond.get_constant(meta)?.as_bool()? {
+            self.true_expr.get_constant(meta)?
+        } else {
+            self.false_expr.get_constant(meta)?
+        };
+        // The chosen value is printed with `echo` that could alter such text
+        match &value {
+            Constant::Text(text) if text.starts_with('-') || text.contains('\\') || text.ends_with('\n') => None,
+            _ => Some(value),
+        }
+    }
+}
+
 impl DocumentationModule for Ternary {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
@@ -2,14 +2,16 @@ use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::modules::expression::expr::Expr;
 use crate::utils::cc_flags::{get_ccflag_name, CCFlags};
-use crate::modules::types::{Type, Typed};
+use crate::modules::types::{resolve_type, Type, Typed};
 
 use super::TypeOp;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cast {
     expr: Box<Expr>,
-    kind: Type
+    kind: Type,
+    kind_tok: Option<Token>
 }
 
 impl Typed for Cast {
@@ -29,6 +31,7 @@ impl TypeOp for Cast {
 
     fn parse_operator(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         token(meta, "as")?;
+        self.kind_tok = meta.get_current_token();
         Ok(())
     }
 }
@@ -39,7 +42,8 @@ impl SyntaxModule<ParserMetadata> for Cast {
     fn new() -> Self {
         Cast {
             expr: Box::new(Expr::new()),
-            kind: Type::default()
+            kind: Type::default(),
+            kind_tok: None
         }
     }
 
@@ -51,6 +55,7 @@ impl SyntaxModule<ParserMetadata> for Cast {
 impl TypeCheckModule for Cast {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         self.expr.typecheck(meta)?;
+        self.kind = resolve_type(meta, &self.kind, self.kind_tok.clone())?;
 
         let pos = self.expr.get_position();
         if !meta.context.cc_flags.contains(&CCFlags::AllowAbsurdCast) {
@@ -2,14 +2,16 @@ use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::fragments;
 use crate::modules::expression::expr::Expr;
-use crate::modules::types::{Typed, Type};
+use crate::modules::types::{resolve_type, Typed, Type};
 
 use super::TypeOp;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Is {
     expr: Box<Expr>,
-    kind: Type
+    kind: Type,
+    kind_tok: Option<Token>
 }
 
 impl Typed for Is {
@@ -29,6 +31,7 @@ impl TypeOp for Is {
 
     fn parse_operator(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         token(meta, "is")?;
+        self.kind_tok = meta.get_current_token();
         Ok(())
     }
 }
@@ -39,7 +42,8 @@ impl SyntaxModule<ParserMetadata> for Is {
     fn new() -> Self {
         Is {
             expr: Box::new(Expr::new()),
-            kind: Type::default()
+            kind: Type::default(),
+            kind_tok: None
         }
     }
 
@@ -50,7 +54,9 @@ impl SyntaxModule<ParserMetadata> for Is {
 
 impl TypeCheckModule for Is {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
-        self.expr.typecheck(meta)
+        self.expr.typecheck(meta)?;
+        self.kind = resolve_type(meta, &self.kind, self.kind_tok.clone())?;
+        Ok(())
     }
 }
 
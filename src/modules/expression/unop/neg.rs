@@ -5,13 +5,15 @@ use crate::modules::prelude::{ArithmeticFragment, FragmentKind, FragmentRenderab
 use crate::modules::types::{Type, Typed};
 use crate::modules::typecheck::TypeCheckModule;
 use crate::translate::compute::{translate_float_computation, ArithOp};
+use crate::translate::constant::Constant;
 use crate::translate::module::TranslateModule;
 use crate::utils::metadata::ParserMetadata;
 use crate::utils::TranslateMetadata;
 use heraclitus_compiler::prelude::*;
 use std::ops::Neg as _;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Neg {
     expr: Box<Expr>
 }
@@ -76,6 +78,11 @@ impl Neg {
         self.expr.get_integer_value().map(isize::neg)
     }
 
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        let value = self.expr.get_constant(meta)?;
+        Constant::compute(ArithOp::Neg, &self.expr.get_type(), None, &value, meta.arith_module)
+    }
+
     pub fn get_array_index(&self, meta: &mut TranslateMetadata) -> FragmentKind {
         if let Some(expr) = self.get_integer_value() {
             RawFragment::from(expr.to_string()).to_frag()
@@ -5,8 +5,10 @@ use crate::modules::types::{Type, Typed};
 use crate::docs::module::DocumentationModule;
 use super::super::expr::Expr;
 use super::UnOp;
+use crate::translate::constant::Constant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Not {
     expr: Box<Expr>
 }
@@ -57,6 +59,12 @@ impl TranslateModule for Not {
     }
 }
 
+impl Not {
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        Some(Constant::Bool(!self.expr.get_constant(meta)?.as_bool()?))
+    }
+}
+
 impl DocumentationModule for Not {
     fn document(&self, _meta: &ParserMetadata) -> String {
         "".to_string()
@@ -4,25 +4,25 @@ use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::Path;
 use super::declaration_utils::*;
-use crate::fragments;
 use crate::modules::expression::expr::Expr;
 use crate::modules::prelude::*;
 use crate::modules::statement::comment_doc::CommentDoc;
 use crate::modules::typecheck::TypeCheckModule;
-use crate::modules::types::parse_type;
+use crate::modules::types::{parse_type, resolve_type};
 use crate::modules::types::{Type, Typed};
 use crate::modules::variable::variable_name_extensions;
-use crate::utils::cc_flags::{CCFlags, get_ccflag_by_name};
+use crate::utils::cc_flags::{CCFlags, get_ccflag_by_name, parse_required_commands};
 use crate::utils::context::Context;
 use crate::utils::function_cache::FunctionInstance;
 use crate::utils::function_interface::FunctionInterface;
-use crate::utils::function_metadata::FunctionMetadata;
+use crate::utils::function_metadata::{FunctionMetadata, FUNCTION_VALUE_RETURN};
 use heraclitus_compiler::prelude::*;
 use itertools::izip;
 
 use crate::modules::block::Block;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDeclarationArgument {
     pub name: String,
     pub kind: Type,
@@ -31,7 +31,7 @@ pub struct FunctionDeclarationArgument {
     pub tok: Option<Token>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDeclaration {
     pub name: String,
     pub args: Vec<FunctionDeclarationArgument>,
@@ -39,6 +39,8 @@ pub struct FunctionDeclaration {
     pub id: usize,
     pub is_public: bool,
     pub flags: HashSet<CCFlags>,
+    /// Commands declared with the `requires` flag
+    pub requires: Vec<String>,
     pub comment: Option<CommentDoc>,
     /// Function signature prepared for docs generation
     pub doc_signature: Option<String>,
@@ -67,10 +69,16 @@ impl FunctionDeclaration {
                     .with_local(true)
                     .with_optimization_when_unused(false);
                 match (arg.is_ref, kind) {
-                    (false, Type::Array(_)) => {
+                    (false, Type::Array(_) | Type::Struct(_) | Type::Map(_)) => {
                         let val = VarExprFragment::new(&format!("{}", index + 1), Type::Generic).with_ref(true);
                         result.push(var.with_index(None).with_value(val.to_frag()).to_frag());
                     },
+                    // Referenced map holds just the name of the associative array
+                    (true, Type::Map(_)) => {
+                        let val = VarExprFragment::new(&format!("{}", index + 1), Type::Generic);
+                        let var = VarStmtFragment::new(&name, Type::Generic, val.to_frag()).with_local(true);
+                        result.push(var.with_optimization_when_unused(false).to_frag());
+                    },
                     _ => result.push(var.to_frag()),
                 }
             }
@@ -80,6 +88,112 @@ impl FunctionDeclaration {
         }
     }
 
+    /// Parses the arguments, the return type and the body that follow the function name
+    pub fn parse_signature_and_body(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        // Get the arguments
+        token(meta, "(")?;
+        loop {
+            if token(meta, ")").is_ok() {
+                break;
+            }
+            let is_ref = token(meta, "ref").is_ok();
+            let name_token = meta.get_current_token();
+            let name = variable(meta, variable_name_extensions())?;
+
+            // Optionally parse the argument type
+            let arg_type = match token(meta, ":") {
+                Ok(_) => parse_type(meta)?,
+                Err(_) => Type::Generic,
+            };
+
+            // Optionally parse default value
+            let optional_expr = match token(meta, "=") {
+                Ok(_) => {
+                    let mut expr = Expr::new();
+                    syntax(meta, &mut expr)?;
+                    Some(expr)
+                }
+                Err(_) => None,
+            };
+
+            self.args.push(FunctionDeclarationArgument {
+                name,
+                kind: arg_type,
+                optional: optional_expr,
+                is_ref,
+                tok: name_token,
+            });
+            match token(meta, ")") {
+                Ok(_) => break,
+                Err(_) => token(meta, ",")?,
+            };
+        }
+        let mut returns_tok = None;
+        let mut question_tok = None;
+        // Optionally parse the return type
+        match token(meta, ":") {
+            Ok(_) => {
+                returns_tok = meta.get_current_token();
+                self.returns = parse_type(meta)?;
+                question_tok = meta.get_current_token();
+                if token(meta, "?").is_ok() {
+                    self.declared_failable = true;
+                }
+            }
+            Err(_) => self.returns = Type::Generic,
+        }
+        // Parse the body
+        let start_pos = meta.get_index();
+        token(meta, "{")?;
+        let (_, _, is_failable) = skip_function_body(meta);
+        meta.set_index(start_pos);
+
+        self.is_failable = is_failable;
+        if self.returns == Type::Generic {
+            self.declared_failable = is_failable;
+        }
+
+        // Validate failable function declarations
+        if is_failable && !self.declared_failable {
+            return error!(
+                meta,
+                returns_tok, "Failable functions must have a '?' after the type name"
+            );
+        }
+        if !is_failable && self.declared_failable {
+            return error!(
+                meta,
+                question_tok.or(returns_tok),
+                "Infallible functions must not have a '?' after the type name"
+            );
+        }
+
+        // Store function body for typecheck phase
+        let mut block = Block::new().with_condition();
+        let was_fun_ctx = meta.context.is_fun_ctx;
+        meta.context.is_fun_ctx = true;
+        let result = syntax(meta, &mut block);
+        meta.context.is_fun_ctx = was_fun_ctx;
+        result?;
+        self.function_body = Some(block);
+        Ok(())
+    }
+
+    /// Wraps the function instance so that it can be called through a function value.
+    /// The return value is stored in a variable shared by all the function values.
+    fn translate_reference(fun_meta: &FunctionMetadata, name: &str) -> FragmentKind {
+        let mut body = vec![raw_fragment!("{name} \"$@\"")];
+        let returns = fun_meta.get_type();
+        if returns != Type::Null {
+            let value = VarExprFragment::new(&fun_meta.mangled_name(), returns.clone()).to_frag();
+            let ret = VarStmtFragment::new(FUNCTION_VALUE_RETURN, returns, value)
+                .with_optimization_when_unused(false);
+            body.push(ret.to_frag());
+        }
+        let body = BlockFragment::new(body, true).to_frag();
+        FunctionFragment::new(&fun_meta.reference_name(), None, body).to_frag()
+    }
+
     fn get_space(&self, parentheses: usize, before: &str, word: &str) -> String {
         if parentheses == 0 && word == "("
             || word == ":"
@@ -140,6 +254,7 @@ impl SyntaxModule<ParserMetadata> for FunctionDeclaration {
             args: vec![],
             returns: Type::Generic,
             flags: HashSet::new(),
+            requires: vec![],
             id: 0,
             is_public: false,
             comment: None,
@@ -159,9 +274,17 @@ impl SyntaxModule<ParserMetadata> for FunctionDeclaration {
             self.comment = Some(comment);
         }
         // Get all the user-defined compiler flags
-        while let Ok(flag) = token_by(meta, |val| val.starts_with("#[")) {
-            // Push to the flags vector as it is more safe in case of parsing errors
-            self.flags.insert(get_ccflag_by_name(&flag[2..flag.len() - 1]));
+        while let Some(tok) = meta.get_current_token().filter(|tok| tok.word.starts_with("#[")) {
+            meta.increment_index();
+            let flag = &tok.word[2..tok.word.len() - 1];
+            match parse_required_commands(flag) {
+                Some(Ok(commands)) => self.requires.extend(commands),
+                Some(Err(message)) => return error!(meta, Some(tok), message),
+                // Push to the flags vector as it is more safe in case of parsing errors
+                None => {
+                    self.flags.insert(get_ccflag_by_name(flag));
+                }
+            }
         }
         let doc_index = meta.get_index();
         // Check if this function is public
@@ -172,96 +295,8 @@ impl SyntaxModule<ParserMetadata> for FunctionDeclaration {
         // Get the function name
         self.name_token = meta.get_current_token();
         self.name = variable(meta, variable_name_extensions())?;
-        let mut optional = false;
         context!({
-            // Get the arguments
-            token(meta, "(")?;
-            loop {
-                if token(meta, ")").is_ok() {
-                    break;
-                }
-                let is_ref = token(meta, "ref").is_ok();
-                let name_token = meta.get_current_token();
-                let name = variable(meta, variable_name_extensions())?;
-
-                // Optionally parse the argument type
-                let arg_type = match token(meta, ":") {
-                    Ok(_) => parse_type(meta)?,
-                    Err(_) => Type::Generic,
-                };
-
-                // Optionally parse default value
-                let optional_expr = match token(meta, "=") {
-                    Ok(_) => {
-                        optional = true;
-                        let mut expr = Expr::new();
-                        syntax(meta, &mut expr)?;
-                        Some(expr)
-                    }
-                    Err(_) => None,
-                };
-
-                self.args.push(FunctionDeclarationArgument {
-                    name,
-                    kind: arg_type,
-                    optional: optional_expr,
-                    is_ref,
-                    tok: name_token,
-                });
-                match token(meta, ")") {
-                    Ok(_) => break,
-                    Err(_) => token(meta, ",")?,
-                };
-            }
-            let mut returns_tok = None;
-            let mut question_tok = None;
-            // Optionally parse the return type
-            match token(meta, ":") {
-                Ok(_) => {
-                    returns_tok = meta.get_current_token();
-                    self.returns = parse_type(meta)?;
-                    question_tok = meta.get_current_token();
-                    if token(meta, "?").is_ok() {
-                        self.declared_failable = true;
-                    }
-                }
-                Err(_) => self.returns = Type::Generic,
-            }
-            // Parse the body
-            let start_pos = meta.get_index();
-            token(meta, "{")?;
-            let (_, _, is_failable) = skip_function_body(meta);
-            meta.set_index(start_pos);
-
-            self.is_failable = is_failable;
-            if self.returns == Type::Generic {
-                self.declared_failable = is_failable;
-            }
-
-            // Validate failable function declarations
-            if is_failable && !self.declared_failable {
-                return error!(
-                    meta,
-                    returns_tok, "Failable functions must have a '?' after the type name"
-                );
-            }
-            if !is_failable && self.declared_failable {
-                return error!(
-                    meta,
-                    question_tok.or(returns_tok),
-                    "Infallible functions must not have a '?' after the type name"
-                );
-            }
-
-            // Store function body for typecheck phase
-            let mut block = Block::new().with_condition();
-            let was_fun_ctx = meta.context.is_fun_ctx;
-            meta.context.is_fun_ctx = true;
-            let result = syntax(meta, &mut block);
-            meta.context.is_fun_ctx = was_fun_ctx;
-            result?;
-            self.function_body = Some(block);
-
+            self.parse_signature_and_body(meta)?;
             self.doc_signature = Some(self.render_function_signature(meta, doc_index)?);
             Ok(())
         }, |pos| {
@@ -289,13 +324,15 @@ impl TypeCheckModule for FunctionDeclaration {
         meta.with_context_fn(Context::set_cc_flags, self.flags.clone(), |meta| {
             // Check for duplicate argument names
             let mut seen_argument_names = HashSet::new();
-            for arg in &self.args {
+            for arg in &mut self.args {
                 if !seen_argument_names.insert(arg.name.clone()) {
                     return error!(meta, arg.tok.clone(),
                         format!("Argument '{}' is already defined", arg.name)
                     );
                 }
+                arg.kind = resolve_type(meta, &arg.kind, arg.tok.clone())?;
             }
+            self.returns = resolve_type(meta, &self.returns, self.name_token.clone())?;
 
             // Validate optional arguments
             // Typecheck and validate optional arguments
@@ -366,16 +403,21 @@ impl TranslateModule for FunctionDeclaration {
                 &self.returns,
             ));
             // Parse the function body
-            let name = raw_fragment!("{}{}__{}_v{}", prefix, self.name, self.id, index);
-            result.push(fragments!(name, "() {"));
-            if let Some(args) = self.set_args_as_variables(meta, function) {
-                result.push(args);
+            let name = format!("{}{}__{}_v{}", prefix, self.name, self.id, index);
+            let args = self.set_args_as_variables(meta, function);
+            let body = function.block.translate(meta);
+            result.push(FunctionFragment::new(&name, args, body).to_frag());
+            if meta.fun_cache.is_referenced(self.id, index) {
+                let fun_meta = FunctionMetadata::new(&self.name, self.id, index, &function.returns);
+                result.push(Self::translate_reference(&fun_meta, &name));
             }
-            result.push(function.block.translate(meta));
-            result.push(fragments!("}\n"));
         }
         // Restore the function name
         meta.fun_meta = prev_fun_meta;
+        // The commands are required only if the function is used
+        if !blocks.is_empty() {
+            meta.required_commands.extend(self.requires.iter().cloned());
+        }
         // Return the translation
         BlockFragment::new(result, false).to_frag()
     }
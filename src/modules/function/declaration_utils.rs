@@ -3,6 +3,7 @@ use crate::modules::types::Type;
 use crate::modules::variable::handle_identifier_name;
 use crate::utils::context::Context;
 use crate::utils::function_interface::FunctionInterface;
+use crate::utils::symbol_table::SymbolId;
 use crate::utils::ParserMetadata;
 use heraclitus_compiler::prelude::*;
 
@@ -99,7 +100,12 @@ pub fn handle_add_function(
     // Try to add the function to the memory
     match meta.add_fun_declaration(fun, ctx, block) {
         // Return the id of the function
-        Some(id) => Ok(id),
+        Some(id) => {
+            if let Some(decl) = meta.get_fun_declaration(&name).cloned() {
+                meta.add_symbol_definition(SymbolId::Function(id), &name, decl.render_signature(), tok);
+            }
+            Ok(id)
+        }
         // If the function already exists, show an error
         None => error!(meta, tok, format!("Function '{}' already exists", name)),
     }
@@ -2,17 +2,20 @@ use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::{fragments, raw_fragment};
 use crate::docs::module::DocumentationModule;
+use crate::modules::condition::failure_handler::{trace_location, translate_trace_frame};
 use crate::modules::expression::expr::Expr;
 use crate::modules::prelude::FragmentKind;
 use crate::modules::types::{Type, Typed};
 use crate::utils::metadata::{ParserMetadata, TranslateMetadata};
 use crate::translate::module::TranslateModule;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fail {
     pub expr: Expr,
     pub code: String,
-    pub is_main: bool
+    pub is_main: bool,
+    trace_location: String,
 }
 
 impl Typed for Fail {
@@ -28,12 +31,15 @@ impl SyntaxModule<ParserMetadata> for Fail {
         Fail {
             expr: Expr::new(),
             code: String::new(),
-            is_main: false
+            is_main: false,
+            trace_location: String::new(),
         }
     }
 
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let fail_tok = meta.get_current_token();
         token(meta, "fail")?;
+        self.trace_location = trace_location(&PositionInfo::from_token(meta, fail_tok));
         let tok = meta.get_current_token();
         if !meta.context.is_fun_ctx && !meta.context.is_main_ctx {
             return error!(meta, tok => {
@@ -97,6 +103,10 @@ impl TranslateModule for Fail {
         } else {
             raw_fragment!("{}", &self.code)
         };
+        if meta.trace_failures {
+            let trace = translate_trace_frame(meta, &self.trace_location, true);
+            meta.stmt_queue.push_back(trace);
+        }
         if self.is_main {
             fragments!("exit ", translate)
         } else {
@@ -9,9 +9,14 @@ use crate::modules::condition::failure_handler::FailureHandler;
 use crate::modules::types::{Type, Typed};
 use crate::modules::variable::variable_name_extensions;
 use crate::modules::expression::expr::{Expr, ExprType};
+use crate::modules::variable::handle_variable_reference;
+use crate::utils::context::VariableDecl;
+use crate::utils::function_metadata::FUNCTION_VALUE_RETURN;
+use crate::utils::pluralize;
 use super::invocation_utils::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionInvocation {
     name: String,
     name_tok: Option<Token>,
@@ -24,7 +29,9 @@ pub struct FunctionInvocation {
     col: usize,
     failure_handler: FailureHandler,
     modifier: CommandModifier,
-    is_failable: bool
+    is_failable: bool,
+    /// Variable holding the called function value
+    function_value: Option<VariableDecl>,
 }
 
 impl Typed for FunctionInvocation {
@@ -35,7 +42,7 @@ impl Typed for FunctionInvocation {
 
 fn is_ref(expr: &Expr) -> bool {
     match &expr.value {
-        Some(ExprType::VariableGet(var)) => !var.is_variable_modified(),
+        Some(ExprType::VariableGet(var)) => !var.is_variable_modified() && !var.is_function_reference(),
         _ => false,
     }
 }
@@ -56,7 +63,8 @@ impl SyntaxModule<ParserMetadata> for FunctionInvocation {
             col: 0,
             failure_handler: FailureHandler::new(),
             modifier: CommandModifier::new_expr(),
-            is_failable: false
+            is_failable: false,
+            function_value: None,
         }
     }
 
@@ -105,6 +113,21 @@ impl TypeCheckModule for FunctionInvocation {
             arg.typecheck(meta)?;
         }
 
+        // Variables holding function values take precedence over the declared functions
+        match meta.get_var(&self.name).map(|var| var.kind.clone()) {
+            Some(Type::Function(args, returns)) => {
+                return self.typecheck_function_value(meta, &args, *returns);
+            }
+            Some(kind) if meta.get_fun_declaration(&self.name).is_none() => {
+                let expected = Type::Function(self.args.iter().map(Expr::get_type).collect(), Box::new(Type::Null));
+                return error!(meta, self.name_tok.clone() => {
+                    message: format!("Variable '{}' of type '{kind}' cannot be called as a function", self.name),
+                    comment: format!("Expected type '{expected}', but '{kind}' was given")
+                });
+            }
+            _ => {}
+        }
+
         // Look up the function declaration (this requires typecheck phase context)
         self.id = handle_function_reference(meta, self.name_tok.clone(), &self.name)?;
 
@@ -126,6 +149,10 @@ impl TypeCheckModule for FunctionInvocation {
             }
         }
 
+        for arg in self.args.iter().filter(|arg| arg.get_type().is_array()) {
+            meta.require_bash(arg.get_position(), "Array arguments")?;
+        }
+
         // Validate arguments and get function variant
         let types = self.args.iter().map(Expr::get_type).collect::<Vec<Type>>();
         let var_refs = self.args.iter().map(is_ref).collect::<Vec<bool>>();
@@ -162,25 +189,73 @@ impl TypeCheckModule for FunctionInvocation {
     }
 }
 
+impl FunctionInvocation {
+    fn typecheck_function_value(&mut self, meta: &mut ParserMetadata, args: &[Type], returns: Type) -> SyntaxResult {
+        let variable = handle_variable_reference(meta, &self.name_tok, &self.name)?;
+        if args.len() != self.args.len() {
+            let txt_arguments = pluralize(args.len(), "argument", "arguments");
+            let txt_given = pluralize(self.args.len(), "was given", "were given");
+            return error!(meta, self.name_tok.clone(), format!(
+                "Function value '{}' expects {} {txt_arguments}, but {} {txt_given}",
+                self.name, args.len(), self.args.len()
+            ));
+        }
+        for (index, (arg, kind)) in izip!(self.args.iter(), args.iter()).enumerate() {
+            let given_type = arg.get_type();
+            if !given_type.is_allowed_in(kind) {
+                let ordinal = ordinal_number(index);
+                return error!(meta, self.name_tok.clone(), format!(
+                    "{ordinal} argument of function value '{}' expects type '{kind}', but '{given_type}' was given",
+                    self.name
+                ));
+            }
+        }
+        if self.failure_handler.is_parsed && !meta.context.is_trust_ctx {
+            let message = Message::new_warn_at_token(meta, self.name_tok.clone())
+                .message(format!("Function value '{}' cannot fail", self.name))
+                .comment("You can remove the failure handler block or '?' at the end");
+            meta.add_message(message);
+        }
+        self.refs = vec![false; args.len()];
+        self.kind = returns;
+        self.function_value = Some(variable);
+        Ok(())
+    }
+
+    fn translate_name(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        match &self.function_value {
+            Some(variable) => VarExprFragment::new(&variable.name, variable.kind.clone())
+                .with_global_id(variable.global_id)
+                .with_ref(variable.is_ref)
+                .to_frag(),
+            None => {
+                // Get the variable prefix based on function name casing
+                let prefix = meta.gen_variable_prefix(&self.name);
+                raw_fragment!("{}{}__{}_v{}", prefix, self.name, self.id, self.variant_id)
+            }
+        }
+    }
+}
+
 impl TranslateModule for FunctionInvocation {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
-        // Get the variable prefix based on function name casing
-        let prefix = meta.gen_variable_prefix(&self.name);
-        let name = raw_fragment!("{}{}__{}_v{}", prefix, self.name, self.id, self.variant_id);
+        let name = self.translate_name(meta);
         let mut is_silent = self.modifier.is_silent || meta.silenced;
         swap(&mut is_silent, &mut meta.silenced);
         let silent = meta.gen_silent().to_frag();
 
         let args = izip!(self.args.iter(), self.refs.iter()).map(| (arg, is_ref) | match arg.translate(meta) {
             FragmentKind::VarExpr(var) if *is_ref => var.with_render_type(VarRenderType::BashRef).to_frag(),
-            FragmentKind::VarExpr(var) if var.kind.is_array() && var.index.is_some() => {
+            FragmentKind::VarExpr(var) if var.kind.is_bash_array() && var.index.is_some() => {
                 let id = meta.gen_value_id();
                 let temp_name = format!("{}_{id}", var.get_index_typename());
                 let stmt = VarStmtFragment::new(&temp_name, var.kind.clone(), FragmentKind::VarExpr(var.clone()));
                 let temp_var = meta.push_ephemeral_variable(stmt);
                 fragments!(temp_var.with_render_type(VarRenderType::BashRef).to_frag().with_quotes(false), "[@]")
             },
-            FragmentKind::VarExpr(var) if var.kind.is_array() => fragments!(var.with_render_type(VarRenderType::BashRef).to_frag().with_quotes(false), "[@]"),
+            FragmentKind::VarExpr(var) if var.kind.is_bash_array() => fragments!(var.with_render_type(VarRenderType::BashRef).to_frag().with_quotes(false), "[@]"),
+            // Maps are passed by name and copied by the function
+            FragmentKind::VarExpr(var) if var.kind.is_map() => var.with_render_type(VarRenderType::BashRef).to_frag(),
             _ if *is_ref => panic!("Reference value accepts only variables"),
             var => var
         }).collect::<Vec<FragmentKind>>();
@@ -191,7 +266,12 @@ impl TranslateModule for FunctionInvocation {
             let handler = self.failure_handler.translate(meta);
             meta.stmt_queue.push_back(handler);
         }
-        if self.kind != Type::Null {
+        if self.kind != Type::Null && self.function_value.is_some() {
+            let invocation_instance = format!("{}__{}_{}", FUNCTION_VALUE_RETURN, self.line, self.col);
+            let value = VarExprFragment::new(FUNCTION_VALUE_RETURN, self.kind.clone()).to_frag();
+            let var_stmt = VarStmtFragment::new(&invocation_instance, self.kind.clone(), value);
+            meta.push_ephemeral_variable(var_stmt).to_frag()
+        } else if self.kind != Type::Null {
             // Get the variable prefix for return values
             let prefix = meta.gen_variable_prefix(&self.name);
             let invocation_return = format!("{}ret_{}{}_v{}", prefix, self.name, self.id, self.variant_id);
@@ -1,6 +1,7 @@
 use crate::modules::typecheck::TypeCheckModule;
 use crate::modules::types::Type;
 use crate::utils::context::{FunctionDecl, VariableDecl, VariableDeclWarn};
+use crate::utils::symbol_table::SymbolId;
 use crate::utils::{pluralize, ParserMetadata};
 use heraclitus_compiler::prelude::*;
 use itertools::izip;
@@ -8,7 +9,7 @@ use similar_string::find_best_similarity;
 
 // Convert a number to an ordinal number
 // Eg. 1 -> 1st, 2 -> 2nd, 3 -> 3rd, 4 -> 4th
-fn ordinal_number(index: usize) -> String {
+pub fn ordinal_number(index: usize) -> String {
     let index = index + 1;
     let mut result = index.to_string();
     let last_digit = index % 10;
@@ -113,7 +114,12 @@ fn run_function_with_args(
                 let var = VariableDecl::new(arg.name.clone(), kind.clone())
                     .with_warn(VariableDeclWarn::from_token(meta, tok.clone()))
                     .with_ref(arg.is_ref);
-                args_global_ids.push(meta.add_var(var));
+                let global_id = meta.add_var(var);
+                if let Some(id) = global_id {
+                    let signature = format!("{}: {kind}", arg.name);
+                    meta.add_symbol_definition(SymbolId::Variable(id), &arg.name, signature, arg.tok.clone());
+                }
+                args_global_ids.push(global_id);
             }
             // Set the expected return type if specified
             if fun.returns != Type::Generic {
@@ -161,12 +167,16 @@ fn run_function_with_args(
 }
 
 pub fn handle_function_reference(
-    meta: &ParserMetadata,
+    meta: &mut ParserMetadata,
     tok: Option<Token>,
     name: &str,
 ) -> Result<usize, Failure> {
     match meta.get_fun_declaration(name) {
-        Some(fun_decl) => Ok(fun_decl.id),
+        Some(fun_decl) => {
+            let id = fun_decl.id;
+            meta.add_symbol_reference(SymbolId::Function(id), tok);
+            Ok(id)
+        }
         None => {
             let message = format!("Function '{name}' does not exist");
             // Find other similar variable if exists
@@ -216,3 +226,30 @@ fn handle_similar_function(meta: &ParserMetadata, name: &str) -> Option<String>
         (score >= 0.75).then(|| format!("Did you mean '{match_name}'?"))
     })
 }
+
+/// Instantiates the function so that it can be used as a value and returns its function type and variant id
+pub fn handle_function_value(
+    meta: &mut ParserMetadata,
+    fun: FunctionDecl,
+    tok: Option<Token>,
+) -> Result<(Type, usize), Failure> {
+    let name = &fun.name;
+    if !fun.is_args_typed {
+        return error!(meta, tok => {
+            message: format!("Function '{name}' cannot be used as a value"),
+            comment: "Only functions with typed arguments can be used as values"
+        });
+    }
+    if fun.is_failable {
+        return error!(meta, tok, format!("Failable function '{name}' cannot be used as a value"));
+    }
+    if fun.args.iter().any(|arg| arg.is_ref) {
+        return error!(meta, tok, format!("Function '{name}' with reference arguments cannot be used as a value"));
+    }
+    let args = fun.args.iter().map(|arg| arg.kind.clone()).collect::<Vec<Type>>();
+    let vars = vec![false; args.len()];
+    let id = fun.id;
+    let (returns, variant_id) = handle_function_parameters(meta, id, fun, &args, &vars, tok)?;
+    meta.fun_cache.mark_referenced(id, variant_id);
+    Ok((Type::Function(args, Box::new(returns)), variant_id))
+}
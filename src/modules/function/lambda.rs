@@ -0,0 +1,112 @@
+use heraclitus_compiler::prelude::*;
+use crate::modules::prelude::*;
+use crate::modules::block::Block;
+use crate::modules::types::{resolve_type, Type, Typed};
+use crate::raw_fragment;
+use crate::utils::function_interface::FunctionInterface;
+use crate::utils::function_metadata::FunctionMetadata;
+use super::declaration::FunctionDeclaration;
+use super::invocation_utils::handle_function_value;
+use serde::{Deserialize, Serialize};
+
+/// Anonymous function that does not capture any local variables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lambda {
+    declaration: FunctionDeclaration,
+    kind: Type,
+    variant_id: usize,
+    tok: Option<Token>,
+}
+
+impl Typed for Lambda {
+    fn get_type(&self) -> Type {
+        self.kind.clone()
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for Lambda {
+    syntax_name!("Lambda");
+
+    fn new() -> Self {
+        Lambda {
+            declaration: FunctionDeclaration::new(),
+            kind: Type::Null,
+            variant_id: 0,
+            tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        token(meta, "fun")?;
+        self.declaration.name = "lambda".to_string();
+        self.declaration.name_token = self.tok.clone();
+        self.declaration.parse_signature_and_body(meta)?;
+        if self.declaration.is_failable {
+            return error!(meta, self.tok.clone() => {
+                message: "Lambdas cannot fail",
+                comment: "Handle the failures inside of the lambda body"
+            })
+        }
+        Ok(())
+    }
+}
+
+impl TypeCheckModule for Lambda {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        for arg in &mut self.declaration.args {
+            if arg.kind == Type::Generic {
+                return error!(meta, arg.tok.clone(), format!("Lambda argument '{}' must have a type", arg.name))
+            }
+            if arg.is_ref {
+                return error!(meta, arg.tok.clone(), "Lambda arguments cannot be passed by reference")
+            }
+            arg.kind = resolve_type(meta, &arg.kind, arg.tok.clone())?;
+        }
+        self.declaration.returns = resolve_type(meta, &self.declaration.returns, self.tok.clone())?;
+
+        // Lambdas can only access the global scope as they do not capture local variables
+        let mut ctx = meta.context.clone();
+        ctx.scopes.truncate(1);
+        ctx.is_fun_ctx = true;
+        ctx.is_loop_ctx = false;
+        ctx.is_main_ctx = false;
+        ctx.is_trust_ctx = false;
+        ctx.fun_ret_type = None;
+        ctx.expr.clear();
+
+        let id = meta.gen_fun_id();
+        let block = self.declaration.function_body.clone().unwrap_or_else(Block::new);
+        meta.fun_cache.add_declaration(id, ctx, block);
+        let fun = FunctionInterface {
+            id: Some(id),
+            name: self.declaration.name.clone(),
+            args: self.declaration.args.clone(),
+            returns: self.declaration.returns.clone(),
+            is_public: false,
+            is_failable: false,
+        }.into_fun_declaration(id);
+        (self.kind, self.variant_id) = handle_function_value(meta, fun, self.tok.clone())?;
+        self.declaration.id = id;
+        Ok(())
+    }
+}
+
+impl TranslateModule for Lambda {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        // Lambdas are defined right before the statement that uses them
+        let definition = meta.with_expr_ctx(false, |meta| self.declaration.translate(meta));
+        meta.stmt_queue.push_back(definition);
+        let Type::Function(_, returns) = &self.kind else {
+            unreachable!("Lambda has to be of a function type")
+        };
+        let fun_meta = FunctionMetadata::new(&self.declaration.name, self.declaration.id, self.variant_id, returns);
+        raw_fragment!("{}", fun_meta.reference_name())
+    }
+}
+
+impl DocumentationModule for Lambda {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
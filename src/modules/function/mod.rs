@@ -2,5 +2,6 @@ pub mod declaration;
 pub mod declaration_utils;
 pub mod invocation;
 pub mod invocation_utils;
+pub mod lambda;
 pub mod ret;
 pub mod fail;
@@ -4,8 +4,9 @@ use crate::modules::prelude::*;
 use crate::modules::expression::expr::Expr;
 use crate::modules::types::{Type, Typed};
 use crate::utils::function_metadata::FunctionMetadata;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Return {
     pub expr: Expr
 }
@@ -5,10 +5,13 @@ use crate::compiler::{AmberCompiler, CompilerOptions};
 use crate::modules::block::Block;
 use crate::modules::variable::variable_name_extensions;
 use crate::stdlib;
-use crate::utils::context::{Context, FunctionDecl};
+use crate::utils::context::{Context, EnumDecl, FunctionDecl, StructDecl};
+use crate::utils::import_cache::{FileCache, FileMetadata};
+use crate::utils::module_cache::ModuleCache;
 use super::import_string::ImportString;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Import {
     path: ImportString,
     token_import: Option<Token>,
@@ -19,10 +22,48 @@ pub struct Import {
 }
 
 impl Import {
-    fn handle_export(&mut self, meta: &mut ParserMetadata, mut pub_funs: Vec<FunctionDecl>) -> SyntaxResult {
+    fn handle_struct_export(&self, meta: &mut ParserMetadata, mut decl: StructDecl, tok: Option<Token>) -> SyntaxResult {
+        decl.is_public = self.is_pub;
+        let name = decl.name.clone();
+        if meta.get_enum_declaration(&name).is_some() || !meta.add_struct_declaration(decl) {
+            return error!(meta, tok, format!("Struct '{}' is already defined", name))
+        }
+        Ok(())
+    }
+
+    fn handle_enum_export(&self, meta: &mut ParserMetadata, mut decl: EnumDecl, tok: Option<Token>) -> SyntaxResult {
+        decl.is_public = self.is_pub;
+        let name = decl.name.clone();
+        if meta.get_struct_declaration(&name).is_some() || !meta.add_enum_declaration(decl) {
+            return error!(meta, tok, format!("Enum '{}' is already defined", name))
+        }
+        Ok(())
+    }
+
+    fn handle_export(
+        &mut self,
+        meta: &mut ParserMetadata,
+        mut pub_funs: Vec<FunctionDecl>,
+        pub_structs: Vec<StructDecl>,
+        pub_enums: Vec<EnumDecl>,
+    ) -> SyntaxResult {
         if !self.is_all {
             for def in self.export_defs.iter() {
                 let (name, alias, tok) = def.clone();
+                if let Some(decl) = pub_structs.iter().find(|decl| decl.name == name) {
+                    if alias.is_some() {
+                        return error!(meta, tok, format!("Struct '{}' cannot be imported under a different name", name))
+                    }
+                    self.handle_struct_export(meta, decl.clone(), tok)?;
+                    continue;
+                }
+                if let Some(decl) = pub_enums.iter().find(|decl| decl.name == name) {
+                    if alias.is_some() {
+                        return error!(meta, tok, format!("Enum '{}' cannot be imported under a different name", name))
+                    }
+                    self.handle_enum_export(meta, decl.clone(), tok)?;
+                    continue;
+                }
                 let fun = match pub_funs.iter_mut().find(|fun| fun.name == name) {
                     Some(fun) => fun,
                     // Check if the function that is being imported is defined
@@ -53,6 +94,12 @@ impl Import {
                     })
                 }
             }
+            for decl in pub_structs {
+                self.handle_struct_export(meta, decl, self.token_import.clone())?;
+            }
+            for decl in pub_enums {
+                self.handle_enum_export(meta, decl, self.token_import.clone())?;
+            }
         }
         Ok(())
     }
@@ -85,17 +132,43 @@ impl Import {
         }
     }
 
+    /// Checks if the code of the imported file is still the same as when it was type checked
+    fn is_unchanged(file: &FileCache) -> bool {
+        let code = match file.path.strip_prefix("std/") {
+            Some(path) => stdlib::resolve(path.to_string()),
+            None => fs::read_to_string(&file.path).ok(),
+        };
+        code.zip(file.metadata.as_ref())
+            .is_some_and(|(code, metadata)| ModuleCache::key("", &code) == metadata.hash)
+    }
+
     fn handle_import(&mut self, meta: &mut ParserMetadata, code: String) -> SyntaxResult {
         // If the import was already cached, we don't need to recompile it
-        match meta.import_cache.get_import_pub_funs(Some(self.path.value.clone())) {
-            Some(pub_funs) => self.handle_export(meta, pub_funs),
+        let path = Some(self.path.value.clone());
+        match meta.import_cache.get_import_pub_funs(path.clone()) {
+            Some(pub_funs) => {
+                let pub_structs = meta.import_cache.get_import_pub_structs(path.clone()).unwrap_or_default();
+                let pub_enums = meta.import_cache.get_import_pub_enums(path).unwrap_or_default();
+                self.handle_export(meta, pub_funs, pub_structs, pub_enums)
+            }
             None => self.handle_compile_code(meta, code)
         }
     }
 
     fn handle_compile_code(&mut self, meta: &mut ParserMetadata, code: String) -> SyntaxResult {
+        let path = self.path.value.clone();
+        let hash = ModuleCache::key("", &code);
+        // Type check the imported code unless neither it nor the state of the parser changed since the last compilation
+        let key = meta.checked_module_key(&path, &hash);
+        if let Some(checked) = key.as_ref().and_then(|key| meta.get_checked_module(&path, key, Self::is_unchanged)) {
+            meta.apply_changes(checked.changes);
+            let FileMetadata { pub_funs, pub_structs, pub_enums, .. } = checked.metadata.clone();
+            meta.import_cache.add_import_metadata(Some(path), checked.metadata);
+            return self.handle_export(meta, pub_funs, pub_structs, pub_enums);
+        }
+        let snapshot = meta.snapshot();
         let options = CompilerOptions::default();
-        let compiler = AmberCompiler::new(code, Some(self.path.value.clone()), options);
+        let compiler = AmberCompiler::new(code.clone(), Some(self.path.value.clone()), options);
         match compiler.tokenize() {
             Ok(tokens) => {
                 let mut block = Block::new().with_no_syntax();
@@ -104,14 +177,33 @@ impl Import {
                 let mut context = Context::new(Some(self.path.value.clone()), tokens)
                     .file_import(&meta.context.trace, position);
                 meta.with_context_ref(&mut context, |meta| {
-                    // Parse imported code
-                    syntax(meta, &mut block)?;
+                    // Parse imported code unless it did not change since the last compilation
+                    match meta.get_cached_module(&code) {
+                        Some(cached) => block = cached,
+                        None => {
+                            let messages = meta.messages.len();
+                            syntax(meta, &mut block)?;
+                            if meta.messages.len() == messages {
+                                meta.cache_module(&code, &block);
+                            }
+                        }
+                    }
                     block.typecheck(meta)
                 })?;
                 // Persist compiled file to cache
-                meta.import_cache.add_import_metadata(Some(self.path.value.clone()), block, context.pub_funs.clone());
+                let metadata = FileMetadata {
+                    hash,
+                    block,
+                    pub_funs: context.pub_funs.clone(),
+                    pub_structs: context.pub_structs.clone(),
+                    pub_enums: context.pub_enums.clone(),
+                };
+                if let Some(key) = key {
+                    meta.cache_checked_module(path.clone(), key, metadata.clone(), &snapshot);
+                }
+                meta.import_cache.add_import_metadata(Some(path), metadata);
                 // Handle exports (add to current file)
-                self.handle_export(meta, context.pub_funs)?;
+                self.handle_export(meta, context.pub_funs, context.pub_structs, context.pub_enums)?;
                 Ok(())
             }
             Err(err) => Err(Failure::Loud(err))
@@ -1,8 +1,10 @@
 use heraclitus_compiler::prelude::*;
 use std::path::Path;
+use crate::compiler::package::{self, PACKAGE_PREFIX};
 use crate::utils::ParserMetadata;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportString {
     pub value: String
 }
@@ -12,6 +14,9 @@ impl ImportString {
         if self.value.starts_with("std/") {
             return Ok(())
         }
+        if self.value.starts_with(PACKAGE_PREFIX) {
+            return self.resolve_package(meta, tok)
+        }
         let mut path = meta.context.path.as_ref()
             .map_or_else(|| Path::new("."), |path| Path::new(path))
             .to_path_buf();
@@ -28,6 +33,21 @@ impl ImportString {
             })
         }
     }
+
+    fn resolve_package(&mut self, meta: &ParserMetadata, tok: Option<Token>) -> SyntaxResult {
+        match package::resolve_import(&self.value, meta.context.path.as_deref()) {
+            Ok(path) => {
+                self.value = path.to_string_lossy().to_string();
+                Ok(())
+            }
+            Err(err) => {
+                let mut message = Message::new_err_at_token(meta, tok)
+                    .message(err.message.unwrap_or_default());
+                message.comment = err.comment;
+                Err(Failure::Loud(message))
+            }
+        }
+    }
 }
 
 impl SyntaxModule<ParserMetadata> for ImportString {
@@ -1,8 +1,9 @@
 use crate::fragments;
 use crate::modules::prelude::*;
 use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Continue {
     tok: Option<Token>,
 }
@@ -3,8 +3,9 @@ use crate::fragments;
 use crate::modules::prelude::*;
 use crate::utils::context::Context;
 use crate::modules::block::Block;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InfiniteLoop {
     block: Block,
 }
@@ -6,14 +6,17 @@ use crate::modules::prelude::{RawFragment, FragmentKind};
 use crate::modules::types::{Typed, Type};
 use crate::modules::variable::variable_name_extensions;
 use crate::translate::fragments::get_variable_name;
+use crate::translate::fragments::var_expr::VarIndexValue;
 use crate::translate::module::TranslateModule;
 use crate::utils::context::{Context, VariableDecl, VariableDeclWarn};
 use crate::utils::metadata::{ParserMetadata, TranslateMetadata};
+use crate::utils::symbol_table::SymbolId;
 use crate::modules::block::Block;
 use crate::{fragments, raw_fragment};
 use crate::modules::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IterLoop {
     block: Block,
     iter_expr: Expr,
@@ -24,6 +27,8 @@ pub struct IterLoop {
     iter_global_id: Option<usize>,
     iter_type: Type,
     iter_index_tok: Option<Token>,
+    // Type of the iterated map values if the iterable is a map
+    map_value_type: Option<Type>,
 }
 
 impl SyntaxModule<ParserMetadata> for IterLoop {
@@ -40,6 +45,7 @@ impl SyntaxModule<ParserMetadata> for IterLoop {
             iter_global_id: None,
             iter_type: Type::Generic,
             iter_index_tok: None,
+            map_value_type: None,
         }
     }
 
@@ -64,6 +70,9 @@ impl SyntaxModule<ParserMetadata> for IterLoop {
 
 impl TranslateModule for IterLoop {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        if self.map_value_type.is_some() {
+            return self.translate_map(meta);
+        }
         let iter_path = self.translate_path(meta);
         let iter_name = raw_fragment!("{}", get_variable_name(&self.iter_name, self.iter_global_id));
 
@@ -80,11 +89,16 @@ impl TranslateModule for IterLoop {
             (Some(index), global_id) => {
                 let indent = TranslateMetadata::single_indent();
                 let index = get_variable_name(index, global_id);
+                let increment = if meta.target.is_posix() {
+                    format!("{indent}{index}=$(( {index} + 1 ))")
+                } else {
+                    format!("{indent}(( {index}++ )) || true")
+                };
                 BlockFragment::new(vec![
                     RawFragment::from(format!("{index}=0;")).to_frag(),
                     for_loop_prefix,
                     self.block.translate(meta),
-                    RawFragment::from(format!("{indent}(( {index}++ )) || true")).to_frag(),
+                    RawFragment::from(increment).to_frag(),
                     for_loop_suffix,
                 ], false).to_frag()
             },
@@ -106,6 +120,11 @@ impl TypeCheckModule for IterLoop {
         // Determine iterator type after typechecking
         self.iter_type = match self.iter_expr.get_type() {
             Type::Array(kind) => *kind,
+            // Iterating over a map yields its keys, or keys and values if two names are given
+            Type::Map(kind) => {
+                self.map_value_type = Some(*kind.clone());
+                if self.iter_index.is_some() { *kind } else { Type::Text }
+            }
             _ => {
                 let pos = self.iter_expr.get_position();
                 return error_pos!(meta, pos, "Expected iterable");
@@ -117,10 +136,19 @@ impl TypeCheckModule for IterLoop {
             let var = VariableDecl::new(self.iter_name.clone(), self.iter_type.clone())
                 .with_warn(VariableDeclWarn::from_token(meta, self.iter_name_tok.clone()));
             self.iter_global_id = meta.add_var(var);
+            if let Some(id) = self.iter_global_id {
+                let signature = format!("{}: {}", self.iter_name, self.iter_type);
+                meta.add_symbol_definition(SymbolId::Variable(id), &self.iter_name, signature, self.iter_name_tok.clone());
+            }
             if let Some(index) = self.iter_index.as_ref() {
-                let var = VariableDecl::new(index.clone(), Type::Int)
+                let index_type = if self.map_value_type.is_some() { Type::Text } else { Type::Int };
+                let var = VariableDecl::new(index.clone(), index_type.clone())
                     .with_warn(VariableDeclWarn::from_token(meta, self.iter_index_tok.clone()));
                 self.iter_index_global_id = meta.add_var(var);
+                if let Some(id) = self.iter_index_global_id {
+                    let signature = format!("{index}: {index_type}");
+                    meta.add_symbol_definition(SymbolId::Variable(id), index, signature, self.iter_index_tok.clone());
+                }
             }
             // Save loop context state and set it to true
             meta.with_context_fn(Context::set_is_loop_ctx, true, |meta| {
@@ -136,6 +164,37 @@ impl TypeCheckModule for IterLoop {
 }
 
 impl IterLoop {
+    fn translate_map(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        // Referenced maps are copied so that the keys can be used to look up the values
+        let map = match self.iter_expr.translate(meta) {
+            FragmentKind::VarExpr(var) if !var.is_ref => var,
+            value => {
+                let id = meta.gen_value_id();
+                let map_stmt = VarStmtFragment::new("__map", self.iter_expr.get_type(), value).with_global_id(id);
+                meta.push_ephemeral_variable(map_stmt)
+            }
+        };
+        let (key_name, key_id) = match self.iter_index.as_ref() {
+            Some(index) => (index, self.iter_index_global_id),
+            None => (&self.iter_name, self.iter_global_id),
+        };
+        let key = get_variable_name(key_name, key_id);
+        let mut block = self.block.translate(meta);
+        if let (Some(_), Some(value_type), FragmentKind::Block(block)) = (&self.iter_index, &self.map_value_type, &mut block) {
+            let value = map.clone()
+                .with_index_by_value(VarIndexValue::Key(raw_fragment!("${key}")))
+                .to_frag();
+            let value_stmt = VarStmtFragment::new(&self.iter_name, value_type.clone(), value)
+                .with_global_id(self.iter_global_id);
+            block.statements.insert(0, value_stmt.to_frag());
+        }
+        BlockFragment::new(vec![
+            fragments!("for ", raw_fragment!("{key}"), " in ", map.with_keys_getter(true).to_frag(), "; do"),
+            block,
+            fragments!("done"),
+        ], false).to_frag()
+    }
+
     fn translate_path(&self, meta: &mut TranslateMetadata) -> Option<FragmentKind> {
         if let Some(ExprType::LinesInvocation(value)) = &self.iter_expr.value {
             Some(value.translate_path(meta))
@@ -5,8 +5,9 @@ use crate::utils::context::Context;
 use crate::modules::block::Block;
 use crate::modules::expression::expr::Expr;
 use crate::modules::types::{Type, Typed};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhileLoop {
     condition: Expr,
     block: Block,
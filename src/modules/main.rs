@@ -1,15 +1,19 @@
 use heraclitus_compiler::prelude::*;
+use std::collections::HashSet;
 
 use crate::raw_fragment;
 use crate::modules::types::Type;
 use crate::modules::block::Block;
 use crate::modules::prelude::*;
-use crate::utils::context::{VariableDecl, VariableDeclWarn};
+use crate::utils::context::{Context, VariableDecl, VariableDeclWarn};
+use crate::utils::cc_flags::{CCFlags, get_ccflag_by_name, parse_required_commands};
 use crate::utils::metadata::ParserMetadata;
+use crate::utils::symbol_table::SymbolId;
 
 use super::variable::variable_name_extensions;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Main {
     pub args: Option<String>,
     pub args_tok: Option<Token>,
@@ -17,6 +21,10 @@ pub struct Main {
     pub block: Block,
     pub token: Option<Token>,
     pub is_skipped: bool,
+    /// Commands declared with the `requires` flag
+    pub requires: Vec<String>,
+    /// Compiler flags applied to the main block
+    pub flags: HashSet<CCFlags>,
 }
 
 impl SyntaxModule<ParserMetadata> for Main {
@@ -29,11 +37,25 @@ impl SyntaxModule<ParserMetadata> for Main {
             args_global_id: None,
             block: Block::new().with_no_indent(),
             token: None,
-            is_skipped: false
+            is_skipped: false,
+            requires: vec![],
+            flags: HashSet::new(),
         }
     }
 
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        // If these flags belong to a function, the parsing fails on the missing `main`
+        while let Some(tok) = meta.get_current_token().filter(|tok| tok.word.starts_with("#[")) {
+            meta.increment_index();
+            let flag = &tok.word[2..tok.word.len() - 1];
+            match parse_required_commands(flag) {
+                Some(Ok(commands)) => self.requires.extend(commands),
+                Some(Err(message)) => return error!(meta, Some(tok), message),
+                None => {
+                    self.flags.insert(get_ccflag_by_name(flag));
+                }
+            }
+        }
         self.token = meta.get_current_token();
         token(meta, "main")?;
         // If this main is included in other file, skip it
@@ -63,26 +85,38 @@ impl TypeCheckModule for Main {
         if !meta.is_global_scope() {
             return error!(meta, self.token.clone(), "Main must be in the global scope")
         }
+        if self.args.is_some() {
+            let pos = PositionInfo::from_token(meta, self.args_tok.clone());
+            meta.require_bash(pos, "Main arguments")?;
+        }
 
         // Typecheck the main block content
-        meta.with_push_scope(true, |meta| {
-            // Create variables for main arguments
-            for arg in self.args.iter() {
-                let var = VariableDecl::new(arg.clone(), Type::Array(Box::new(Type::Text)))
-                    .with_const(true)
-                    .with_warn(VariableDeclWarn::from_token(meta, self.args_tok.clone()));
-                self.args_global_id = Some(meta.add_var(var).unwrap());
-            }
-            // Typecheck the block
-            self.block.typecheck(meta)?;
-            Ok(())
+        meta.with_context_fn(Context::set_cc_flags, self.flags.clone(), |meta| {
+            meta.with_push_scope(true, |meta| {
+                // Create variables for main arguments
+                for arg in self.args.iter() {
+                    let var = VariableDecl::new(arg.clone(), Type::Array(Box::new(Type::Text)))
+                        .with_const(true)
+                        .with_warn(VariableDeclWarn::from_token(meta, self.args_tok.clone()));
+                    self.args_global_id = Some(meta.add_var(var).unwrap());
+                    if let Some(id) = self.args_global_id {
+                        let kind = Type::array_of(Type::Text);
+                        let signature = format!("{arg}: {kind}");
+                        meta.add_symbol_definition(SymbolId::Variable(id), arg, signature, self.args_tok.clone());
+                    }
+                }
+                // Typecheck the block
+                self.block.typecheck(meta)?;
+                Ok(())
+            })
         })
     }
 }
 
 impl TranslateModule for Main {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
-        if self.is_skipped {
+        // Tests are run in place of the main block
+        if self.is_skipped || meta.test_mode {
             FragmentKind::Empty
         } else {
             let quote = meta.gen_quote();
@@ -100,6 +134,7 @@ impl TranslateModule for Main {
             // other instances of code blocks, we do not want to indent
             // the code generated from the main block.
             meta.stmt_queue.push_back(args);
+            meta.required_commands.extend(self.requires.iter().cloned());
             self.block.translate(meta)
         }
     }
@@ -10,6 +10,9 @@ pub mod function;
 pub mod types;
 pub mod imports;
 pub mod main;
+pub mod test;
+pub mod structs;
+pub mod enums;
 pub mod builtin;
 pub mod prelude;
 pub mod typecheck;
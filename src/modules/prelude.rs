@@ -1,8 +1,10 @@
 /// This is a prelude module that re-exports all the necessary modules for syntax modules
 pub use crate::docs::module::DocumentationModule;
 pub use crate::translate::fragments::block::BlockFragment;
+pub use crate::translate::fragments::case::CaseFragment;
 pub use crate::translate::fragments::comment::CommentFragment;
 pub use crate::translate::fragments::fragment::{FragmentRenderable, FragmentKind};
+pub use crate::translate::fragments::function::FunctionFragment;
 pub use crate::translate::fragments::interpolable::{InterpolableFragment, InterpolableRenderType};
 pub use crate::translate::fragments::list::ListFragment;
 pub use crate::translate::fragments::raw::RawFragment;
@@ -4,11 +4,12 @@ use crate::modules::expression::expr::Expr;
 use crate::modules::variable::{handle_variable_reference, prevent_constant_mutation, variable_name_extensions};
 use crate::translate::compute::translate_computation_eval;
 use crate::translate::{compute::ArithOp, module::TranslateModule};
-use crate::modules::types::Type;
+use crate::modules::types::{Type, Typed};
 
 use super::shorthand_typecheck_allowed_types;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShorthandAdd {
     var: String,
     expr: Box<Expr>,
@@ -51,6 +52,11 @@ impl TypeCheckModule for ShorthandAdd {
         self.kind = variable.kind;
         self.global_id = variable.global_id;
         self.is_ref = variable.is_ref;
+        // The first values added to an untyped empty array determine its element type
+        if self.kind == Type::array_of(Type::Generic) && self.expr.get_type().is_array() {
+            self.kind = self.expr.get_type();
+            meta.set_var_type(&self.var, self.kind.clone());
+        }
         
         shorthand_typecheck_allowed_types(meta, "add", &self.kind, &self.expr, &[
             Type::Num,
@@ -7,8 +7,9 @@ use crate::translate::compute::ArithOp;
 use crate::modules::types::Type;
 
 use super::shorthand_typecheck_allowed_types;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShorthandSub {
     var: String,
     expr: Box<Expr>,
@@ -1,7 +1,8 @@
 use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Comment {
     pub value: String
 }
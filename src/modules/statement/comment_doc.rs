@@ -1,8 +1,9 @@
 use heraclitus_compiler::prelude::*;
 use crate::modules::prelude::*;
 use crate::utils::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommentDoc {
     pub value: String
 }
@@ -4,6 +4,7 @@ use crate::docs::module::DocumentationModule;
 use crate::utils::metadata::{ParserMetadata, TranslateMetadata};
 use crate::modules::expression::expr::Expr;
 use crate::translate::module::TranslateModule;
+use crate::compiler::source_map::SourceMap;
 use crate::modules::variable::{
     init::VariableInit,
     set::VariableSet,
@@ -16,6 +17,7 @@ use crate::{
 use crate::modules::condition::{
     ifchain::IfChain,
     ifcond::IfCondition,
+    match_stmt::Match,
 };
 use crate::modules::shorthand::{
     add::ShorthandAdd,
@@ -38,16 +40,21 @@ use crate::modules::function::{
 };
 use crate::modules::imports::import::Import;
 use crate::modules::main::Main;
+use crate::modules::test::Test;
+use crate::modules::structs::declaration::StructDeclaration;
+use crate::modules::enums::declaration::EnumDeclaration;
 use crate::modules::builtin::{
     echo::Echo,
     mv::Mv,
     cd::Cd,
     exit::Exit,
+    assert::Assert,
 };
 use super::comment_doc::CommentDoc;
 use super::comment::Comment;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StmtType {
     Expr(Expr),
     VariableInit(VariableInit),
@@ -69,22 +76,43 @@ pub enum StmtType {
     Fail(Fail),
     Import(Import),
     Main(Main),
+    Test(Test),
+    StructDeclaration(StructDeclaration),
+    EnumDeclaration(EnumDeclaration),
+    Match(Match),
     Cd(Cd),
     Echo(Echo),
     Mv(Mv),
     Exit(Exit),
+    Assert(Assert),
     Command(Command),
     CommandModifier(CommandModifier),
     Comment(Comment),
     CommentDoc(CommentDoc),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Statement {
-    pub value: Option<StmtType>
+    pub value: Option<StmtType>,
+    /// First token of the statement
+    pub tok: Option<Token>,
 }
 
 impl Statement {
+    /// Renders the comment marking the source line of this statement for the source map
+    pub fn translate_source_marker(&self, meta: &TranslateMetadata) -> Option<FragmentKind> {
+        if !meta.source_map || matches!(self.value, Some(StmtType::Comment(_) | StmtType::CommentDoc(_))) {
+            return None
+        }
+        // Function declarations start with their documentation and attributes
+        let tok = match &self.value {
+            Some(StmtType::FunctionDeclaration(fun)) => fun.name_token.as_ref().or(self.tok.as_ref()),
+            _ => self.tok.as_ref(),
+        };
+        let (line, _) = tok?.pos;
+        Some(CommentFragment::new(&SourceMap::marker(meta.source_index, line)).to_frag())
+    }
+
     pub fn get_docs_item_name(&self) -> Option<String> {
         match &self.value {
             Some(StmtType::FunctionDeclaration(inner)) => Some(inner.name.clone()),
@@ -98,24 +126,28 @@ impl SyntaxModule<ParserMetadata> for Statement {
 
     fn new() -> Self {
         Statement {
-            value: None
+            value: None,
+            tok: None,
         }
     }
 
     #[allow(unused_assignments)]
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
         // Order matters here
         parse_statement!([
             // Imports
             Import,
+            // Structs and enums
+            StructDeclaration, EnumDeclaration,
             // Functions
-            FunctionDeclaration, Main, Return, Fail,
+            FunctionDeclaration, Main, Test, Return, Fail,
             // Loops
             InfiniteLoop, IterLoop, WhileLoop, Break, Continue,
             // Conditions
-            IfChain, IfCondition,
+            IfChain, IfCondition, Match,
             // Command
-            CommandModifier, Echo, Mv, Cd, Exit, Command,
+            CommandModifier, Echo, Mv, Cd, Exit, Assert, Command,
             // Variables
             VariableInit, VariableSet,
             // Short hand
@@ -141,11 +173,11 @@ impl SyntaxModule<ParserMetadata> for Statement {
 impl TypeCheckModule for Statement {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         typecheck_statement!(meta, self.value.as_mut().unwrap(), [
-            Break, Cd, Command, CommandModifier, Comment, CommentDoc, Continue, Echo,
-            Exit, Expr, Fail, FunctionDeclaration, IfChain, IfCondition,
-            Import, InfiniteLoop, IterLoop, Main, Mv, Return, ShorthandAdd,
+            Assert, Break, Cd, Command, CommandModifier, Comment, CommentDoc, Continue, Echo,
+            EnumDeclaration, Exit, Expr, Fail, FunctionDeclaration, IfChain, IfCondition,
+            Import, InfiniteLoop, IterLoop, Main, Match, Mv, Return, ShorthandAdd,
             ShorthandDiv, ShorthandModulo, ShorthandMul, ShorthandSub,
-            VariableInit, VariableSet, WhileLoop
+            StructDeclaration, Test, VariableInit, VariableSet, WhileLoop
         ]);
         Ok(())
     }
@@ -158,10 +190,11 @@ impl TranslateModule for Statement {
         // This is a workaround that handles $(...) which cannot be used as a statement
         translate_statement!(statement, [
             Import,
-            FunctionDeclaration, Main, Return, Fail,
+            StructDeclaration, EnumDeclaration,
+            FunctionDeclaration, Main, Test, Return, Fail,
             InfiniteLoop, IterLoop, WhileLoop, Break, Continue,
-            IfChain, IfCondition,
-            CommandModifier, Echo, Mv, Cd, Exit, Command,
+            IfChain, IfCondition, Match,
+            CommandModifier, Echo, Mv, Cd, Exit, Assert, Command,
             VariableInit, VariableSet,
             ShorthandAdd, ShorthandSub,
             ShorthandMul, ShorthandDiv,
@@ -185,10 +218,11 @@ impl DocumentationModule for Statement {
         let statement = self.value.as_ref().unwrap();
         document_statement!(statement, [
             Import,
-            FunctionDeclaration, Main, Return, Fail,
+            StructDeclaration, EnumDeclaration,
+            FunctionDeclaration, Main, Test, Return, Fail,
             InfiniteLoop, IterLoop, WhileLoop, Break, Continue,
-            IfChain, IfCondition,
-            CommandModifier, Echo, Mv, Cd, Exit, Command,
+            IfChain, IfCondition, Match,
+            CommandModifier, Echo, Mv, Cd, Exit, Assert, Command,
             VariableInit, VariableSet,
             ShorthandAdd, ShorthandSub,
             ShorthandMul, ShorthandDiv,
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use heraclitus_compiler::prelude::*;
+use crate::modules::prelude::*;
+use crate::modules::types::{is_type_name, parse_type, resolve_type, Type};
+use crate::modules::variable::variable_name_extensions;
+use crate::utils::context::StructDecl;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructDeclaration {
+    pub name: String,
+    pub fields: Vec<(String, Type, Option<Token>)>,
+    pub is_public: bool,
+    tok: Option<Token>,
+    name_tok: Option<Token>,
+}
+
+impl StructDeclaration {
+    fn skip_empty_lines(meta: &mut ParserMetadata) {
+        while token_by(meta, |word| word.starts_with('\n') || word.starts_with("//")).is_ok() {}
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for StructDeclaration {
+    syntax_name!("Struct Declaration");
+
+    fn new() -> Self {
+        StructDeclaration {
+            name: String::new(),
+            fields: vec![],
+            is_public: false,
+            tok: None,
+            name_tok: None,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.tok = meta.get_current_token();
+        self.is_public = token(meta, "pub").is_ok();
+        token(meta, "struct")?;
+        self.name_tok = meta.get_current_token();
+        self.name = variable(meta, variable_name_extensions())?;
+        if !is_type_name(&self.name) {
+            return error!(meta, self.name_tok.clone() => {
+                message: format!("Struct name '{}' must be in pascal case", self.name),
+                comment: "Struct names start with an uppercase letter and contain only letters and digits, eg. 'HttpRequest'"
+            })
+        }
+        context!({
+            token(meta, "{")?;
+            loop {
+                Self::skip_empty_lines(meta);
+                if token(meta, "}").is_ok() {
+                    break;
+                }
+                let field_tok = meta.get_current_token();
+                let field = variable(meta, variable_name_extensions())?;
+                token(meta, ":")?;
+                let kind = parse_type(meta)?;
+                self.fields.push((field, kind, field_tok));
+                Self::skip_empty_lines(meta);
+                if token(meta, "}").is_ok() {
+                    break;
+                }
+                token(meta, ",")?;
+            }
+            Ok(())
+        }, |pos| {
+            error_pos!(meta, pos, format!("Failed to parse struct declaration '{}'", self.name))
+        })
+    }
+}
+
+impl TypeCheckModule for StructDeclaration {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        if !meta.is_global_scope() {
+            return error!(meta, self.tok.clone(), "Structs can only be declared in the global scope")
+        }
+        let pos = PositionInfo::from_token(meta, self.tok.clone());
+        meta.require_bash(pos, "Structs")?;
+        if self.fields.is_empty() {
+            return error!(meta, self.name_tok.clone(), format!("Struct '{}' must have at least one field", self.name))
+        }
+        let mut seen_fields = HashSet::new();
+        for (field, kind, tok) in &mut self.fields {
+            if !seen_fields.insert(field.clone()) {
+                return error!(meta, tok.clone(), format!("Field '{field}' is already defined"))
+            }
+            *kind = resolve_type(meta, kind, tok.clone())?;
+            if kind.is_bash_array() || kind.is_map() {
+                return error!(meta, tok.clone() => {
+                    message: format!("Field '{field}' cannot be of type '{kind}'"),
+                    comment: "Struct fields cannot hold arrays, structs or maps due to the Bash limitations"
+                })
+            }
+        }
+        let decl = StructDecl {
+            name: self.name.clone(),
+            fields: self.fields.iter().map(|(field, kind, _)| (field.clone(), kind.clone())).collect(),
+            is_public: self.is_public,
+        };
+        if meta.get_enum_declaration(&self.name).is_some() || !meta.add_struct_declaration(decl) {
+            return error!(meta, self.name_tok.clone(), format!("Struct '{}' already exists", self.name))
+        }
+        Ok(())
+    }
+}
+
+impl TranslateModule for StructDeclaration {
+    fn translate(&self, _meta: &mut TranslateMetadata) -> FragmentKind {
+        // Structs exist only at compile time
+        FragmentKind::Empty
+    }
+}
+
+impl DocumentationModule for StructDeclaration {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -0,0 +1,86 @@
+use heraclitus_compiler::prelude::*;
+
+use crate::raw_fragment;
+use crate::modules::block::Block;
+use crate::modules::prelude::*;
+use crate::utils::metadata::ParserMetadata;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Test {
+    pub name: String,
+    pub block: Block,
+    pub token: Option<Token>,
+    pub is_skipped: bool,
+}
+
+impl Test {
+    /// Name of the Bash function that runs this test
+    pub fn function_name(&self) -> String {
+        let (line, col) = self.token.as_ref().map_or((0, 0), |tok| tok.pos);
+        format!("__test_{line}_{col}")
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for Test {
+    syntax_name!("Test");
+
+    fn new() -> Self {
+        Self {
+            name: String::new(),
+            block: Block::new().with_needs_noop(),
+            token: None,
+            is_skipped: false,
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        self.token = meta.get_current_token();
+        token(meta, "test")?;
+        let name = token_by(meta, |word| word.starts_with('"'))?;
+        if name.len() < 2 || !name.ends_with('"') {
+            return error!(meta, self.token.clone(), "Test name cannot interpolate expressions")
+        }
+        self.name = name[1..name.len() - 1].to_string();
+        // Tests of the imported files are not run
+        if !meta.context.trace.is_empty() {
+            self.is_skipped = true;
+        }
+        context!({
+            meta.context.is_main_ctx = true;
+            syntax(meta, &mut self.block)?;
+            meta.context.is_main_ctx = false;
+            Ok(())
+        }, |pos| {
+            error_pos!(meta, pos, format!("Undefined syntax in test '{}'", self.name))
+        })
+    }
+}
+
+impl TypeCheckModule for Test {
+    fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        if !meta.is_global_scope() {
+            return error!(meta, self.token.clone(), "Test must be in the global scope")
+        }
+        meta.with_push_scope(true, |meta| self.block.typecheck(meta))
+    }
+}
+
+impl TranslateModule for Test {
+    fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
+        if self.is_skipped || !meta.test_mode {
+            return FragmentKind::Empty
+        }
+        BlockFragment::new(vec![
+            raw_fragment!("{}() {{", self.function_name()),
+            self.block.translate(meta),
+            raw_fragment!("}}"),
+        ], false).to_frag()
+    }
+}
+
+impl DocumentationModule for Test {
+    fn document(&self, _meta: &ParserMetadata) -> String {
+        "".to_string()
+    }
+}
@@ -3,8 +3,9 @@ use std::fmt::Display;
 use heraclitus_compiler::prelude::*;
 use itertools::Itertools;
 use crate::utils::ParserMetadata;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Type {
     #[default] Null,
     Text,
@@ -12,6 +13,14 @@ pub enum Type {
     Num,
     Int,
     Array(Box<Type>),
+    /// User defined struct referenced by its name
+    Struct(String),
+    /// User defined enum whose values are the names of its variants
+    Enum(String),
+    /// Associative array with text keys and values of the given type
+    Map(Box<Type>),
+    /// Reference to a function with the given argument types and return type
+    Function(Vec<Type>, Box<Type>),
     Generic
 }
 
@@ -21,6 +30,11 @@ impl Type {
         Self::Array(Box::new(kind))
     }
 
+    #[inline]
+    pub fn map_of(kind: Type) -> Self {
+        Self::Map(Box::new(kind))
+    }
+
     pub fn is_subset_of(&self, other: &Type) -> bool {
         match (self, other) {
             (_, Type::Generic) => true,
@@ -42,6 +56,15 @@ impl Type {
         matches!(self, Type::Array(_))
     }
 
+    pub fn is_map(&self) -> bool {
+        matches!(self, Type::Map(_))
+    }
+
+    /// Determines if the value is stored in a Bash array (structs are arrays of their fields)
+    pub fn is_bash_array(&self) -> bool {
+        matches!(self, Type::Array(_) | Type::Struct(_))
+    }
+
     pub fn pretty_join(types: &[Self], op: &str) -> String {
         let mut all_types = types.iter().map(|kind| kind.to_string()).collect_vec();
         let last_item = all_types.pop();
@@ -71,6 +94,15 @@ impl Display for Type {
                 } else {
                     write!(f, "[{t}]")
                 },
+            Type::Struct(name) | Type::Enum(name) => write!(f, "{name}"),
+            Type::Map(t) => write!(f, "[Text: {t}]"),
+            Type::Function(args, returns) => {
+                write!(f, "Fun({})", args.iter().join(", "))?;
+                if **returns != Type::Null {
+                    write!(f, ": {returns}")?;
+                }
+                Ok(())
+            },
             Type::Generic => write!(f, "Generic")
         }
     }
@@ -121,6 +153,12 @@ pub fn try_parse_type(meta: &mut ParserMetadata) -> Result<Type, Failure> {
                     } else {
                         match try_parse_type(meta) {
                             Ok(Type::Array(_)) => error!(meta, tok, "Arrays cannot be nested due to the Bash limitations"),
+                            Ok(Type::Map(_)) => error!(meta, tok, "Maps cannot be stored in arrays due to the Bash limitations"),
+                            Ok(result_type) if token(meta, ":").is_ok() => {
+                                let value_type = parse_map_value_type(meta, result_type, tok)?;
+                                token(meta, "]")?;
+                                Ok(Type::Map(Box::new(value_type)))
+                            },
                             Ok(result_type) => {
                                 token(meta, "]")?;
                                 Ok(Type::Array(Box::new(result_type)))
@@ -148,6 +186,15 @@ pub fn try_parse_type(meta: &mut ParserMetadata) -> Result<Type, Failure> {
                         comment: "Where 'T' is the type of the array elements"
                     })
                 },
+                "Fun" => {
+                    meta.increment_index();
+                    parse_function_type(meta)
+                },
+                // User defined types are written in pascal case and resolved once their declarations are known
+                name if is_type_name(name) => {
+                    meta.increment_index();
+                    Ok(Type::Struct(name.to_string()))
+                },
                 // The quiet error
                 _ => Err(Failure::Quiet(PositionInfo::at_eof(meta)))
             }
@@ -160,9 +207,77 @@ pub fn try_parse_type(meta: &mut ParserMetadata) -> Result<Type, Failure> {
     res
 }
 
+/// Parses the value type of a map type `[Text: T]` once the key type and the colon are parsed
+pub fn parse_map_value_type(meta: &mut ParserMetadata, key_type: Type, tok: Option<Token>) -> Result<Type, Failure> {
+    if key_type != Type::Text {
+        return error!(meta, tok, format!("Map keys must be of type 'Text', found '{key_type}'"))
+    }
+    let value_tok = meta.get_current_token();
+    let value_type = parse_type(meta)?;
+    if value_type.is_array() || value_type.is_map() {
+        return error!(meta, value_tok, format!("Map values cannot be of type '{value_type}' due to the Bash limitations"))
+    }
+    Ok(value_type)
+}
+
+/// Parses the argument types and the optional return type of a function type `Fun(Int, Text): Bool`
+fn parse_function_type(meta: &mut ParserMetadata) -> Result<Type, Failure> {
+    let mut args = vec![];
+    token(meta, "(")?;
+    if token(meta, ")").is_err() {
+        loop {
+            args.push(parse_type(meta)?);
+            if token(meta, ")").is_ok() {
+                break
+            }
+            token(meta, ",")?;
+        }
+    }
+    let returns = match token(meta, ":") {
+        Ok(_) => parse_type(meta)?,
+        Err(_) => Type::Null,
+    };
+    Ok(Type::Function(args, Box::new(returns)))
+}
+
+/// Determines if the identifier can name a struct or an enum (pascal case like `T`, `Host` or `HttpRequest`)
+pub fn is_type_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|chr| chr.is_ascii_uppercase())
+        // Names longer than a letter need a lowercase letter to differ from the constants like `HOST`
+        && (chars.as_str().is_empty() || chars.clone().any(|chr| chr.is_ascii_lowercase()))
+        && chars.all(|chr| chr.is_ascii_alphanumeric())
+}
+
+/// Resolves user defined types used in a type annotation to the declared structs and enums
+pub fn resolve_type(meta: &ParserMetadata, kind: &Type, tok: Option<Token>) -> Result<Type, Failure> {
+    match kind {
+        Type::Struct(name) if meta.get_enum_declaration(name).is_some() => Ok(Type::Enum(name.clone())),
+        Type::Struct(name) if meta.get_struct_declaration(name).is_none() => {
+            error!(meta, tok, format!("Type '{name}' is not defined"))
+        }
+        Type::Array(inner) => match resolve_type(meta, inner, tok.clone())? {
+            Type::Struct(_) => error!(meta, tok, "Structs cannot be stored in arrays due to the Bash limitations"),
+            inner => Ok(Type::array_of(inner))
+        },
+        Type::Map(inner) => match resolve_type(meta, inner, tok.clone())? {
+            inner @ Type::Struct(_) => error!(meta, tok, format!("Map values cannot be of type '{inner}' due to the Bash limitations")),
+            inner => Ok(Type::map_of(inner))
+        },
+        Type::Function(args, returns) => {
+            let args = args.iter()
+                .map(|arg| resolve_type(meta, arg, tok.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let returns = resolve_type(meta, returns, tok.clone())?;
+            Ok(Type::Function(args, Box::new(returns)))
+        },
+        _ => Ok(kind.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Type;
+    use super::{is_type_name, Type};
 
     #[test]
     fn concrete_array_is_a_subset_of_generic_array() {
@@ -193,4 +308,28 @@ mod tests {
 
         assert!(!a.is_subset_of(&a));
     }
+
+    #[test]
+    fn map_type_is_displayed_with_text_keys() {
+        assert_eq!(Type::map_of(Type::Int).to_string(), "[Text: Int]");
+    }
+
+    #[test]
+    fn function_type_is_displayed_with_its_signature() {
+        let fun = Type::Function(vec![Type::Int, Type::Text], Box::new(Type::Bool));
+        let procedure = Type::Function(vec![], Box::new(Type::Null));
+
+        assert_eq!(fun.to_string(), "Fun(Int, Text): Bool");
+        assert_eq!(procedure.to_string(), "Fun()");
+    }
+
+    #[test]
+    fn type_names_are_pascal_case() {
+        assert!(is_type_name("Host"));
+        assert!(is_type_name("HttpRequest2"));
+        assert!(is_type_name("T"));
+        assert!(!is_type_name("HOST"));
+        assert!(!is_type_name("host"));
+        assert!(!is_type_name("Host_name"));
+    }
 }
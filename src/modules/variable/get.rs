@@ -2,15 +2,22 @@ use crate::modules::types::{Type, Typed};
 use crate::modules::variable::{handle_variable_reference, variable_name_extensions};
 use crate::modules::prelude::*;
 use crate::modules::typecheck::TypeCheckModule;
+use crate::modules::function::invocation_utils::{handle_function_reference, handle_function_value};
+use crate::raw_fragment;
+use crate::translate::constant::Constant;
+use crate::utils::function_metadata::FunctionMetadata;
 use heraclitus_compiler::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableGet {
     pub name: String,
     kind: Type,
     global_id: Option<usize>,
     is_ref: bool,
-    tok: Option<Token>
+    tok: Option<Token>,
+    /// Function referenced by its name when used as a value
+    function: Option<FunctionMetadata>,
 }
 
 impl Typed for VariableGet {
@@ -23,6 +30,18 @@ impl VariableGet {
     pub fn is_variable_modified(&self) -> bool {
         false
     }
+
+    pub fn is_function_reference(&self) -> bool {
+        self.function.is_some()
+    }
+
+    pub fn get_constant(&self, meta: &TranslateMetadata) -> Option<Constant> {
+        meta.get_constant(self.global_id)
+    }
+
+    pub fn splits_into_words(&self, meta: &ParserMetadata) -> bool {
+        self.global_id.is_some_and(|id| meta.splitting_vars.contains(&id))
+    }
 }
 
 impl SyntaxModule<ParserMetadata> for VariableGet {
@@ -34,7 +53,8 @@ impl SyntaxModule<ParserMetadata> for VariableGet {
             kind: Type::Null,
             global_id: None,
             is_ref: false,
-            tok: None
+            tok: None,
+            function: None,
         }
     }
 
@@ -47,6 +67,17 @@ impl SyntaxModule<ParserMetadata> for VariableGet {
 
 impl TypeCheckModule for VariableGet {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        // Functions can be referenced by their name if there is no such variable
+        if meta.get_var(&self.name).is_none() {
+            if let Some(fun) = meta.get_fun_declaration(&self.name).cloned() {
+                let id = handle_function_reference(meta, self.tok.clone(), &self.name)?;
+                let (kind, variant_id) = handle_function_value(meta, fun, self.tok.clone())?;
+                let Type::Function(_, returns) = &kind else { unreachable!() };
+                self.function = Some(FunctionMetadata::new(&self.name, id, variant_id, returns));
+                self.kind = kind;
+                return Ok(())
+            }
+        }
         let variable = handle_variable_reference(meta, &self.tok, &self.name)?;
         self.global_id = variable.global_id;
         self.is_ref = variable.is_ref;
@@ -57,6 +88,9 @@ impl TypeCheckModule for VariableGet {
 
 impl TranslateModule for VariableGet {
     fn translate(&self, _meta: &mut TranslateMetadata) -> FragmentKind {
+        if let Some(function) = &self.function {
+            return raw_fragment!("{}", function.reference_name())
+        }
         VarExprFragment::new(&self.name, self.get_type())
             .with_global_id(self.global_id)
             .with_ref(self.is_ref)
@@ -6,8 +6,10 @@ use crate::modules::expression::expr::Expr;
 use super::{variable_name_extensions, handle_identifier_name};
 use crate::utils::context::{VariableDecl, VariableDeclWarn};
 use crate::utils::metadata::ParserMetadata;
+use crate::utils::symbol_table::SymbolId;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableInit {
     name: String,
     expr: Box<Expr>,
@@ -51,12 +53,24 @@ impl TypeCheckModule for VariableInit {
     fn typecheck(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         self.expr.typecheck(meta)?;
         handle_identifier_name(meta, &self.name, self.tok.clone())?;
+        if self.expr.get_type().is_array() {
+            let pos = PositionInfo::from_token(meta, self.tok.clone());
+            meta.require_bash(pos, "Array variables")?;
+        }
         let var = VariableDecl::new(self.name.clone(), self.expr.get_type())
             .with_warn(VariableDeclWarn::from_token(meta, self.tok.clone())
                 .warn_when_unmodified(!self.is_const && !meta.is_global_scope())
                 .warn_when_unused(!meta.is_global_scope()))
             .with_const(self.is_const);
         self.global_id = meta.add_var(var);
+        if let Some(id) = self.global_id {
+            if self.expr.splits_into_words(meta) {
+                meta.splitting_vars.insert(id);
+            }
+            let keyword = if self.is_const { "const" } else { "let" };
+            let signature = format!("{keyword} {}: {}", self.name, self.expr.get_type());
+            meta.add_symbol_definition(SymbolId::Variable(id), &self.name, signature, self.tok.clone());
+        }
         Ok(())
     }
 }
@@ -64,6 +78,14 @@ impl TypeCheckModule for VariableInit {
 impl TranslateModule for VariableInit {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
         let expr = self.expr.translate(meta);
+        if let (true, Some(id)) = (self.is_const && meta.optimize, self.global_id) {
+            // The uses of the constant are replaced with its value unless a reference can change it
+            if !meta.mutable_constants.contains(&id) {
+                if let Some(constant) = self.expr.get_constant(meta) {
+                    meta.constants.insert(id, constant);
+                }
+            }
+        }
         VarStmtFragment::new(&self.name, self.expr.get_type(), expr)
             .with_global_id(self.global_id)
             .with_local(self.is_fun_ctx)
@@ -3,6 +3,7 @@ use crate::modules::types::{Type, Typed};
 use crate::utils::cc_flags::{get_ccflag_name, CCFlags};
 use crate::utils::context::VariableDecl;
 use crate::utils::metadata::ParserMetadata;
+use crate::utils::symbol_table::SymbolId;
 use crate::utils::is_all_caps;
 use heraclitus_compiler::prelude::*;
 use similar_string::find_best_similarity;
@@ -17,14 +18,14 @@ pub fn variable_name_extensions() -> Vec<char> {
 
 pub fn variable_name_keywords() -> Vec<&'static str> {
     vec![
-        "Bool", "Null", "Number", "Text", "and", "as",
+        "Bool", "Null", "Number", "Text", "and", "as", "assert", "assert_eq",
         "break", "cd", "const", "continue", "echo",
-        "else", "exit", "exited", "fail", "failed",
+        "else", "embed", "enum", "exit", "exited", "fail", "failed",
         "false", "for", "from", "fun", "if",
         "import", "in", "is", "len", "let",
-        "lines", "loop", "main", "mv", "nameof",
+        "lines", "loop", "main", "match", "mv", "nameof",
         "not", "null", "or", "pub", "ref",
-        "return", "silent", "status", "sudo", "succeeded",
+        "return", "silent", "status", "struct", "sudo", "succeeded",
         "then", "trust", "true", "unsafe", "while",
     ]
 }
@@ -33,7 +34,13 @@ pub fn variable_name_keywords() -> Vec<&'static str> {
 pub fn handle_variable_reference(meta: &mut ParserMetadata, tok: &Option<Token>, name: &str) -> Result<VariableDecl, Failure> {
     handle_identifier_name(meta, name, tok.clone())?;
     match meta.get_var_used(name) {
-        Some(variable_unit) => Ok(variable_unit.clone()),
+        Some(variable_unit) => {
+            let variable_unit = variable_unit.clone();
+            if let Some(id) = variable_unit.global_id {
+                meta.add_symbol_reference(SymbolId::Variable(id), tok.clone());
+            }
+            Ok(variable_unit)
+        }
         None => {
             let message = format!("Variable '{name}' does not exist");
             // Find other similar variable if exists
@@ -5,13 +5,17 @@ use crate::{modules::expression::expr::Expr, translate::module::TranslateModule}
 use crate::utils::{ParserMetadata, TranslateMetadata};
 use super::{handle_index_accessor, handle_variable_reference, prevent_constant_mutation, variable_name_extensions, validate_index_accessor};
 use crate::modules::types::{Typed, Type};
+use crate::raw_fragment;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableSet {
     name: String,
     expr: Box<Expr>,
     global_id: Option<usize>,
     index: Option<Expr>,
+    /// Struct field name with its token and position in the Bash array
+    field: Option<(String, Option<Token>, usize)>,
     is_ref: bool,
     var_type: Type,
     tok: Option<Token>,
@@ -26,6 +30,7 @@ impl SyntaxModule<ParserMetadata> for VariableSet {
             expr: Box::new(Expr::new()),
             global_id: None,
             index: None,
+            field: None,
             is_ref: false,
             var_type: Type::Null,
             tok: None,
@@ -35,7 +40,12 @@ impl SyntaxModule<ParserMetadata> for VariableSet {
     fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
         self.tok = meta.get_current_token();
         self.name = variable(meta, variable_name_extensions())?;
-        self.index = handle_index_accessor(meta, false)?;
+        if token(meta, ".").is_ok() {
+            let tok = meta.get_current_token();
+            self.field = Some((variable(meta, variable_name_extensions())?, tok, 0));
+        } else {
+            self.index = handle_index_accessor(meta, false)?;
+        }
         token(meta, "=")?;
         syntax(meta, &mut *self.expr)?;
         Ok(())
@@ -55,8 +65,20 @@ impl TypeCheckModule for VariableSet {
         self.var_type = variable.kind.clone();
         prevent_constant_mutation(meta, &self.tok, &self.name, variable.is_const)?;
         meta.mark_var_modified(&self.name);
+        if let (None, Some(id)) = (&self.index, self.global_id) {
+            if self.expr.splits_into_words(meta) {
+                meta.splitting_vars.insert(id);
+            }
+        }
 
-        if let Some(ref index_expr) = self.index {
+        if let (Some(key_expr), Type::Map(_)) = (&self.index, &variable.kind) {
+            let key_type = key_expr.get_type();
+            if key_type != Type::Text {
+                let pos = key_expr.get_position();
+                return error_pos!(meta, pos, format!("Map keys must be of type 'Text', found '{key_type}'"));
+            }
+        }
+        else if let Some(ref index_expr) = self.index {
             if !matches!(variable.kind, Type::Array(_)) {
                 let left_type = variable.kind.clone();
                 return error!(meta, self.tok.clone(), format!("Cannot assign a value to an index of a non-array variable of type '{left_type}'"));
@@ -68,13 +90,35 @@ impl TypeCheckModule for VariableSet {
 
         let right_type = self.expr.get_type();
 
-        if self.index.is_some() {
+        if let Some((field, tok, index)) = self.field.as_mut() {
+            let Type::Struct(name) = &variable.kind else {
+                return error!(meta, tok.clone(), format!("Cannot assign a value to a field of a non-struct variable of type '{}'", variable.kind))
+            };
+            let Some(decl) = meta.get_struct_declaration(name) else {
+                return error!(meta, tok.clone(), format!("Struct '{name}' is not defined"))
+            };
+            let Some((field_index, kind)) = decl.get_field(field) else {
+                return error!(meta, tok.clone(), format!("Struct '{name}' has no field '{field}'"))
+            };
+            if !right_type.is_allowed_in(kind) {
+                let pos = self.expr.get_position();
+                return error_pos!(meta, pos, format!("Cannot assign value of type '{right_type}' to a field of type '{kind}'"));
+            }
+            *index = field_index;
+        }
+        else if self.index.is_some() {
             if let Type::Array(kind) = &self.var_type {
                 if !right_type.is_allowed_in(kind) {
                     let tok = self.expr.get_position();
                     return error_pos!(meta, tok, format!("Cannot assign value of type '{right_type}' to an array of '{kind}'"));
                 }
             }
+            if let Type::Map(kind) = &self.var_type {
+                if !right_type.is_allowed_in(kind) {
+                    let tok = self.expr.get_position();
+                    return error_pos!(meta, tok, format!("Cannot assign value of type '{right_type}' to a map of '{kind}'"));
+                }
+            }
         }
         else if !right_type.is_allowed_in(&self.var_type) {
             let tok = self.expr.get_position();
@@ -87,12 +131,27 @@ impl TypeCheckModule for VariableSet {
 
 impl TranslateModule for VariableSet {
     fn translate(&self, meta: &mut TranslateMetadata) -> FragmentKind {
-        let index = self.index.as_ref().map(|v| v.translate(meta));
-        let expr = self.expr.translate(meta);
-        VarStmtFragment::new(&self.name, self.expr.get_type(), expr)
-            .with_global_id(self.global_id)
+        let stmt = match (self.field.as_ref(), self.index.as_ref()) {
+            (Some((_, _, index)), _) => {
+                let expr = self.expr.translate(meta);
+                VarStmtFragment::new(&self.name, self.expr.get_type(), expr)
+                    .with_index(raw_fragment!("{index}"))
+            }
+            (None, Some(key)) if self.var_type.is_map() => {
+                let key = key.translate(meta);
+                let expr = self.expr.translate(meta);
+                VarStmtFragment::new(&self.name, self.expr.get_type(), expr)
+                    .with_key(key)
+            }
+            (None, index) => {
+                let index = index.map(|v| v.get_static_array_index(meta).unwrap_or_else(|| v.translate(meta)));
+                let expr = self.expr.translate(meta);
+                VarStmtFragment::new(&self.name, self.expr.get_type(), expr)
+                    .with_index(index)
+            }
+        };
+        stmt.with_global_id(self.global_id)
             .with_ref(self.is_ref)
-            .with_index(index)
             .to_frag()
     }
 }
@@ -13,6 +13,14 @@ use crate::modules::prelude::*;
 // 2. (eph1 = 5; eph2 = eph1; var = eph2) -> (var = 5)
 
 pub fn remove_ephemeral_variables(ast: &mut FragmentKind) {
+    if let FragmentKind::Function(function) = ast {
+        remove_ephemeral_variables(&mut function.body);
+    }
+    if let FragmentKind::Case(case) = ast {
+        for arm in case.arms.iter_mut() {
+            remove_ephemeral_variables(&mut arm.block);
+        }
+    }
     if let FragmentKind::Block(block) = ast {
         let mut i = 0;
         while i < block.statements.len() {
@@ -21,6 +29,7 @@ pub fn remove_ephemeral_variables(ast: &mut FragmentKind) {
                     (&block.statements[i], &block.statements[i + 1]) {
                     if let FragmentKind::VarExpr(expression) = second.value.as_ref() {
                         let is_regular_variable = !expression.is_length
+                            && !expression.is_keys
                             && !expression.is_ref
                             && !expression.is_array_to_string
                             && expression.index.is_none();
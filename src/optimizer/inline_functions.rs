@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::modules::prelude::*;
+use super::unused_functions::{collect_functions, collect_texts};
+
+// This optimizer replaces calls of small functions with their bodies.
+// Functions are inlined when they take no arguments and their body is a single
+// statement optionally followed by `return 0` (eg. `foo() { ret_foo0_v0=42; return 0; }`).
+// Bodies that rely on running in a function (local variables, positional arguments
+// or return codes) are left as they are.
+//
+// The definitions that are no longer called are removed by the unused functions optimizer.
+
+/// Words that change their meaning when they are used outside of a function
+const FUNCTION_SCOPED: [&str; 7] = ["return", "local", "declare", "typeset", "shift", "set", "FUNCNAME"];
+
+pub fn inline_functions(ast: &mut FragmentKind) {
+    let mut functions = vec![];
+    collect_functions(ast, &mut functions);
+    let bodies: HashMap<String, FragmentKind> = functions.into_iter()
+        .filter_map(|function| Some((function.name.clone(), get_inlined_body(function)?)))
+        .collect();
+    if !bodies.is_empty() {
+        replace_calls(ast, &bodies);
+    }
+}
+
+fn get_inlined_body(function: &FunctionFragment) -> Option<FragmentKind> {
+    let FragmentKind::Block(block) = function.body.as_ref() else {
+        return None
+    };
+    if function.args.is_some() {
+        return None
+    }
+    let mut statements = block.statements.iter()
+        .filter(|statement| !statement.is_empty_logic())
+        .collect::<Vec<_>>();
+    if statements.last().is_some_and(|statement| is_successful_return(statement)) {
+        statements.pop();
+    }
+    let [statement] = statements[..] else {
+        return None
+    };
+    let is_single_line = match statement {
+        FragmentKind::VarStmt(var_stmt) => !var_stmt.is_local && !var_stmt.is_ref,
+        FragmentKind::Raw(_) | FragmentKind::List(_) => true,
+        _ => false,
+    };
+    let mut texts = vec![];
+    collect_texts(statement, &mut texts);
+    let is_function_scoped = texts.iter().any(|text| {
+        text.split(|letter: char| !letter.is_ascii_alphanumeric() && letter != '_')
+            .any(|word| FUNCTION_SCOPED.contains(&word))
+            || uses_positional_arguments(text)
+    });
+    (is_single_line && !is_function_scoped).then(|| statement.clone())
+}
+
+fn is_successful_return(statement: &FragmentKind) -> bool {
+    let mut texts = vec![];
+    collect_texts(statement, &mut texts);
+    texts == ["return 0"]
+}
+
+/// Checks for the expansions like `$1`, `${2}`, `$@` or `$#`
+fn uses_positional_arguments(text: &str) -> bool {
+    text.match_indices('$').any(|(index, _)| {
+        let rest = text[index + 1..].trim_start_matches('{');
+        rest.starts_with(|letter: char| letter.is_ascii_digit() || "@*#".contains(letter))
+    })
+}
+
+/// Returns the name of the called function if the statement is a plain call without arguments
+fn get_called_function(statement: &FragmentKind) -> Option<&str> {
+    let FragmentKind::List(list) = statement else {
+        return None
+    };
+    match list.values.as_slice() {
+        [FragmentKind::Raw(name), FragmentKind::Raw(space), FragmentKind::List(args), FragmentKind::Empty]
+            if space.value == " " && args.values.is_empty() => Some(&name.value),
+        _ => None,
+    }
+}
+
+/// Follows the inlined functions that only call another inlined function
+fn get_body<'a>(name: &str, bodies: &'a HashMap<String, FragmentKind>) -> Option<&'a FragmentKind> {
+    let mut body = bodies.get(name)?;
+    // The limit stops the functions that call each other
+    for _ in 0..bodies.len() {
+        match get_called_function(body).and_then(|name| bodies.get(name)) {
+            Some(next) => body = next,
+            None => break,
+        }
+    }
+    Some(body)
+}
+
+fn replace_calls(ast: &mut FragmentKind, bodies: &HashMap<String, FragmentKind>) {
+    match ast {
+        FragmentKind::Block(block) => {
+            for statement in block.statements.iter_mut() {
+                let body = get_called_function(statement).and_then(|name| get_body(name, bodies));
+                match body {
+                    Some(body) => *statement = body.clone(),
+                    None => replace_calls(statement, bodies),
+                }
+            }
+        }
+        FragmentKind::Function(function) => replace_calls(&mut function.body, bodies),
+        FragmentKind::Case(case) => {
+            for arm in case.arms.iter_mut() {
+                replace_calls(&mut arm.block, bodies);
+            }
+        }
+        _ => {}
+    }
+}
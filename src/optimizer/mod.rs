@@ -1,11 +1,17 @@
 use crate::modules::prelude::*;
 use unused_vars::remove_unused_variables;
 use ephemeral_vars::remove_ephemeral_variables;
+use inline_functions::inline_functions;
+use unused_functions::remove_unused_functions;
 
 pub mod ephemeral_vars;
+pub mod inline_functions;
+pub mod unused_functions;
 pub mod unused_vars;
 
 pub fn optimize_fragments(ast: &mut FragmentKind) {
+    inline_functions(ast);
+    remove_unused_functions(ast);
     remove_unused_variables(ast);
     remove_ephemeral_variables(ast);
 }
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::modules::prelude::*;
+use crate::translate::fragments::var_expr::VarIndexValue;
+
+// This optimizer removes the definitions of functions that are never called.
+// A function variant is generated for every call that was type checked,
+// but some of these calls are never emitted (eg. the main block of an imported file)
+// and others disappear when the function is inlined.
+//
+// A function is used when its name occurs as a word in the code outside of the
+// function definitions or in the body of another function that is used.
+
+pub fn remove_unused_functions(ast: &mut FragmentKind) {
+    let mut functions = vec![];
+    collect_functions(ast, &mut functions);
+    let mut bodies: HashMap<&str, HashSet<String>> = HashMap::new();
+    for function in functions {
+        let mut texts = vec![];
+        if let Some(args) = &function.args {
+            collect_texts(args, &mut texts);
+        }
+        collect_texts(&function.body, &mut texts);
+        bodies.entry(function.name.as_str()).or_default().extend(words(&texts));
+    }
+    let mut texts = vec![];
+    collect_texts(ast, &mut texts);
+    let mut queue: Vec<String> = words(&texts).into_iter()
+        .filter(|word| bodies.contains_key(word.as_str()))
+        .collect();
+    let mut used = HashSet::new();
+    while let Some(name) = queue.pop() {
+        if let Some(body) = bodies.get(name.as_str()) {
+            queue.extend(body.iter().filter(|word| !used.contains(*word)).cloned());
+        }
+        used.insert(name);
+    }
+    remove_functions(ast, &used);
+}
+
+pub fn collect_functions<'a>(ast: &'a FragmentKind, functions: &mut Vec<&'a FunctionFragment>) {
+    if let FragmentKind::Function(function) = ast {
+        functions.push(function);
+    }
+    for child in children(ast) {
+        collect_functions(child, functions);
+    }
+}
+
+fn children(ast: &FragmentKind) -> Vec<&FragmentKind> {
+    match ast {
+        FragmentKind::Function(function) => function.args.iter().map(AsRef::as_ref)
+            .chain([function.body.as_ref()])
+            .collect(),
+        FragmentKind::Block(block) => block.statements.iter().collect(),
+        FragmentKind::Case(case) => [case.value.as_ref()].into_iter()
+            .chain(case.arms.iter().flat_map(|arm| arm.patterns.iter().chain([&arm.block])))
+            .collect(),
+        FragmentKind::Interpolable(interpolable) => interpolable.interps.iter().collect(),
+        FragmentKind::List(list) => list.values.iter().collect(),
+        FragmentKind::Subprocess(subprocess) => vec![subprocess.fragment.as_ref()],
+        FragmentKind::Arithmetic(arith) => [arith.left.as_ref(), arith.right.as_ref()].into_iter().flatten().collect(),
+        FragmentKind::VarStmt(var_stmt) => [&var_stmt.index, &var_stmt.key].into_iter().flatten()
+            .map(AsRef::as_ref)
+            .chain([var_stmt.value.as_ref()])
+            .collect(),
+        FragmentKind::VarExpr(var_expr) => match var_expr.index.as_deref() {
+            Some(VarIndexValue::Index(index) | VarIndexValue::Key(index)) => vec![index],
+            Some(VarIndexValue::Range(start, end)) => vec![start, end],
+            None => vec![],
+        },
+        FragmentKind::Raw(_) | FragmentKind::Comment(_) | FragmentKind::Empty => vec![],
+    }
+}
+
+fn remove_functions(ast: &mut FragmentKind, used: &HashSet<String>) {
+    match ast {
+        FragmentKind::Function(function) if !used.contains(&function.name) => {
+            *ast = FragmentKind::Empty;
+        }
+        FragmentKind::Function(function) => remove_functions(&mut function.body, used),
+        FragmentKind::Block(block) => {
+            for statement in block.statements.iter_mut() {
+                remove_functions(statement, used);
+            }
+        }
+        FragmentKind::Case(case) => {
+            for arm in case.arms.iter_mut() {
+                remove_functions(&mut arm.block, used);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Splits the code to the words that can name a function
+fn words(texts: &[&str]) -> HashSet<String> {
+    texts.iter()
+        .flat_map(|text| text.split(|letter: char| !letter.is_ascii_alphanumeric() && letter != '_'))
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Collects the pieces of code that are rendered as they are, without the nested function definitions
+pub fn collect_texts<'a>(ast: &'a FragmentKind, texts: &mut Vec<&'a str>) {
+    match ast {
+        FragmentKind::Raw(raw) => texts.push(&raw.value),
+        FragmentKind::VarExpr(var_expr) => texts.push(&var_expr.name),
+        FragmentKind::VarStmt(var_stmt) => texts.push(&var_stmt.name),
+        FragmentKind::Interpolable(interpolable) => texts.extend(interpolable.strings.iter().map(String::as_str)),
+        FragmentKind::Function(_) => return,
+        _ => {}
+    }
+    for child in children(ast) {
+        collect_texts(child, texts);
+    }
+}
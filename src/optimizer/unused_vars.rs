@@ -102,6 +102,14 @@ pub fn remove_unused_variables(ast: &mut FragmentKind) {
 }
 
 fn remove_non_existing_variables(ast: &mut FragmentKind, meta: &mut UnusedVariablesMetadata) {
+    if let FragmentKind::Function(function) = ast {
+        remove_non_existing_variables(&mut function.body, meta);
+    }
+    if let FragmentKind::Case(case) = ast {
+        for arm in case.arms.iter_mut() {
+            remove_non_existing_variables(&mut arm.block, meta);
+        }
+    }
     if let FragmentKind::Block(block) = ast {
         let mut remove_indexes = vec![];
         for (index, statement) in block.statements.iter_mut().enumerate() {
@@ -129,6 +137,7 @@ fn should_optimize_var_stmt(var_stmt: &VarStmtFragment) -> bool {
     !var_stmt.is_ref
         && var_stmt.optimize_unused
         && var_stmt.index.is_none()
+        && var_stmt.key.is_none()
         && var_stmt.operator == "="
         && !var_stmt.value.is_mutating()
 }
@@ -146,6 +155,21 @@ fn find_unused_variables(ast: &FragmentKind, meta: &mut UnusedVariablesMetadata)
                 meta.symbols.push_back(SymbolType::ConditionalBlock(CondBlockBehavior::End));
             }
         }
+        FragmentKind::Function(function) => {
+            if let Some(args) = &function.args {
+                find_unused_variables(args, meta);
+            }
+            find_unused_variables(&function.body, meta);
+        }
+        FragmentKind::Case(case) => {
+            find_unused_variables(&case.value, meta);
+            for arm in case.arms.iter() {
+                for pattern in arm.patterns.iter() {
+                    find_unused_variables(pattern, meta);
+                }
+                find_unused_variables(&arm.block, meta);
+            }
+        }
         FragmentKind::List(list) => {
             for item in list.values.iter() {
                 find_unused_variables(item, meta);
@@ -185,7 +209,7 @@ fn find_unused_variables(ast: &FragmentKind, meta: &mut UnusedVariablesMetadata)
             }
             if let Some(index) = &var_expr.index {
                 match index.as_ref() {
-                    VarIndexValue::Index(index) => find_unused_variables(index, meta),
+                    VarIndexValue::Index(index) | VarIndexValue::Key(index) => find_unused_variables(index, meta),
                     VarIndexValue::Range(start, end) => {
                         find_unused_variables(start, meta);
                         find_unused_variables(end, meta);
@@ -0,0 +1,346 @@
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::modules::statement::stmt::StmtType;
+use crate::modules::types::Type;
+use crate::translate::fragments::get_variable_name;
+use crate::utils::context::{ScopeUnit, VariableDecl};
+use heraclitus_compiler::prelude::*;
+use std::env;
+use std::fs;
+use std::io::{self, stdin, BufRead, BufReader, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Variable that holds the value of the entered expression
+const VALUE_VARIABLE: &str = "__repl_value";
+/// Line printed by Bash once the entered code has finished running
+const DONE_MARKER: &str = "__amber_repl_done__";
+/// Number of the Bash sessions started so far
+static SESSION_COUNT: AtomicUsize = AtomicUsize::new(0);
+/// Keywords that start declarations which are kept for the following inputs
+const DECLARATION_KEYWORDS: [&str; 5] = ["enum", "fun", "import", "pub", "struct"];
+
+const HELP: &str = "\
+Enter Amber statements, expressions, imports and declarations.
+Commands:
+  :type <expr>  Show the type of the expression
+  :bash <code>  Show the Bash code generated for the code
+  :help         Show this message
+  :quit         Exit the REPL";
+
+/// Amber code compiled together with the declarations entered so far
+struct Compiled {
+    code: String,
+    /// Generated Bash code of the entered snippet
+    snippet: String,
+    variables: Vec<VariableDecl>,
+    var_id: usize,
+    value: Option<VariableDecl>,
+}
+
+/// Bash process that keeps the variables between inputs
+struct BashSession {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    script: PathBuf,
+}
+
+impl BashSession {
+    fn new() -> io::Result<Self> {
+        let mut process = Command::new("bash")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = process.stdin.take().expect("Bash input is piped");
+        let stdout = BufReader::new(process.stdout.take().expect("Bash output is piped"));
+        let session = SESSION_COUNT.fetch_add(1, Ordering::Relaxed);
+        let script = env::temp_dir().join(format!("amber-repl-{}-{session}.sh", std::process::id()));
+        Ok(BashSession { process, stdin, stdout, script })
+    }
+
+    /// Runs the code and forwards its output until it finishes.
+    /// Returns the output that was not terminated by a new line or `None` if Bash has exited.
+    fn run(&mut self, code: &str, out: &mut impl Write) -> io::Result<Option<String>> {
+        fs::write(&self.script, code)?;
+        let script = self.script.to_string_lossy().replace('\'', "'\\''");
+        writeln!(self.stdin, "source '{script}'; printf '%s\\n' '{DONE_MARKER}'")?;
+        self.stdin.flush()?;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.stdout.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            if let Some(rest) = line.strip_suffix(&format!("{DONE_MARKER}\n")) {
+                return Ok(Some(rest.to_string()));
+            }
+            out.write_all(line.as_bytes())?;
+        }
+    }
+}
+
+impl Drop for BashSession {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = fs::remove_file(&self.script);
+    }
+}
+
+/// Interactive session that compiles every input together with the previous declarations
+pub struct Repl {
+    /// Imports and declarations of functions and types entered so far
+    declarations: String,
+    /// Global variables that are stored in the Bash session
+    variables: Vec<VariableDecl>,
+    /// Next free variable id so that the new variables do not overwrite the stored ones
+    var_id: usize,
+    session: BashSession,
+}
+
+impl Repl {
+    pub fn new() -> io::Result<Self> {
+        Ok(Repl {
+            declarations: String::new(),
+            variables: vec![],
+            var_id: 0,
+            session: BashSession::new()?,
+        })
+    }
+
+    fn options() -> CompilerOptions {
+        CompilerOptions::from_args(&["*".to_string()], false)
+            .with_source_map(true)
+            .with_optimize(false)
+    }
+
+    fn is_declaration(input: &str) -> bool {
+        let input = input.trim_start();
+        input.starts_with("#[") || input.split_whitespace().next()
+            .is_some_and(|word| DECLARATION_KEYWORDS.contains(&word))
+    }
+
+    /// Assigns the trailing expression of the snippet to a variable so that its value can be shown
+    fn assign_value(snippet: &str) -> Option<String> {
+        let compiler = AmberCompiler::new(snippet.to_string(), None, Self::options());
+        let tokens = compiler.tokenize().ok()?;
+        let (block, _) = compiler.parse(tokens).ok()?;
+        let statement = block.statements.last()?;
+        if !matches!(statement.value, Some(StmtType::Expr(_))) {
+            return None;
+        }
+        let start = statement.tok.as_ref()?.start;
+        let offset = snippet.char_indices().nth(start).map_or(snippet.len(), |(offset, _)| offset);
+        Some(format!("{}let {VALUE_VARIABLE} = {}", &snippet[..offset], &snippet[offset..]))
+    }
+
+    fn compile(&self, declarations: &str, snippet: &str) -> Result<Compiled, Message> {
+        let snippet_line = declarations.lines().count() + 1;
+        let compiler = AmberCompiler::new(format!("{declarations}{snippet}"), None, Self::options());
+        let tokens = compiler.tokenize()?;
+        let (block, mut meta) = compiler.parse(tokens)?;
+        // Variables of the previous inputs are declared in the global scope
+        let mut scope = ScopeUnit::new();
+        for var in &self.variables {
+            scope.add_var(var.clone());
+        }
+        meta.context.scopes.push(scope);
+        meta.var_id = self.var_id;
+        let (block, meta) = compiler.typecheck(block, meta)?;
+        let var_id = meta.var_id;
+        let mut variables = meta.context.scopes.first()
+            .map(|scope| scope.vars.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let value = variables.iter().position(|var| var.name == VALUE_VARIABLE)
+            .map(|index| variables.remove(index));
+        let (code, source_map) = compiler.translate(block, meta)?;
+        let main_source = source_map.sources.len().saturating_sub(1);
+        let lines = code.lines().collect::<Vec<_>>();
+        let snippet = source_map.mappings.iter()
+            .filter(|mapping| mapping.source == main_source && mapping.source_line >= snippet_line)
+            .filter_map(|mapping| lines.get(mapping.line - 1))
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(Compiled { code, snippet, variables, var_id, value })
+    }
+
+    /// Compiles the snippet showing the value of its trailing expression if possible
+    fn compile_snippet(&self, snippet: &str) -> Result<Compiled, Message> {
+        if let Some(assigned) = Self::assign_value(snippet) {
+            if let Ok(compiled) = self.compile(&self.declarations, &assigned) {
+                return Ok(compiled);
+            }
+        }
+        self.compile(&self.declarations, snippet)
+    }
+
+    /// Renders the Bash code that prints the value in the Amber notation
+    fn print_value(var: &VariableDecl) -> Option<String> {
+        let name = get_variable_name(&var.name, var.global_id);
+        let print_item = |kind: &Type, value: &str| match kind {
+            Type::Text => format!("printf '\"%s\"' \"{value}\""),
+            Type::Bool => format!("[ \"{value}\" != 0 ] && printf true || printf false"),
+            _ => format!("printf '%s' \"{value}\""),
+        };
+        match &var.kind {
+            Type::Null => None,
+            Type::Array(kind) => {
+                let item = print_item(kind, "${__amber_repl_item}");
+                Some([
+                    "__amber_repl_separator=''".to_string(),
+                    "printf '['".to_string(),
+                    format!("for __amber_repl_item in \"${{{name}[@]}}\"; do"),
+                    "    printf '%s' \"${__amber_repl_separator}\"".to_string(),
+                    format!("    {item}"),
+                    "    __amber_repl_separator=', '".to_string(),
+                    "done".to_string(),
+                    "printf ']'".to_string(),
+                ].join("\n"))
+            }
+            Type::Struct(_) | Type::Map(_) => Some(print_item(&var.kind, &format!("${{{name}[*]}}"))),
+            kind => Some(print_item(kind, &format!("${{{name}}}"))),
+        }
+    }
+
+    fn run_snippet(&mut self, snippet: &str, out: &mut impl Write) -> io::Result<()> {
+        let compiled = match self.compile_snippet(snippet) {
+            Ok(compiled) => compiled,
+            Err(err) => {
+                err.show();
+                return Ok(());
+            }
+        };
+        let printer = compiled.value.as_ref().and_then(Self::print_value);
+        let code = match &printer {
+            Some(printer) => format!("{}\n{printer}", compiled.code),
+            None => compiled.code,
+        };
+        match self.session.run(&code, out)? {
+            Some(rest) => {
+                match (&compiled.value, printer) {
+                    (Some(value), Some(_)) => writeln!(out, "{rest}: {}", value.kind)?,
+                    _ if !rest.is_empty() => writeln!(out, "{rest}")?,
+                    _ => {}
+                }
+                self.variables = compiled.variables;
+                self.var_id = compiled.var_id;
+            }
+            None => {
+                // The code has exited the shell so the variables are lost
+                Message::new_warn_msg("Bash session has exited, the variables were reset").show();
+                self.session = BashSession::new()?;
+                self.variables.clear();
+            }
+        }
+        Ok(())
+    }
+
+    fn add_declaration(&mut self, declaration: &str) {
+        let declarations = format!("{}{declaration}\n", self.declarations);
+        match self.compile(&declarations, "") {
+            Ok(_) => self.declarations = declarations,
+            Err(err) => err.show(),
+        }
+    }
+
+    /// Handles a single input. Returns `false` when the REPL should quit.
+    pub fn eval(&mut self, input: &str, out: &mut impl Write) -> io::Result<bool> {
+        let input = input.trim();
+        if let Some(command) = input.strip_prefix(':') {
+            let (command, argument) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+            match command {
+                "quit" | "q" => return Ok(false),
+                "help" => writeln!(out, "{HELP}")?,
+                "type" => match self.compile_snippet(argument) {
+                    Ok(Compiled { value: Some(value), .. }) => writeln!(out, "{}", value.kind)?,
+                    Ok(_) => Message::new_err_msg("Expected an expression").show(),
+                    Err(err) => err.show(),
+                },
+                "bash" => match self.compile(&self.declarations, argument) {
+                    Ok(compiled) => writeln!(out, "{}", compiled.snippet)?,
+                    Err(err) => err.show(),
+                },
+                _ => Message::new_err_msg(format!("Unknown command ':{command}', see ':help'")).show(),
+            }
+        } else if Self::is_declaration(input) {
+            self.add_declaration(input);
+        } else if !input.is_empty() {
+            self.run_snippet(input, out)?;
+        }
+        Ok(true)
+    }
+}
+
+/// Determines if the input has unclosed brackets and continues on the next line
+pub fn is_unfinished(input: &str) -> bool {
+    // Open brackets and the quotes of the texts and commands that the current letter is in
+    let mut open = vec![];
+    let mut chars = input.chars().peekable();
+    while let Some(chr) = chars.next() {
+        match (open.last(), chr) {
+            // Texts and commands only contain the brackets of the interpolations
+            (Some('"' | '$'), '\\') => {
+                chars.next();
+            }
+            (Some(&quote @ ('"' | '$')), _) if chr == quote => {
+                open.pop();
+            }
+            (Some('"' | '$'), '{') => open.push(chr),
+            (Some('"' | '$'), _) => {}
+            (_, '/') if chars.peek() == Some(&'/') => {
+                chars.find(|chr| *chr == '\n');
+            }
+            (_, '"' | '$' | '{' | '(' | '[') => open.push(chr),
+            (_, '}' | ')' | ']') => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    open.iter().any(|chr| matches!(chr, '{' | '(' | '['))
+}
+
+/// Reads a line with the line editing and history of Bash if the input is a terminal
+fn read_line(prompt: &str) -> io::Result<Option<String>> {
+    if !stdin().is_terminal() {
+        let mut line = String::new();
+        return Ok((stdin().read_line(&mut line)? > 0).then_some(line));
+    }
+    let history = env::var_os("HOME")
+        .map_or_else(|| PathBuf::from(".amber_history"), |home| PathBuf::from(home).join(".amber_history"));
+    let output = Command::new("bash")
+        .arg("-c")
+        .arg("set -o history; history -r; IFS= read -e -r -p \"$1\" line || exit 1; history -s -- \"$line\"; history -a; printf '%s\\n' \"$line\"")
+        .arg("bash")
+        .arg(prompt)
+        .env("HISTFILE", history)
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+pub fn run() -> io::Result<()> {
+    let mut repl = Repl::new()?;
+    if stdin().is_terminal() {
+        println!("Amber REPL, type ':help' for help");
+    }
+    let mut out = io::stdout();
+    while let Some(mut input) = read_line("amber> ")? {
+        while is_unfinished(&input) {
+            match read_line("...... ")? {
+                Some(line) => input.push_str(&line),
+                None => break,
+            }
+        }
+        if !repl.eval(&input, &mut out)? {
+            break;
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,9 @@
+# Commands required by the script
+__amber_missing=""
+for __amber_command in {{ commands }}; do
+    command -v "${__amber_command}" >/dev/null 2>&1 || __amber_missing="${__amber_missing} ${__amber_command}"
+done
+if [ -n "${__amber_missing}" ]; then
+    printf 'Missing required commands:%s\n' "${__amber_missing}" >&2
+    exit 127
+fi
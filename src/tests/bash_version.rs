@@ -0,0 +1,78 @@
+/// Tests for the compatibility mode with older Bash versions.
+use super::extract_output;
+use crate::compiler::target::BashVersion;
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::tests::eval_bash;
+use std::fs;
+use test_generator::test_resources;
+
+fn compile_for_bash_3_2(code: &str) -> String {
+    let options = CompilerOptions::default().with_bash_version(Some(BashVersion::OLDEST));
+    let compiler = AmberCompiler::new(code.to_string(), None, options);
+    let (_, code) = compiler.compile().expect("Couldn't compile Amber code");
+    code
+}
+
+#[test]
+fn parse_bash_version() {
+    assert_eq!("3.2".parse::<BashVersion>(), Ok(BashVersion::new(3, 2)));
+    assert_eq!("5".parse::<BashVersion>(), Ok(BashVersion::new(5, 0)));
+    assert!("3.1".parse::<BashVersion>().is_err());
+    assert!("latest".parse::<BashVersion>().is_err());
+}
+
+#[test]
+fn negative_index_counts_from_array_length() {
+    let code = compile_for_bash_3_2(r#"
+        let items = [1, 2, 3]
+        items[-1] = 4
+        echo items[-2]
+    "#);
+    assert!(code.contains("items_0[${#items_0[@]}-1]=4"));
+    assert!(code.contains("${items_0[${#items_0[@]}-2]}"));
+}
+
+#[test]
+fn constant_negative_index_counts_from_array_length() {
+    let code = compile_for_bash_3_2(r#"
+        const last = -1
+        let items = [1, 2, 3]
+        items[last] = 4
+        echo items[last - 1]
+    "#);
+    assert!(code.contains("items_1[${#items_1[@]}-1]=4"));
+    assert!(code.contains("${items_1[${#items_1[@]}-2]}"));
+}
+
+#[test]
+fn dynamic_negative_index_is_checked_at_runtime() {
+    let code = compile_for_bash_3_2(r#"
+        let items = [1, 2, 3]
+        let index = -1
+        items[index] = 4
+        echo items[index]
+    "#);
+    assert!(code.contains(r#"items_0[("${index_1}") < 0 ? ${#items_0[@]} + ("${index_1}") : ("${index_1}")]=4"#));
+    assert!(code.contains("${items_0[(${index_1}) < 0 ? ${#items_0[@]} + (${index_1}) : (${index_1})]}"));
+}
+
+/// Negative indices rendered for Bash 3.2 must behave the same on the current Bash
+#[test_resources("src/tests/validity/array_get_negative_index_*.ab")]
+fn negative_index_compatibility(input: &str) {
+    let code = fs::read_to_string(input)
+        .unwrap_or_else(|_| panic!("Failed to open {input} test file"));
+    let output = extract_output(&code);
+    let (stdout, _) = eval_bash(compile_for_bash_3_2(&code));
+    assert_eq!(stdout, output);
+}
+
+#[test]
+fn maps_require_associative_arrays() {
+    let code = r#"let ports = ["http": 80]"#;
+    let options = CompilerOptions::default().with_bash_version(Some(BashVersion::OLDEST));
+    let compiler = AmberCompiler::new(code.to_string(), None, options);
+    let Err(message) = compiler.compile() else {
+        panic!("Maps should not compile for Bash 3.2");
+    };
+    assert_eq!(message.message.as_deref(), Some("Maps require Bash 4.2 or newer"));
+}
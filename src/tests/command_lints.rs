@@ -0,0 +1,144 @@
+/// Tests for the warnings about the common mistakes in command literals.
+use crate::compiler::message_format::Diagnostic;
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use serde_json::json;
+
+fn compile_warnings(code: &str) -> Vec<Diagnostic> {
+    let compiler = AmberCompiler::new(code.to_string(), None, CompilerOptions::default());
+    let (messages, _) = compiler.compile().expect("Couldn't compile Amber code");
+    messages.iter().map(Diagnostic::from_message).collect()
+}
+
+fn warning_messages(code: &str) -> Vec<String> {
+    compile_warnings(code).into_iter().filter_map(|diagnostic| diagnostic.message).collect()
+}
+
+#[test]
+fn unquoted_interpolation() {
+    let diagnostics = compile_warnings("let dir = \"my files\"\ntrust $ ls {dir} \"{dir}\" '{dir}' $\n");
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(diagnostic["message"], json!("Unquoted text interpolation is split into words by the shell, wrap it in double quotes"));
+    assert_eq!(diagnostic["comment"], json!("To suppress this warning, use 'allow_unquoted_interpolation' compiler flag"));
+    assert_eq!(diagnostic["span"]["start"], json!({ "line": 2, "column": 13 }));
+}
+
+#[test]
+fn safe_interpolations() {
+    let warnings = warning_messages(r#"
+        import { temp_dir_create } from "std/fs"
+        fun show(path: Text) {
+            trust $ ls {path} $
+        }
+        let file = "my file"
+        let tmpdir = trust temp_dir_create("amber-XXXX", true, true)
+        let path = "{tmpdir}/file"
+        trust $ touch {path} $
+        show(file)
+        let count = 3
+        let files = ["a", "b"]
+        trust $ head -n {count} {files} $
+        trust $ [[ {file} == *.txt ]] $
+        trust $ echo "\$(basename "{file}")" $
+        trust $ {nameof file}="changed" $
+    "#);
+    assert_eq!(warnings, Vec::<String>::new());
+}
+
+#[test]
+fn interpolation_in_command_substitution() {
+    let warnings = warning_messages(r#"
+        let file = "my file"
+        trust $ echo "\$(basename {file})" $
+    "#);
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn cd_in_command() {
+    let diagnostics = compile_warnings("let dir = \"/tmp\"\ntrust $ echo \\$HOME \\{ && cd {dir} $\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message.as_deref(), Some("Changing the directory in a command literal, use the 'cd' builtin instead"));
+    let diagnostic = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(diagnostic["span"]["start"], json!({ "line": 2, "column": 27 }));
+    assert_eq!(diagnostic["span"]["end"], json!({ "line": 2, "column": 29 }));
+}
+
+#[test]
+fn cd_in_subshell() {
+    let warnings = warning_messages(r#"
+        trust $ (cd /tmp && ls) $
+        trust $ echo "\$(cd /tmp; pwd)" $
+    "#);
+    assert_eq!(warnings, Vec::<String>::new());
+}
+
+#[test]
+fn unsafe_rm() {
+    let warnings = warning_messages(r#"
+        let dir = "build"
+        trust $ rm -rf "{dir}"/ $
+        trust $ rm -r "{dir}/cache" $
+        trust $ rm -f "{dir}"/lock $
+        trust $ rm -R "./{dir}"/ $
+    "#);
+    assert_eq!(warnings, [
+        "Recursive removal of a path starting with an interpolation, it removes the root directory if the value is empty",
+        "Recursive removal of a path starting with an interpolation, it removes the root directory if the value is empty",
+    ]);
+}
+
+#[test]
+fn backticks() {
+    let diagnostics = compile_warnings("let name = \"x\"\ntrust $ echo {name}\n    `date` '`' $\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message.as_deref(), Some("Backticks are deprecated in favor of '$(...)' which can be nested and quoted safely"));
+    let diagnostic = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(diagnostic["span"]["start"], json!({ "line": 3, "column": 5 }));
+}
+
+#[test]
+fn suppressed_in_function() {
+    let warnings = warning_messages(r#"
+        #[allow_unquoted_interpolation]
+        #[allow_cd_in_command]
+        #[allow_unsafe_rm]
+        #[allow_backticks]
+        fun clean(dir: Text) {
+            const files = "*.o *.a"
+            trust $ cd {dir} && rm -rf {dir}/ {files} `echo x` $
+        }
+        clean("build")
+    "#);
+    assert_eq!(warnings, Vec::<String>::new());
+}
+
+#[test]
+fn suppressed_in_main() {
+    let warnings = warning_messages(r#"
+        #[allow_backticks]
+        main {
+            trust $ echo `date` $
+            trust $ cd /tmp $
+        }
+    "#);
+    assert_eq!(warnings, ["Changing the directory in a command literal, use the 'cd' builtin instead"]);
+}
+
+
+#[test]
+fn reported_once_for_function_variants() {
+    let warnings = warning_messages(r#"
+        fun change(value) {
+            trust $ cd /tmp $
+            trust $ echo {value} `date` $
+        }
+        change(1)
+        change("a b")
+        change(2)
+    "#);
+    assert_eq!(warnings, [
+        "Changing the directory in a command literal, use the 'cd' builtin instead",
+        "Backticks are deprecated in favor of '$(...)' which can be nested and quoted safely",
+    ]);
+}
@@ -0,0 +1,54 @@
+/// Tests for the depfiles listing the files that the output was compiled from.
+use crate::compiler::depfile::{render_depfile, Dependency};
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use std::fs;
+
+fn dependency(path: &str, is_virtual: bool) -> Dependency {
+    Dependency { path: path.to_string(), is_virtual }
+}
+
+#[test]
+fn dependencies_include_imports_and_embeds() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+    fs::create_dir(dir.path().join("lib")).unwrap();
+    fs::write(dir.path().join("lib/greet.ab"), "import { trim } from \"std/text\"\npub fun greet() {\n    echo trim(embed(\"greeting.txt\"))\n}\n").unwrap();
+    fs::write(dir.path().join("lib/greeting.txt"), "Hello").unwrap();
+    let code = "import { greet } from \"lib/greet.ab\"\nimport { trim } from \"std/text\"\ngreet()\n";
+    let compiler = AmberCompiler::new(code.to_string(), Some(path("main.ab")), CompilerOptions::default());
+    let (_, _, _, dependencies) = compiler.compile_with_dependencies().unwrap();
+    assert_eq!(dependencies, vec![
+        dependency(&path("main.ab"), false),
+        dependency(&path("lib/greet.ab"), false),
+        dependency("std/text", true),
+        dependency(&path("lib/greeting.txt"), false),
+    ]);
+}
+
+#[test]
+fn code_without_file_has_no_dependencies() {
+    let compiler = AmberCompiler::new("echo 1".to_string(), None, CompilerOptions::default());
+    let (_, _, _, dependencies) = compiler.compile_with_dependencies().unwrap();
+    assert!(dependencies.is_empty());
+}
+
+#[test]
+fn depfile_escapes_paths() {
+    let dependencies = vec![
+        dependency("main.ab", false),
+        dependency("my lib/$HOME#1.ab", false),
+    ];
+    let depfile = render_depfile("out dir/main.sh", &dependencies);
+    assert_eq!(depfile, "out\\ dir/main.sh: \\\n  main.ab \\\n  my\\ lib/$$HOME\\#1.ab\n");
+}
+
+#[test]
+fn depfile_lists_virtual_modules_in_comments() {
+    let dependencies = vec![
+        dependency("main.ab", false),
+        dependency("std/text", true),
+        dependency("std/fs", true),
+    ];
+    let depfile = render_depfile("main.sh", &dependencies);
+    assert_eq!(depfile, "main.sh: \\\n  main.ab\n# virtual: std/text\n# virtual: std/fs\n");
+}
@@ -73,3 +73,22 @@ fn download() {
     std::thread::sleep(Duration::from_millis(150));
     assert!(server.is_finished(), "Server has not stopped!");
 }
+
+#[test]
+fn embedded_files_are_tracked() {
+    use crate::compiler::{AmberCompiler, CompilerOptions};
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("lib")).unwrap();
+    fs::write(dir.path().join("lib/template.txt"), "Hello").unwrap();
+    fs::write(dir.path().join("lib/greet.ab"), "pub fun greet() {\n    echo embed(\"template.txt\")\n}\n").unwrap();
+    let main = dir.path().join("main.ab");
+    let code = "import { greet } from \"lib/greet.ab\"\ngreet()\necho embed(\"lib/template.txt\")\n";
+    let compiler = AmberCompiler::new(code.to_string(), Some(main.to_string_lossy().to_string()), CompilerOptions::default());
+    let (_, meta) = compiler.tokenize()
+        .and_then(|tokens| compiler.parse(tokens))
+        .and_then(|(block, meta)| compiler.typecheck(block, meta))
+        .unwrap();
+    let template = dir.path().join("lib/template.txt").to_string_lossy().to_string();
+    assert_eq!(meta.import_cache.embedded_files, vec![template]);
+}
@@ -0,0 +1,30 @@
+/// Tests for the source code formatter.
+use crate::formatter::format_code;
+use test_generator::test_resources;
+use insta::assert_snapshot;
+use std::fs;
+use std::path::Path;
+
+/// Autoload the Amber test files in formatting
+#[test_resources("src/tests/formatting/*.ab")]
+fn test_formatting(input: &str) {
+    let code = fs::read_to_string(input)
+        .unwrap_or_else(|_| panic!("Failed to open {input} test file"));
+    let output = format_code(code, Some(input.to_string()))
+        .expect("Couldn't format Amber code");
+    let filename = Path::new(input).file_name()
+        .expect("Provided directory")
+        .to_str()
+        .expect("Cannot translate to string");
+    // Formatting already formatted code should not change it
+    let reformatted = format_code(output.clone(), Some(input.to_string()))
+        .expect("Couldn't format formatted Amber code");
+    assert_eq!(output, reformatted);
+    assert_snapshot!(filename, output);
+}
+
+#[test]
+fn format_rejects_invalid_code() {
+    let result = format_code("fun foo( {".to_string(), None);
+    assert!(result.is_err());
+}
@@ -0,0 +1,127 @@
+use crate::lsp::protocol::{path_to_uri, read_message};
+use crate::lsp::LanguageServer;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::Cursor;
+
+fn frame(message: Value) -> String {
+    let content = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{content}", content.len())
+}
+
+/// Sends the messages to the language server and returns all of its responses
+fn run_session(messages: Vec<Value>) -> Vec<Value> {
+    let input = messages.into_iter().map(frame).collect::<String>();
+    let mut reader = Cursor::new(input.into_bytes());
+    let mut output = vec![];
+    LanguageServer::new().run(&mut reader, &mut output).unwrap();
+    let mut reader = Cursor::new(output);
+    let mut responses = vec![];
+    while let Some(message) = read_message(&mut reader).unwrap() {
+        responses.push(message);
+    }
+    responses
+}
+
+fn open_document(uri: &str, text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": { "textDocument": { "uri": uri, "languageId": "amber", "version": 1, "text": text } },
+    })
+}
+
+fn position_request(id: usize, method: &str, uri: &str, line: usize, character: usize) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": { "textDocument": { "uri": uri }, "position": { "line": line, "character": character } },
+    })
+}
+
+fn find_response(responses: &[Value], id: usize) -> &Value {
+    responses.iter()
+        .find(|response| response["id"] == json!(id))
+        .map(|response| &response["result"])
+        .expect("Response not found")
+}
+
+#[test]
+fn lsp_initialize_and_shutdown() {
+    let responses = run_session(vec![
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]);
+    let capabilities = &find_response(&responses, 1)["capabilities"];
+    assert_eq!(capabilities["hoverProvider"], json!(true));
+    assert_eq!(capabilities["definitionProvider"], json!(true));
+    assert_eq!(find_response(&responses, 2), &Value::Null);
+}
+
+#[test]
+fn lsp_publishes_diagnostics() {
+    let uri = "file:///tmp/amber_lsp_diagnostics.ab";
+    let code = "let value = 12\necho valeu\n";
+    let responses = run_session(vec![open_document(uri, code)]);
+    let diagnostics = &responses[0]["params"]["diagnostics"];
+    assert_eq!(responses[0]["method"], json!("textDocument/publishDiagnostics"));
+    assert_eq!(diagnostics[0]["severity"], json!(1));
+    assert!(diagnostics[0]["message"].as_str().unwrap().starts_with("Variable 'valeu' does not exist"));
+    assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 1, "character": 5 }));
+    assert_eq!(diagnostics[0]["range"]["end"], json!({ "line": 1, "character": 10 }));
+}
+
+#[test]
+fn lsp_hover_shows_type() {
+    let uri = "file:///tmp/amber_lsp_hover.ab";
+    let code = "fun add(a: Int, b: Int): Int {\n    return a + b\n}\nlet sum = add(1, 2)\necho sum\n";
+    let responses = run_session(vec![
+        open_document(uri, code),
+        position_request(1, "textDocument/hover", uri, 4, 6),
+        position_request(2, "textDocument/hover", uri, 3, 11),
+    ]);
+    let variable = &find_response(&responses, 1)["contents"]["value"];
+    assert_eq!(variable, &json!("```amber\nlet sum: Int\n```"));
+    let function = &find_response(&responses, 2)["contents"]["value"];
+    assert_eq!(function, &json!("```amber\nfun add(a: Int, b: Int): Int\n```"));
+}
+
+#[test]
+fn lsp_goes_to_definition() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("main.ab");
+    let code = "fun greet(name) {\n    echo \"Hello {name}\"\n}\n\ngreet(\"World\")\n";
+    fs::write(&path, code).unwrap();
+    let uri = path_to_uri(&path.to_string_lossy());
+    let responses = run_session(vec![
+        open_document(&uri, code),
+        position_request(1, "textDocument/definition", &uri, 4, 2),
+    ]);
+    let location = find_response(&responses, 1);
+    assert_eq!(location["uri"], json!(uri));
+    assert_eq!(location["range"]["start"], json!({ "line": 0, "character": 4 }));
+    assert_eq!(location["range"]["end"], json!({ "line": 0, "character": 9 }));
+}
+
+#[test]
+fn lsp_completes_names_in_scope() {
+    let uri = "file:///tmp/amber_lsp_completion.ab";
+    let code = "let outer = 1\nfun compute(x: Int): Int {\n    let inner = x * 2\n    return inner\n}\necho compute(outer)\n";
+    let responses = run_session(vec![
+        open_document(uri, code),
+        position_request(1, "textDocument/completion", uri, 3, 4),
+        position_request(2, "textDocument/completion", uri, 5, 0),
+    ]);
+    let labels = |id| find_response(&responses, id)["items"].as_array().unwrap().iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    let inside = labels(1);
+    assert!(inside.contains(&"inner".to_string()));
+    assert!(inside.contains(&"x".to_string()));
+    assert!(inside.contains(&"compute".to_string()));
+    let outside = labels(2);
+    assert!(outside.contains(&"outer".to_string()));
+    assert!(!outside.contains(&"inner".to_string()));
+}
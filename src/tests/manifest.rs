@@ -0,0 +1,76 @@
+/// Tests for the 'amber.toml' project manifest.
+use crate::compiler::manifest::{Manifest, MANIFEST_FILE};
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn manifest_is_parsed() {
+    let content = r#"
+        [build]
+        entries = ["bin/deploy.ab", "bin/backup.ab"]
+        output_dir = "dist"
+        no_proc = ["bshchk"]
+        minify = true
+        header = "header.sh"
+    "#;
+    let manifest = Manifest::parse(content, PathBuf::from("project")).unwrap();
+    assert_eq!(manifest.build.no_proc, vec!["bshchk".to_string()]);
+    assert!(manifest.build.minify);
+    assert_eq!(manifest.build.footer, None);
+    assert_eq!(manifest.targets(), vec![
+        (PathBuf::from("project/bin/deploy.ab"), PathBuf::from("project/dist/deploy.sh")),
+        (PathBuf::from("project/bin/backup.ab"), PathBuf::from("project/dist/backup.sh")),
+    ]);
+}
+
+#[test]
+fn outputs_default_to_entry_directory() {
+    let manifest = Manifest::parse("build.entries = [\"main.ab\"]", PathBuf::from("project")).unwrap();
+    assert_eq!(manifest.targets(), vec![
+        (PathBuf::from("project/main.ab"), PathBuf::from("project/main.sh")),
+    ]);
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    assert!(Manifest::parse("[build]\nentry = \"main.ab\"", PathBuf::new()).is_err());
+    assert!(Manifest::parse("[package]\nname = \"app\"", PathBuf::new()).is_err());
+}
+
+#[test]
+fn manifest_is_found_in_parent_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("src/lib");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(dir.path().join(MANIFEST_FILE), "[build]\nminify = true").unwrap();
+    let path = Manifest::find(&nested).unwrap();
+    assert_eq!(path, dir.path().join(MANIFEST_FILE));
+    let manifest = Manifest::load(&path).unwrap();
+    assert_eq!(manifest.root, dir.path());
+    assert!(manifest.build.minify);
+}
+
+#[test]
+fn invalid_manifest_reports_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(MANIFEST_FILE);
+    fs::write(&path, "[build\n").unwrap();
+    let err = Manifest::load(&path).unwrap_err();
+    assert!(err.message.unwrap().contains(MANIFEST_FILE));
+}
+
+#[test]
+fn options_use_manifest_header_and_footer() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("header.sh"), "#!/bin/bash\n# project header").unwrap();
+    fs::write(dir.path().join("footer.sh"), "# project footer").unwrap();
+    let content = "[build]\nno_proc = [\"*\"]\nheader = \"header.sh\"\nfooter = \"footer.sh\"";
+    let manifest = Manifest::parse(content, dir.path().to_path_buf()).unwrap();
+    let options = CompilerOptions::from_manifest(&manifest);
+    assert_eq!(options.no_proc, vec!["*".to_string()]);
+    let compiler = AmberCompiler::new("echo 1".to_string(), None, options);
+    let (_, code) = compiler.compile().unwrap();
+    assert!(code.starts_with("#!/bin/bash\n# project header"));
+    assert!(code.trim_end().ends_with("# project footer"));
+}
@@ -0,0 +1,46 @@
+use crate::compiler::message_format::{render_sarif, Diagnostic};
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use serde_json::json;
+
+fn compile_messages(code: &str) -> Vec<Diagnostic> {
+    let compiler = AmberCompiler::new(code.to_string(), None, CompilerOptions::default());
+    match compiler.compile() {
+        Ok((messages, _)) => messages.iter().map(Diagnostic::from_message).collect(),
+        Err(error) => vec![Diagnostic::from_message(&error)],
+    }
+}
+
+#[test]
+fn json_diagnostic_for_error() {
+    let diagnostics = compile_messages("let value = 1\necho valeu\n");
+    let diagnostic = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(diagnostic, json!({
+        "severity": "error",
+        "path": null,
+        "span": { "start": { "line": 2, "column": 6 }, "end": { "line": 2, "column": 11 } },
+        "message": "Variable 'valeu' does not exist",
+        "comment": "Did you mean 'value'?",
+        "trace": [],
+    }));
+}
+
+#[test]
+fn json_diagnostic_for_warning() {
+    let diagnostics = compile_messages("fun foo() {\n    let unused = 1\n}\nfoo()\n");
+    let diagnostic = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(diagnostic["severity"], json!("warning"));
+    assert_eq!(diagnostic["message"], json!("Unused variable 'unused'"));
+    assert_eq!(diagnostic["span"]["start"], json!({ "line": 2, "column": 9 }));
+}
+
+#[test]
+fn sarif_log_contains_results() {
+    let diagnostics = compile_messages("let value = 1\necho valeu\n");
+    let sarif = render_sarif(&diagnostics);
+    assert_eq!(sarif["version"], json!("2.1.0"));
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["level"], json!("error"));
+    assert_eq!(result["message"]["text"], json!("Variable 'valeu' does not exist\nDid you mean 'value'?"));
+    // Messages without a file path have no physical location
+    assert_eq!(result["locations"], json!([]));
+}
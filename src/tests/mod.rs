@@ -8,10 +8,25 @@ use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
 pub mod cli;
+pub mod lsp;
+pub mod message_format;
 pub mod extra;
 pub mod postprocessor;
 pub mod translation;
 pub mod optimizing;
+pub mod formatting;
+pub mod posix;
+pub mod bash_version;
+pub mod source_map;
+pub mod trace_failures;
+pub mod test_blocks;
+pub mod repl;
+pub mod manifest;
+pub mod package;
+pub mod depfile;
+pub mod watch;
+pub mod requires;
+pub mod command_lints;
 mod stdlib;
 mod validity;
 mod erroring;
@@ -34,15 +49,19 @@ pub enum TestOutcomeTarget {
     Failure,
 }
 
-fn eval_amber(code: &str) -> Result<String, Message> {
-    let options = CompilerOptions::default();
+fn eval_amber(code: &str, options: CompilerOptions) -> Result<String, Message> {
     let mut compiler = AmberCompiler::new(code.to_string(), None, options);
     compiler.test_eval()
 }
 
 /// Tests script output in case of success or failure
 pub fn test_amber(code: &str, result: &str, target: TestOutcomeTarget) {
-    let evaluated = eval_amber(code);
+    test_amber_with_options(code, result, target, CompilerOptions::default());
+}
+
+/// Tests script output compiled with the given options in case of success or failure
+pub fn test_amber_with_options(code: &str, result: &str, target: TestOutcomeTarget, options: CompilerOptions) {
+    let evaluated = eval_amber(code, options);
     match target {
         TestOutcomeTarget::Success => match evaluated {
             Ok(stdout) => {
@@ -103,6 +122,11 @@ fn extract_output(code: impl Into<String>) -> String {
 
 /// Inner test logic for testing script output in case of success or failure
 pub fn script_test(input: &str, target: TestOutcomeTarget) {
+    script_test_with_options(input, target, CompilerOptions::default());
+}
+
+/// Inner test logic for testing script output compiled with the given options
+pub fn script_test_with_options(input: &str, target: TestOutcomeTarget, options: CompilerOptions) {
     let code = fs::read_to_string(input)
         .unwrap_or_else(|_| panic!("Failed to open {input} test file"));
     // Extract output from script comment
@@ -117,7 +141,7 @@ pub fn script_test(input: &str, target: TestOutcomeTarget) {
             SUCCEEDED.to_string()
         };
     }
-    test_amber(&code, &output, target);
+    test_amber_with_options(&code, &output, target, options);
 }
 
 #[cfg(test)]
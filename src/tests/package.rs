@@ -0,0 +1,174 @@
+/// Tests for the project dependencies imported with 'pkg:'.
+use crate::compiler::manifest::{Manifest, MANIFEST_FILE};
+use crate::compiler::package::{self, Lockfile, LOCKFILE};
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::tests::eval_bash;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Amber", "-c", "user.email=amber@example.com", "-c", "init.defaultBranch=main"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Couldn't run git");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Creates a library repository and returns the hash of its only commit
+fn create_library(dir: &Path, greeting: &str) -> String {
+    fs::create_dir_all(dir.join("text")).unwrap();
+    fs::write(dir.join("text/greet.ab"), format!("pub fun greet(name) {{\n    return \"{greeting}, {{name}}\"\n}}\n")).unwrap();
+    git(dir, &["init", "--quiet"]);
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "--quiet", "-m", "Add greet"]);
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+fn create_project(dir: &Path, dependencies: &str) -> Manifest {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join(MANIFEST_FILE), format!("[dependencies]\n{dependencies}")).unwrap();
+    Manifest::load(&dir.join(MANIFEST_FILE)).unwrap()
+}
+
+fn run_project_file(path: &Path, code: &str) -> Result<String, String> {
+    fs::write(path, code).unwrap();
+    let path = path.to_string_lossy().to_string();
+    let compiler = AmberCompiler::new(code.to_string(), Some(path), CompilerOptions::default());
+    match compiler.compile() {
+        Ok((_, code)) => Ok(eval_bash(code).0),
+        Err(err) => Err(err.message.unwrap_or_default()),
+    }
+}
+
+#[test]
+fn git_dependency_is_vendored_and_imported() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join("library");
+    let commit = create_library(&library, "Hello");
+    let project = dir.path().join("project");
+    let manifest = create_project(&project, &format!("utils = {{ git = \"file://{}\" }}", library.display()));
+
+    let lockfile = package::vendor(&manifest, false).unwrap();
+    assert_eq!(lockfile.packages.len(), 1);
+    assert_eq!(lockfile.packages[0].commit.as_deref(), Some(commit.as_str()));
+    assert!(lockfile.packages[0].hash.starts_with("sha256:"));
+    assert!(!project.join("vendor/utils/.git").exists());
+    assert_eq!(Lockfile::load(&manifest).unwrap(), lockfile);
+
+    let code = "import { greet } from \"pkg:utils/text/greet\"\necho greet(\"Amber\")\n";
+    let output = run_project_file(&project.join("main.ab"), code).unwrap();
+    assert_eq!(output, "Hello, Amber");
+}
+
+#[test]
+fn lockfile_pins_the_commit() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join("library");
+    let first = create_library(&library, "Hello");
+    let project = dir.path().join("project");
+    let manifest = create_project(&project, &format!("utils = {{ git = \"file://{}\" }}", library.display()));
+    package::vendor(&manifest, false).unwrap();
+
+    fs::write(library.join("text/greet.ab"), "pub fun greet(name) {\n    return \"Hi, {name}\"\n}\n").unwrap();
+    git(&library, &["commit", "--quiet", "-am", "Change greeting"]);
+    let lockfile = package::vendor(&manifest, false).unwrap();
+    assert_eq!(lockfile.packages[0].commit.as_deref(), Some(first.as_str()));
+
+    let lockfile = package::vendor(&manifest, true).unwrap();
+    assert_ne!(lockfile.packages[0].commit.as_deref(), Some(first.as_str()));
+    let code = "import { greet } from \"pkg:utils/text/greet\"\necho greet(\"Amber\")\n";
+    assert_eq!(run_project_file(&project.join("main.ab"), code).unwrap(), "Hi, Amber");
+}
+
+#[test]
+fn changed_content_does_not_match_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join("library");
+    create_library(&library, "Hello");
+    let project = dir.path().join("project");
+    let manifest = create_project(&project, &format!("utils = {{ git = \"file://{}\" }}", library.display()));
+    let mut lockfile = package::vendor(&manifest, false).unwrap();
+    lockfile.packages[0].hash = "sha256:0".to_string();
+    lockfile.save(&manifest).unwrap();
+
+    let err = package::vendor(&manifest, false).unwrap_err();
+    assert_eq!(err.message.unwrap(), "Content of package 'utils' does not match the lockfile");
+    assert!(project.join(LOCKFILE).exists());
+}
+
+#[test]
+fn mismatched_content_keeps_vendored_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join("library");
+    create_library(&library, "Hello");
+    let project = dir.path().join("project");
+    let manifest = create_project(&project, &format!("utils = {{ git = \"file://{}\" }}", library.display()));
+    let lockfile = package::vendor(&manifest, false).unwrap();
+    let vendored = project.join("vendor/utils/text/greet.ab");
+    let original = fs::read_to_string(&vendored).unwrap();
+
+    // The library history is rewritten, so the locked commit now has a different content
+    fs::write(library.join("text/greet.ab"), "pub fun greet(name) {\n    return \"Bye, {name}\"\n}\n").unwrap();
+    git(&library, &["commit", "--quiet", "--amend", "-am", "Add greet"]);
+    let mut changed = lockfile.clone();
+    changed.packages[0].commit = Some(git(&library, &["rev-parse", "HEAD"]));
+    changed.save(&manifest).unwrap();
+
+    let err = package::vendor(&manifest, false).unwrap_err();
+    assert_eq!(err.message.unwrap(), "Content of package 'utils' does not match the lockfile");
+    assert_eq!(fs::read_to_string(&vendored).unwrap(), original);
+    assert_eq!(fs::read_dir(project.join("vendor")).unwrap().count(), 1);
+}
+
+#[test]
+fn failed_fetch_keeps_vendored_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join("library");
+    create_library(&library, "Hello");
+    let project = dir.path().join("project");
+    let manifest = create_project(&project, &format!("utils = {{ git = \"file://{}\" }}", library.display()));
+    package::vendor(&manifest, false).unwrap();
+
+    fs::remove_dir_all(&library).unwrap();
+    assert!(package::vendor(&manifest, false).is_err());
+    assert!(project.join("vendor/utils/text/greet.ab").exists());
+}
+
+#[test]
+fn path_dependency_is_vendored() {
+    let dir = tempfile::tempdir().unwrap();
+    create_library(&dir.path().join("library"), "Hey");
+    let project = dir.path().join("project");
+    let manifest = create_project(&project, "utils = { path = \"../library\" }");
+    let lockfile = package::vendor(&manifest, false).unwrap();
+    assert_eq!(lockfile.packages[0].source, "path+../library");
+    assert_eq!(lockfile.packages[0].commit, None);
+
+    let code = "import * from \"pkg:utils/text/greet.ab\"\necho greet(\"Amber\")\n";
+    assert_eq!(run_project_file(&project.join("main.ab"), code).unwrap(), "Hey, Amber");
+}
+
+#[test]
+fn undeclared_package_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("project");
+    create_project(&project, "utils = { path = \"../library\" }");
+    let code = "import * from \"pkg:other/text\"\n";
+    let err = run_project_file(&project.join("main.ab"), code).unwrap_err();
+    assert!(err.starts_with("Package 'other' is not declared in"));
+    let code = "import * from \"pkg:utils/text\"\n";
+    let err = run_project_file(&project.join("main.ab"), code).unwrap_err();
+    assert_eq!(err, "Package 'utils' is not vendored");
+}
+
+#[test]
+fn dependency_needs_single_source() {
+    let content = "[dependencies]\nutils = { git = \"file:///utils\", path = \"utils\" }";
+    assert!(Manifest::parse(content, Default::default()).is_err());
+    let content = "[dependencies]\nutils = { path = \"utils\", rev = \"main\" }";
+    assert!(Manifest::parse(content, Default::default()).is_err());
+}
@@ -0,0 +1,96 @@
+/// Tests for the POSIX shell output target.
+use crate::compiler::target::Target;
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use heraclitus_compiler::prelude::Message;
+use std::process::Command;
+
+fn compile_posix(code: &str) -> Result<String, Message> {
+    let options = CompilerOptions::default().with_target(Target::Posix);
+    let compiler = AmberCompiler::new(code.to_string(), None, options);
+    compiler.compile().map(|(_, code)| code)
+}
+
+/// Runs the code with the system POSIX shell (`dash` on most Linux distributions)
+fn eval_posix(code: &str) -> String {
+    let code = compile_posix(code).expect("Couldn't compile Amber code");
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(code)
+        .output()
+        .expect("Couldn't spawn sh");
+    String::from_utf8_lossy(&output.stdout).trim_end().to_string()
+}
+
+fn compile_posix_error(code: &str) -> String {
+    let error = compile_posix(code).expect_err("Expected the code to be rejected");
+    error.message.expect("Error message expected")
+}
+
+#[test]
+fn posix_header() {
+    let code = compile_posix("echo 1").unwrap();
+    assert!(code.starts_with("#!/bin/sh\n"));
+}
+
+#[test]
+fn posix_functions_and_references() {
+    let code = r#"
+        fun add(a: Int, b: Int): Int {
+            return a + b
+        }
+        fun bump(ref value: Int) {
+            value += 1
+        }
+        let number = 5
+        bump(number)
+        echo add(number, 2)
+    "#;
+    assert_eq!(eval_posix(code), "8");
+}
+
+#[test]
+fn posix_loop_with_index() {
+    let code = r#"
+        for index, value in 3..5 {
+            echo "{index}: {value}"
+        }
+    "#;
+    assert_eq!(eval_posix(code), "0: 3\n1: 4");
+}
+
+#[test]
+fn posix_echo_keeps_backslashes() {
+    let code = r#"
+        echo "a\\nb"
+    "#;
+    assert_eq!(eval_posix(code), "a\\nb");
+}
+
+#[test]
+fn posix_text_equality() {
+    let code = r#"
+        let name = "amber"
+        if name == "amber" and name != "bash" {
+            echo "equal"
+        }
+    "#;
+    assert_eq!(eval_posix(code), "equal");
+}
+
+#[test]
+fn posix_rejects_arrays() {
+    let message = compile_posix_error("let items = [1, 2, 3]");
+    assert_eq!(message, "Arrays are not supported by the POSIX shell target");
+}
+
+#[test]
+fn posix_rejects_main_arguments() {
+    let message = compile_posix_error("main(args) {}");
+    assert_eq!(message, "Main arguments are not supported by the POSIX shell target");
+}
+
+#[test]
+fn posix_rejects_text_comparison() {
+    let message = compile_posix_error(r#"echo "a" < "b""#);
+    assert_eq!(message, "Text comparisons are not supported by the POSIX shell target");
+}
@@ -0,0 +1,61 @@
+/// Tests for the interactive session started by 'amber repl'.
+use crate::repl::{is_unfinished, Repl};
+
+fn eval_all(inputs: &[&str]) -> String {
+    let mut repl = Repl::new().expect("Couldn't start the REPL");
+    let mut out = vec![];
+    for input in inputs {
+        repl.eval(input, &mut out).expect("Couldn't evaluate the input");
+    }
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn variables_are_kept_between_inputs() {
+    let output = eval_all(&["let name = \"world\"", "let count = 2", "count += 1", "echo \"{name} {count}\""]);
+    assert_eq!(output, "world 3\n");
+}
+
+#[test]
+fn expression_values_are_shown_with_types() {
+    let output = eval_all(&["let items = [\"a\", \"b\"]", "items", "len(items) * 2", "len(items) > 5", "items[0]"]);
+    assert_eq!(output, "[\"a\", \"b\"]: [Text]\n4: Int\nfalse: Bool\n\"a\": Text\n");
+}
+
+#[test]
+fn declarations_are_available_to_later_inputs() {
+    let output = eval_all(&[
+        "import { array_contains } from \"std/array\"",
+        "fun greet(name: Text): Text {\n    return \"Hello {name}\"\n}",
+        "greet(\"you\")",
+        "array_contains([1, 2], 2)",
+    ]);
+    assert_eq!(output, "\"Hello you\": Text\ntrue: Bool\n");
+}
+
+#[test]
+fn type_command_does_not_run_the_expression() {
+    let output = eval_all(&[
+        "let value = 1.5",
+        ":type value + 1",
+        "fun noisy(): [Int] {\n    echo \"running\"\n    return [1]\n}",
+        ":type noisy()",
+    ]);
+    assert_eq!(output, "Num\n[Int]\n");
+}
+
+#[test]
+fn bash_command_shows_generated_code() {
+    let output = eval_all(&["let x = 1", ":bash echo x + 1"]);
+    assert_eq!(output, "echo \"$(( ${x_0} + 1 ))\"\n");
+}
+
+#[test]
+fn brackets_in_texts_and_commands_are_ignored() {
+    assert!(!is_unfinished("echo \"(\""));
+    assert!(!is_unfinished("trust $ echo \\$ \\{ \"(\" $"));
+    assert!(!is_unfinished("echo \"\\\"{name}\\\"\" // {"));
+    assert!(is_unfinished("if true {\n    echo \"}\""));
+    assert!(is_unfinished("echo \"{join([\"a\", \"}\"], \"\")"));
+    assert!(!is_unfinished("echo \"{join([\"a\", \"}\"], \"\")}\""));
+}
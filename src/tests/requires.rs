@@ -0,0 +1,79 @@
+/// Tests for the external commands required and invoked by the scripts.
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::tests::eval_bash;
+
+fn compile(code: &str) -> String {
+    let compiler = AmberCompiler::new(code.to_string(), None, CompilerOptions::default());
+    let (_, code) = compiler.compile().expect("Couldn't compile Amber code");
+    code
+}
+
+fn invoked_commands(code: &str) -> Vec<String> {
+    let compiler = AmberCompiler::new(code.to_string(), None, CompilerOptions::default());
+    let (_, commands) = compiler.compile_with_commands().expect("Couldn't compile Amber code");
+    commands.into_iter().collect()
+}
+
+#[test]
+fn missing_commands_stop_the_script() {
+    let code = compile(r#"
+        #[requires("sh", "amber_missing_command", "amber_other_missing_command")]
+        main {
+            echo "started"
+        }
+    "#);
+    let (stdout, stderr) = eval_bash(code);
+    assert_eq!(stdout, "");
+    assert_eq!(stderr, "Missing required commands: amber_missing_command amber_other_missing_command");
+}
+
+#[test]
+fn available_commands_let_the_script_run() {
+    let code = compile(r#"
+        #[requires("sh")]
+        fun shell(): Text {
+            return trust $ sh -c "echo started" $
+        }
+        main {
+            echo shell()
+        }
+    "#);
+    let (stdout, stderr) = eval_bash(code);
+    assert_eq!(stdout, "started");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn commands_of_unused_functions_are_not_required() {
+    let code = compile(r#"
+        #[requires("amber_missing_command")]
+        fun unused() {
+            trust $ amber_missing_command $
+        }
+        echo "started"
+    "#);
+    assert!(!code.contains("amber_missing_command"));
+    let (stdout, _) = eval_bash(code);
+    assert_eq!(stdout, "started");
+}
+
+#[test]
+fn invoked_commands_are_listed() {
+    let commands = invoked_commands(r#"
+        #[requires("rsync")]
+        fun sync() {
+            trust $ rsync -a src/ dst/ $
+        }
+        main {
+            sync()
+            let count = 2.5
+            trust $ curl -s example.com | LANG=C sort | head -n 1 $
+            trust $ echo {count} > /dev/null $
+            echo count * 2.0
+            for index in 0..3 {
+                echo index
+            }
+        }
+    "#);
+    assert_eq!(commands, ["bc", "curl", "head", "rsync", "sed", "seq", "sort"]);
+}
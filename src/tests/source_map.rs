@@ -0,0 +1,61 @@
+/// Tests for the source maps relating the generated code to the Amber source.
+use crate::compiler::source_map::{Mapping, SourceMap};
+use crate::compiler::{AmberCompiler, CompilerOptions};
+
+fn compile_with_source_map(code: &str) -> (String, SourceMap) {
+    let options = CompilerOptions::default().with_source_map(true);
+    let compiler = AmberCompiler::new(code.to_string(), Some("main.ab".to_string()), options);
+    let (_, code, source_map) = compiler.compile_with_source_map().expect("Couldn't compile Amber code");
+    (code, source_map)
+}
+
+fn find_line(code: &str, pattern: &str) -> usize {
+    let index = code.lines()
+        .position(|line| line.contains(pattern))
+        .unwrap_or_else(|| panic!("Couldn't find '{pattern}' in the generated code"));
+    index + 1
+}
+
+#[test]
+fn extract_removes_markers() {
+    let code = "#!/usr/bin/env bash\n# @amber-source 0:2\nx=1\n\n# @amber-source 0:3\necho \"$x\"";
+    let (code, source_map) = SourceMap::extract(code, vec!["main.ab".to_string()]);
+    assert_eq!(code, "#!/usr/bin/env bash\nx=1\n\necho \"$x\"");
+    assert_eq!(source_map.mappings, vec![
+        Mapping { line: 2, source: 0, source_line: 2 },
+        Mapping { line: 4, source: 0, source_line: 3 },
+    ]);
+    assert_eq!(source_map.resolve(4), Some(("main.ab", 3)));
+    assert_eq!(source_map.resolve(1), None);
+    assert_eq!(source_map.resolve(3), None);
+}
+
+#[test]
+fn source_map_survives_serialization() {
+    let (_, source_map) = SourceMap::extract("# @amber-source 0:1\necho 1", vec!["main.ab".to_string()]);
+    assert_eq!(SourceMap::from_json(&source_map.to_json()).unwrap(), source_map);
+}
+
+#[test]
+fn statements_resolve_to_their_source_lines() {
+    let (code, source_map) = compile_with_source_map(r#"
+        let name = "world"
+        fun greet(name: Text): Text {
+            return "Hello {name}"
+        }
+        echo greet(name)
+    "#);
+    assert!(!code.contains("@amber-source"));
+    assert_eq!(source_map.resolve(find_line(&code, "name_0=")), Some(("main.ab", 2)));
+    assert_eq!(source_map.resolve(find_line(&code, "greet__")), Some(("main.ab", 3)));
+    assert_eq!(source_map.resolve(find_line(&code, "=\"Hello")), Some(("main.ab", 4)));
+    assert_eq!(source_map.resolve(find_line(&code, "echo")), Some(("main.ab", 6)));
+}
+
+#[test]
+fn source_map_is_empty_when_disabled() {
+    let compiler = AmberCompiler::new("echo 1".to_string(), None, CompilerOptions::default());
+    let (_, code, source_map) = compiler.compile_with_source_map().unwrap();
+    assert!(!code.contains("@amber-source"));
+    assert_eq!(source_map, SourceMap::default());
+}
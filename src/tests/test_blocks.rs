@@ -0,0 +1,66 @@
+/// Tests for the test blocks run by 'amber test'.
+use crate::compiler::{AmberCompiler, CompilerOptions, TestCase};
+use crate::tests::eval_bash;
+
+fn compile_tests(code: &str) -> (String, Vec<TestCase>) {
+    let options = CompilerOptions::default().with_test_mode(true);
+    let compiler = AmberCompiler::new(code.to_string(), Some("main.ab".to_string()), options);
+    let (_, code, tests) = compiler.compile_tests().expect("Couldn't compile Amber code");
+    (code, tests)
+}
+
+const TESTS: &str = r#"
+fun double(value: Int): Int {
+    return value * 2
+}
+
+test "doubles" {
+    assert_eq double(2), 4
+}
+
+test "doubles wrong" {
+    echo "checking"
+    assert_eq double(2), 5
+}
+
+test "checks condition" {
+    assert double(1) > 2
+}
+
+main {
+    echo "main"
+}
+"#;
+
+#[test]
+fn test_blocks_are_collected_in_order() {
+    let (_, tests) = compile_tests(TESTS);
+    let names = tests.iter().map(|test| test.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["doubles", "doubles wrong", "checks condition"]);
+    assert_eq!(tests[0].function, "__test_6_1");
+}
+
+#[test]
+fn passing_test_succeeds() {
+    let (code, tests) = compile_tests(TESTS);
+    let (stdout, stderr) = eval_bash(format!("{code}\n{}\necho $?", tests[0].function));
+    assert_eq!(stdout, "0");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn failed_assertion_reports_values_and_location() {
+    let (code, tests) = compile_tests(TESTS);
+    let (stdout, stderr) = eval_bash(format!("{code}\n{}", tests[1].function));
+    assert_eq!(stdout, "checking");
+    assert_eq!(stderr, "Assertion failed at main.ab:12:5\n  left: 4\n  right: 5");
+    let (_, stderr) = eval_bash(format!("{code}\n{}", tests[2].function));
+    assert_eq!(stderr, "Assertion failed at main.ab:16:5");
+}
+
+#[test]
+fn main_block_is_not_run_in_test_mode() {
+    let (code, _) = compile_tests(TESTS);
+    let (stdout, _) = eval_bash(code);
+    assert_eq!(stdout, "");
+}
@@ -0,0 +1,82 @@
+/// Tests for the Amber stack traces printed on unhandled failures.
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::tests::eval_bash;
+
+fn compile_with_trace(code: &str, trace_failures: bool) -> String {
+    let options = CompilerOptions::default().with_trace_failures(trace_failures);
+    let compiler = AmberCompiler::new(code.to_string(), Some("main.ab".to_string()), options);
+    let (_, code) = compiler.compile().expect("Couldn't compile Amber code");
+    code
+}
+
+const PROPAGATED_FAILURE: &str = r#"
+fun inner(): Null? {
+    fail 3
+}
+
+fun outer(): Null? {
+    inner()?
+}
+
+main {
+    echo "start"
+    outer()?
+}
+"#;
+
+#[test]
+fn unhandled_failure_prints_stack_trace() {
+    let code = compile_with_trace(PROPAGATED_FAILURE, true);
+    let (stdout, stderr) = eval_bash(code);
+    assert_eq!(stdout, "start");
+    assert_eq!(stderr, [
+        "Unhandled failure with exit code 3",
+        "  at inner (main.ab:3:5)",
+        "  at outer (main.ab:7:5)",
+        "  at main (main.ab:12:5)",
+    ].join("\n"));
+}
+
+#[test]
+fn failed_command_starts_stack_trace() {
+    let code = compile_with_trace(r#"
+        fun run(): Null? {
+            $ false $?
+        }
+        main {
+            run()?
+        }
+    "#, true);
+    let (_, stderr) = eval_bash(code);
+    assert_eq!(stderr, [
+        "Unhandled failure with exit code 1",
+        "  at run (main.ab:3:13)",
+        "  at main (main.ab:6:13)",
+    ].join("\n"));
+}
+
+#[test]
+fn handled_failure_discards_stack_trace() {
+    let code = compile_with_trace(r#"
+        fun check(): Null? {
+            fail 2
+        }
+        main {
+            check() failed {
+                echo "handled"
+            }
+            exit 5
+        }
+    "#, true);
+    let (stdout, stderr) = eval_bash(code);
+    assert_eq!(stdout, "handled");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn stack_trace_is_not_recorded_by_default() {
+    let code = compile_with_trace(PROPAGATED_FAILURE, false);
+    assert!(!code.contains("__amber_trace"));
+    let (_, stderr) = eval_bash(code);
+    assert_eq!(stderr, "");
+}
@@ -8,7 +8,8 @@ use std::fs;
 use std::path::Path;
 
 pub fn translate_amber_code<T: Into<String>>(code: T) -> Option<FragmentKind> {
-    let options = CompilerOptions::default();
+    // Constant expressions are kept to check how the operations themselves are translated
+    let options = CompilerOptions::default().with_optimize(false);
     let compiler = AmberCompiler::new(code.into(), None, options);
     let tokens = compiler.tokenize().ok()?;
     let (ast, meta) = compiler.parse(tokens).ok()?;
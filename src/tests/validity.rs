@@ -1,6 +1,8 @@
 /// Tests for Amber scripts that check validity of generated bash execution output.
-use super::script_test;
+use super::{script_test, script_test_with_options};
 use super::TestOutcomeTarget;
+use crate::compiler::CompilerOptions;
+use crate::translate::compute::ArithType;
 use test_generator::test_resources;
 
 /// Autoload the Amber test files in validity, match the output in the comment
@@ -8,3 +10,10 @@ use test_generator::test_resources;
 fn test_validity(input: &str) {
     script_test(input, TestOutcomeTarget::Success);
 }
+
+/// Run the same files with the math on numbers evaluated by `awk`
+#[test_resources("src/tests/validity/*.ab")]
+fn test_validity_awk(input: &str) {
+    let options = CompilerOptions::default().with_num_backend(ArithType::Awk);
+    script_test_with_options(input, TestOutcomeTarget::Success, options);
+}
@@ -0,0 +1,152 @@
+/// Tests for the watch mode and the parsed modules reused between compilations.
+use crate::compiler::{AmberCompiler, CompilerOptions};
+use crate::utils::module_cache::{ModuleCache, SharedModuleCache};
+use crate::watch::Watcher;
+use std::fs;
+use std::path::Path;
+
+fn compile_file(path: &Path, cache: &SharedModuleCache) -> String {
+    let code = fs::read_to_string(path).unwrap();
+    let options = CompilerOptions::default().with_module_cache(cache.clone());
+    let compiler = AmberCompiler::new(code, Some(path.to_string_lossy().to_string()), options);
+    compiler.compile().unwrap().1
+}
+
+#[test]
+fn unchanged_modules_are_reused() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main.ab");
+    let lib = dir.path().join("lib.ab");
+    fs::write(&main, "import { greet } from \"lib.ab\"\ngreet(\"Amber\")\n").unwrap();
+    fs::write(&lib, "pub fun greet(name) {\n    echo \"Hello, {name}\"\n}\n").unwrap();
+    let cache = ModuleCache::new_shared();
+
+    let first = compile_file(&main, &cache);
+    assert_eq!((cache.borrow().hits, cache.borrow().misses), (0, 2));
+    let second = compile_file(&main, &cache);
+    // The type checked import does not have to be parsed either
+    assert_eq!((cache.borrow().hits, cache.borrow().misses, cache.borrow().checked_hits), (1, 2, 1));
+    assert_eq!(first, second);
+
+    fs::write(&lib, "pub fun greet(name) {\n    echo \"Hi, {name}\"\n}\n").unwrap();
+    let third = compile_file(&main, &cache);
+    assert_eq!((cache.borrow().hits, cache.borrow().misses, cache.borrow().checked_hits), (2, 3, 1));
+    assert!(third.contains("Hi, "));
+}
+
+#[test]
+fn modules_with_warnings_are_parsed_again() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main.ab");
+    fs::write(&main, "import {} from \"std/text\"\necho 1\n").unwrap();
+    let cache = ModuleCache::new_shared();
+    compile_file(&main, &cache);
+    compile_file(&main, &cache);
+    // Only the imported module is reused
+    assert!(!cache.borrow().modules.contains_key(&main.to_string_lossy().to_string()));
+    assert_eq!((cache.borrow().hits, cache.borrow().misses, cache.borrow().checked_hits), (0, 3, 1));
+}
+
+#[test]
+fn typechecked_imports_are_reused() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main.ab");
+    let lib = dir.path().join("lib.ab");
+    let names = dir.path().join("names.ab");
+    fs::write(&main, "import { greet } from \"lib.ab\"\ngreet(\"Amber\")\n").unwrap();
+    fs::write(&lib, "import { name } from \"names.ab\"\npub fun greet(greeting) {\n    echo \"{greeting}, {name()}\"\n}\n").unwrap();
+    fs::write(&names, "pub fun name(): Text {\n    return \"World\"\n}\n").unwrap();
+    let cache = ModuleCache::new_shared();
+
+    compile_file(&main, &cache);
+    assert_eq!(cache.borrow().checked_hits, 0);
+    fs::write(&main, "import { greet } from \"lib.ab\"\ngreet(\"Amber\")\ngreet(1)\n").unwrap();
+    let second = compile_file(&main, &cache);
+    assert_eq!(cache.borrow().checked_hits, 1);
+    assert_eq!(second, compile_file(&main, &ModuleCache::new_shared()));
+
+    // Changes of the indirectly imported modules are type checked again
+    fs::write(&names, "pub fun name(): Text {\n    return \"Bash\"\n}\n").unwrap();
+    let third = compile_file(&main, &cache);
+    assert_eq!(cache.borrow().checked_hits, 1);
+    assert!(third.contains("Bash"));
+}
+
+fn disk_cache(dir: &Path) -> SharedModuleCache {
+    let cache = ModuleCache::new_shared();
+    cache.borrow_mut().directory = Some(dir.join("cache"));
+    cache
+}
+
+#[test]
+fn modules_are_reused_from_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main.ab");
+    let lib = dir.path().join("lib.ab");
+    fs::write(&main, "import { greet } from \"lib.ab\"\ngreet(\"Amber\")\n").unwrap();
+    fs::write(&lib, "pub fun greet(name) {\n    echo \"Hello, {name}\"\n}\n").unwrap();
+
+    let cache = disk_cache(dir.path());
+    let first = compile_file(&main, &cache);
+    assert_eq!((cache.borrow().hits, cache.borrow().misses), (0, 2));
+    assert_eq!(fs::read_dir(dir.path().join("cache")).unwrap().count(), 2);
+
+    // A new compiler process starts with an empty memory cache
+    let cache = disk_cache(dir.path());
+    let second = compile_file(&main, &cache);
+    assert_eq!((cache.borrow().hits, cache.borrow().misses), (2, 0));
+    assert_eq!(first, second);
+
+    fs::write(&lib, "pub fun greet(name) {\n    echo \"Hi, {name}\"\n}\n").unwrap();
+    let cache = disk_cache(dir.path());
+    let third = compile_file(&main, &cache);
+    assert_eq!((cache.borrow().hits, cache.borrow().misses), (1, 1));
+    assert!(third.contains("Hi, "));
+    // Only the latest version of each module is kept
+    assert_eq!(fs::read_dir(dir.path().join("cache")).unwrap().count(), 2);
+}
+
+#[test]
+fn invalid_cache_entries_are_parsed_again() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main.ab");
+    fs::write(&main, "echo \"Hello\"\n").unwrap();
+    let first = compile_file(&main, &disk_cache(dir.path()));
+    for entry in fs::read_dir(dir.path().join("cache")).unwrap() {
+        fs::write(entry.unwrap().path(), "{").unwrap();
+    }
+    let cache = disk_cache(dir.path());
+    let second = compile_file(&main, &cache);
+    assert_eq!((cache.borrow().hits, cache.borrow().misses), (0, 1));
+    assert_eq!(first, second);
+}
+
+#[test]
+fn imported_modules_are_cached_separately() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main.ab");
+    let lib = dir.path().join("lib.ab");
+    fs::write(&main, "import * from \"lib.ab\"\n").unwrap();
+    fs::write(&lib, "main {\n    echo \"Hello\"\n}\n").unwrap();
+    let cache = disk_cache(dir.path());
+    compile_file(&main, &cache);
+    // The main block of the library runs only when it is compiled directly
+    let lib_output = compile_file(&lib, &disk_cache(dir.path()));
+    assert!(lib_output.contains("Hello"));
+}
+
+#[test]
+fn watcher_detects_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("main.ab");
+    let created = dir.path().join("lib.ab");
+    fs::write(&file, "echo 1\n").unwrap();
+    let watcher = Watcher::new(vec![file.clone(), created.clone()]);
+    assert!(!watcher.has_changed());
+    fs::write(&file, "echo 12\n").unwrap();
+    assert!(watcher.has_changed());
+
+    let watcher = Watcher::new(vec![file.clone(), created.clone()]);
+    fs::write(&created, "").unwrap();
+    assert!(watcher.has_changed());
+}
@@ -0,0 +1,12 @@
+# Amber stack trace of the failure that is being propagated
+__amber_trace=""
+__amber_trace_push() {
+    __amber_trace="$(printf '%s\n  at %s' "${__amber_trace}" "$1")"
+}
+__amber_trace_print() {
+    __amber_status=$?
+    if [ "${__amber_status}" != 0 ] && [ -n "${__amber_trace}" ]; then
+        printf 'Unhandled failure with exit code %s%s\n' "${__amber_status}" "${__amber_trace}" >&2
+    fi
+}
+trap __amber_trace_print EXIT
@@ -1,10 +1,21 @@
+use clap::ValueEnum;
 use crate::modules::prelude::*;
-use crate::fragments;
+use crate::{fragments, raw_fragment};
 
 use super::fragments::subprocess::SubprocessFragment;
 
+/// Number of decimal places that the results of `awk` are rounded to
+pub const AWK_SCALE: u32 = 10;
+
+/// Program that evaluates the math on numbers in the generated code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 pub enum ArithType {
-    BcSed
+    /// `bc` with the math library, the results are truncated to 20 decimal places
+    #[default]
+    #[value(name = "bc")]
+    BcSed,
+    /// `awk` with double precision, the results are rounded to 10 decimal places
+    Awk,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -60,20 +71,69 @@ pub fn translate_bc_sed_computation(
     SubprocessFragment::new(value).to_frag()
 }
 
+pub fn translate_awk_computation(
+    op: ArithOp,
+    left: FragmentKind,
+    right: FragmentKind
+) -> FragmentKind {
+    // The operands are converted to numbers so that they are not compared as text
+    let (expression, is_number) = match op {
+        ArithOp::Add => ("a + b", true),
+        ArithOp::Sub => ("a - b", true),
+        ArithOp::Mul => ("a * b", true),
+        ArithOp::Div => ("a / b", true),
+        ArithOp::Modulo => ("a % b", true),
+        ArithOp::Neg => ("-b", true),
+        ArithOp::Gt => ("a + 0 > b + 0", false),
+        ArithOp::Ge => ("a + 0 >= b + 0", false),
+        ArithOp::Lt => ("a + 0 < b + 0", false),
+        ArithOp::Le => ("a + 0 <= b + 0", false),
+        ArithOp::Eq => ("a + 0 == b + 0", false),
+        ArithOp::Neq => ("a + 0 != b + 0", false),
+        ArithOp::Not => ("!(b + 0)", false),
+        ArithOp::And => ("(a + 0) && (b + 0)", false),
+        ArithOp::Or => ("(a + 0) || (b + 0)", false),
+    };
+    // The number is printed the same way as `bc` does after the trailing zeros are removed.
+    // The program has no quotes since it can end up in an `eval` (`e` is an empty string).
+    let (format, program) = if is_number {
+        let program = format!(
+            "BEGIN {{ r = sprintf(f, {expression}); sub(/0+$/, e, r); sub(/[.]$/, e, r); \
+            if (r ~ /^-?0[.]/) sub(/0/, e, r); if (r + 0 == 0) r = 0; print r }}"
+        );
+        (raw_fragment!("-v f=%.{AWK_SCALE}f "), program)
+    } else {
+        (FragmentKind::Empty, format!("BEGIN {{ print ({expression}) }}"))
+    };
+    let left = match left {
+        FragmentKind::Empty => FragmentKind::Empty,
+        left => fragments!("-v a=", left, " "),
+    };
+    let program = RawFragment::from(program).to_frag();
+    let value = fragments!("awk ", format, left, "-v b=", right, " '", program, "'");
+    SubprocessFragment::new(value).to_frag()
+}
+
 pub fn translate_float_computation(
-    meta: &TranslateMetadata,
+    meta: &mut TranslateMetadata,
     operator: ArithOp,
     left: Option<FragmentKind>,
     right: Option<FragmentKind>
 ) -> FragmentKind {
+    let (left, right) = (
+        left.unwrap_or(FragmentKind::Empty),
+        right.unwrap_or(FragmentKind::Empty)
+    );
     match meta.arith_module {
         ArithType::BcSed => {
-            let (left, right) = (
-                left.unwrap_or(FragmentKind::Empty),
-                right.unwrap_or(FragmentKind::Empty)
-            );
+            meta.use_command("bc");
+            meta.use_command("sed");
             translate_bc_sed_computation(operator, left, right)
         }
+        ArithType::Awk => {
+            meta.use_command("awk");
+            translate_awk_computation(operator, left, right)
+        }
     }
 }
 
@@ -0,0 +1,302 @@
+use std::cmp::{max, min, Ordering};
+
+use crate::modules::prelude::*;
+use crate::modules::types::Type;
+use super::compute::{ArithOp, ArithType, AWK_SCALE};
+
+/// Number of decimal places used by `bc -l`
+const BC_SCALE: u32 = 20;
+/// Number of significant digits that a double precision number holds exactly
+const DOUBLE_DIGITS: u32 = 15;
+
+/// Value of an expression that is known at compile time.
+/// The values are kept in the form that the generated script would hold them at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constant {
+    Bool(bool),
+    Int(i64),
+    /// Text of the number as it is passed to `bc`
+    Num(String),
+    Text(String),
+}
+
+impl Constant {
+    /// Parses an integer literal unless Bash would read it differently (eg. octal `010`)
+    pub fn from_integer(value: &str) -> Option<Constant> {
+        let number = value.parse::<i64>().ok()?;
+        (number.to_string() == value).then_some(Constant::Int(number))
+    }
+
+    /// Text that the value is rendered to when interpolated
+    pub fn to_text(&self) -> String {
+        match self {
+            Constant::Bool(value) => if *value { "1" } else { "0" }.to_string(),
+            Constant::Int(value) => value.to_string(),
+            Constant::Num(value) | Constant::Text(value) => value.clone(),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Constant::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Reads the number unless the backend would compute with a different value
+    fn as_decimal(&self, backend: ArithType) -> Option<Decimal> {
+        let value = match self {
+            Constant::Int(value) => Decimal::parse(&value.to_string()),
+            Constant::Num(value) => Decimal::parse(value),
+            _ => None,
+        }?;
+        value.is_exact(backend).then_some(value)
+    }
+
+    /// Evaluates the operation the same way as Bash's `$(( ))` for integers or the backend for numbers.
+    /// The results that `awk` would round are left for the runtime.
+    pub fn compute(op: ArithOp, kind: &Type, left: Option<&Constant>, right: &Constant, backend: ArithType) -> Option<Constant> {
+        match (kind, left, right) {
+            (Type::Int, None, Constant::Int(value)) if op == ArithOp::Neg => Some(Constant::Int(value.wrapping_neg())),
+            (Type::Int, Some(Constant::Int(left)), Constant::Int(right)) => {
+                let (left, right) = (*left, *right);
+                let result = match op {
+                    ArithOp::Add => left.wrapping_add(right),
+                    ArithOp::Sub => left.wrapping_sub(right),
+                    ArithOp::Mul => left.wrapping_mul(right),
+                    // Division by zero fails at runtime
+                    ArithOp::Div => left.checked_div(right)?,
+                    ArithOp::Modulo => left.checked_rem(right)?,
+                    _ => return None,
+                };
+                Some(Constant::Int(result))
+            }
+            (Type::Num, None, right) if op == ArithOp::Neg => {
+                let right = right.as_decimal(backend)?;
+                Some(Constant::Num(Decimal { mantissa: -right.mantissa, ..right }.to_bc_output()))
+            }
+            (Type::Num, Some(left), right) => {
+                let (left, right) = (left.as_decimal(backend)?, right.as_decimal(backend)?);
+                let result = match op {
+                    ArithOp::Add => left.add(&right)?,
+                    ArithOp::Sub => left.add(&Decimal { mantissa: -right.mantissa, ..right })?,
+                    ArithOp::Mul => left.mul(&right)?,
+                    ArithOp::Div => left.div(&right)?,
+                    ArithOp::Modulo => left.modulo(&right)?,
+                    _ => return None,
+                };
+                result.is_exact(backend).then(|| Constant::Num(result.to_bc_output()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compares integers or numbers
+    pub fn compare(op: ArithOp, kind: &Type, left: &Constant, right: &Constant, backend: ArithType) -> Option<Constant> {
+        let ordering = match (kind, left, right) {
+            (Type::Int, Constant::Int(left), Constant::Int(right)) => left.cmp(right),
+            (Type::Num, left, right) => left.as_decimal(backend)?.compare(&right.as_decimal(backend)?)?,
+            _ => return None,
+        };
+        let result = match op {
+            ArithOp::Gt => ordering == Ordering::Greater,
+            ArithOp::Ge => ordering != Ordering::Less,
+            ArithOp::Lt => ordering == Ordering::Less,
+            ArithOp::Le => ordering != Ordering::Greater,
+            ArithOp::Eq => ordering == Ordering::Equal,
+            ArithOp::Neq => ordering != Ordering::Equal,
+            _ => return None,
+        };
+        Some(Constant::Bool(result))
+    }
+
+    pub fn translate(&self) -> FragmentKind {
+        match self {
+            Constant::Text(value) => {
+                InterpolableFragment::new(vec![value.clone()], vec![], InterpolableRenderType::StringLiteral).to_frag()
+            }
+            _ => RawFragment::from(self.to_text()).to_frag(),
+        }
+    }
+}
+
+/// Decimal number with the semantics of `bc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Decimal {
+    mantissa: i128,
+    /// Number of digits after the decimal point
+    scale: u32,
+}
+
+fn pow10(exponent: u32) -> Option<i128> {
+    10i128.checked_pow(exponent)
+}
+
+impl Decimal {
+    fn parse(text: &str) -> Option<Decimal> {
+        let (is_negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return None
+        }
+        let mut mantissa: i128 = 0;
+        for letter in integer.chars().chain(fraction.chars()) {
+            let digit = letter.to_digit(10)?;
+            mantissa = mantissa.checked_mul(10)?.checked_add(digit as i128)?;
+        }
+        let scale = u32::try_from(fraction.len()).ok()?;
+        let mantissa = if is_negative { -mantissa } else { mantissa };
+        Some(Decimal { mantissa, scale })
+    }
+
+    fn rescale(&self, scale: u32) -> Option<i128> {
+        self.mantissa.checked_mul(pow10(scale.checked_sub(self.scale)?)?)
+    }
+
+    fn add(&self, other: &Decimal) -> Option<Decimal> {
+        let scale = max(self.scale, other.scale);
+        let mantissa = self.rescale(scale)?.checked_add(other.rescale(scale)?)?;
+        Some(Decimal { mantissa, scale })
+    }
+
+    fn mul(&self, other: &Decimal) -> Option<Decimal> {
+        let full_scale = self.scale + other.scale;
+        let scale = min(full_scale, max(BC_SCALE, max(self.scale, other.scale)));
+        let product = self.mantissa.checked_mul(other.mantissa)?;
+        // The digits beyond the scale are truncated
+        let mantissa = product / pow10(full_scale - scale)?;
+        Some(Decimal { mantissa, scale })
+    }
+
+    fn div(&self, other: &Decimal) -> Option<Decimal> {
+        if other.mantissa == 0 {
+            return None
+        }
+        let numerator = self.mantissa.checked_mul(pow10(other.scale + BC_SCALE)?)?;
+        let denominator = other.mantissa.checked_mul(pow10(self.scale)?)?;
+        Some(Decimal { mantissa: numerator / denominator, scale: BC_SCALE })
+    }
+
+    /// Remainder of `bc` without the math library where the quotient is truncated to an integer
+    fn modulo(&self, other: &Decimal) -> Option<Decimal> {
+        if other.mantissa == 0 {
+            return None
+        }
+        let numerator = self.mantissa.checked_mul(pow10(other.scale)?)?;
+        let denominator = other.mantissa.checked_mul(pow10(self.scale)?)?;
+        let quotient = numerator / denominator;
+        let scale = max(self.scale, other.scale);
+        let subtrahend = Decimal { mantissa: quotient.checked_mul(other.mantissa)?, scale: other.scale };
+        let mantissa = self.rescale(scale)?.checked_sub(subtrahend.rescale(scale)?)?;
+        Some(Decimal { mantissa, scale })
+    }
+
+    /// Checks if the backend holds the number without rounding it
+    fn is_exact(&self, backend: ArithType) -> bool {
+        match backend {
+            ArithType::BcSed => true,
+            ArithType::Awk => {
+                let mut value = *self;
+                while value.scale > 0 && value.mantissa % 10 == 0 {
+                    value = Decimal { mantissa: value.mantissa / 10, scale: value.scale - 1 };
+                }
+                let digits = value.mantissa.unsigned_abs().to_string().len();
+                value.scale <= AWK_SCALE && digits <= DOUBLE_DIGITS as usize
+            }
+        }
+    }
+
+    fn compare(&self, other: &Decimal) -> Option<Ordering> {
+        let scale = max(self.scale, other.scale);
+        Some(self.rescale(scale)?.cmp(&other.rescale(scale)?))
+    }
+
+    /// Renders the number as `bc` prints it after the trailing zeros are removed by `sed`
+    fn to_bc_output(self) -> String {
+        if self.mantissa == 0 {
+            return String::from("0")
+        }
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let (integer, fraction) = if digits.len() > scale {
+            digits.split_at(digits.len() - scale)
+        } else {
+            ("", digits.as_str())
+        };
+        // The integer part is left out for the numbers between -1 and 1
+        let fraction = format!("{fraction:0>scale$}");
+        let fraction = fraction.trim_end_matches('0');
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{sign}{integer}")
+        } else {
+            format!("{sign}{integer}.{fraction}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(value: &str) -> Constant {
+        Constant::Num(value.to_string())
+    }
+
+    fn compute(op: ArithOp, left: &str, right: &str) -> Option<Constant> {
+        Constant::compute(op, &Type::Num, Some(&num(left)), &num(right), ArithType::BcSed)
+    }
+
+    #[test]
+    fn test_bc_output() {
+        assert_eq!(compute(ArithOp::Add, "1.5", "1.5"), Some(num("3")));
+        assert_eq!(compute(ArithOp::Sub, "0.25", "0.75"), Some(num("-.5")));
+        assert_eq!(compute(ArithOp::Mul, "100.0", "0.5"), Some(num("50")));
+        assert_eq!(compute(ArithOp::Div, "1.0", "3.0"), Some(num(".33333333333333333333")));
+        assert_eq!(compute(ArithOp::Div, "-2", "3"), Some(num("-.66666666666666666666")));
+        assert_eq!(compute(ArithOp::Modulo, "7.5", "2"), Some(num("1.5")));
+        assert_eq!(compute(ArithOp::Modulo, "-7", "2"), Some(num("-1")));
+        assert_eq!(compute(ArithOp::Sub, "2.5", "2.5"), Some(num("0")));
+        assert_eq!(compute(ArithOp::Div, "1.0", "0.0"), None);
+    }
+
+    #[test]
+    fn test_bc_truncation() {
+        // The product keeps at most 20 decimal places unless the operands have more
+        let small = "0.00000000001";
+        assert_eq!(compute(ArithOp::Mul, small, small), Some(num("0")));
+        assert_eq!(compute(ArithOp::Mul, "1.25", "1.25"), Some(num("1.5625")));
+    }
+
+    #[test]
+    fn test_integers() {
+        let int = |op, left, right| Constant::compute(op, &Type::Int, Some(&Constant::Int(left)), &Constant::Int(right), ArithType::BcSed);
+        assert_eq!(int(ArithOp::Div, -7, 2), Some(Constant::Int(-3)));
+        assert_eq!(int(ArithOp::Modulo, -7, 2), Some(Constant::Int(-1)));
+        assert_eq!(int(ArithOp::Mul, i64::MAX, 2), Some(Constant::Int(-2)));
+        assert_eq!(int(ArithOp::Div, 1, 0), None);
+        assert_eq!(Constant::from_integer("010"), None);
+        assert_eq!(Constant::from_integer("-12"), Some(Constant::Int(-12)));
+    }
+
+    #[test]
+    fn test_awk_rounding() {
+        let awk = |op, left: &str, right: &str| Constant::compute(op, &Type::Num, Some(&num(left)), &num(right), ArithType::Awk);
+        assert_eq!(awk(ArithOp::Div, "1.0", "4.0"), Some(num(".25")));
+        assert_eq!(awk(ArithOp::Div, "1.0", "3.0"), None);
+        assert_eq!(awk(ArithOp::Add, "0.1", "0.2"), Some(num(".3")));
+        assert_eq!(awk(ArithOp::Mul, "123456789.123", "1000"), Some(num("123456789123")));
+        assert_eq!(awk(ArithOp::Mul, "123456789.123", "100000000"), None);
+    }
+
+    #[test]
+    fn test_comparison() {
+        let result = Constant::compare(ArithOp::Ge, &Type::Num, &num("1.50"), &Constant::Int(1), ArithType::BcSed);
+        assert_eq!(result, Some(Constant::Bool(true)));
+        let result = Constant::compare(ArithOp::Eq, &Type::Num, &num("-.5"), &num("-0.50"), ArithType::BcSed);
+        assert_eq!(result, Some(Constant::Bool(true)));
+    }
+}
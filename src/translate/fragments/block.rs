@@ -57,6 +57,12 @@ impl FragmentRenderable for BlockFragment {
                         result.push(rendered);
                     }
                 }
+                FragmentKind::Function(function) => {
+                    let rendered = function.to_string(meta);
+                    if !rendered.is_empty() {
+                        result.push(rendered);
+                    }
+                }
                 _ => {
                     let statement = statement.to_string(meta);
                     for stmt in mem::take(&mut meta.stmt_queue) {
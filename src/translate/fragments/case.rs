@@ -0,0 +1,62 @@
+use std::mem;
+use itertools::Itertools;
+use crate::utils::TranslateMetadata;
+use super::fragment::{FragmentKind, FragmentRenderable};
+
+/// Renders Bash `case` statement matching a value against the patterns of its arms.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseArm {
+    pub patterns: Vec<FragmentKind>,
+    pub block: FragmentKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseFragment {
+    pub value: Box<FragmentKind>,
+    pub arms: Vec<CaseArm>,
+}
+
+impl CaseFragment {
+    pub fn new(value: FragmentKind) -> Self {
+        CaseFragment {
+            value: Box::new(value),
+            arms: vec![],
+        }
+    }
+
+    pub fn add_arm(&mut self, patterns: Vec<FragmentKind>, block: FragmentKind) {
+        self.arms.push(CaseArm { patterns, block });
+    }
+}
+
+impl FragmentRenderable for CaseFragment {
+    fn to_string(self, meta: &mut TranslateMetadata) -> String {
+        let value = self.value.to_string(meta);
+        // Statements queued by the value have to be rendered before the case statement
+        let value_queue = mem::take(&mut meta.stmt_queue);
+        let mut result = vec![format!("case {value} in")];
+        meta.increase_indent();
+        for arm in self.arms {
+            let patterns = arm.patterns.into_iter()
+                .map(|pattern| pattern.to_string(meta))
+                .join("|");
+            result.push(format!("{}{patterns})", meta.gen_indent()));
+            let block = arm.block.to_string(meta);
+            if !block.is_empty() {
+                result.push(block);
+            }
+            meta.increase_indent();
+            result.push(format!("{};;", meta.gen_indent()));
+            meta.decrease_indent();
+        }
+        meta.decrease_indent();
+        result.push(format!("{}esac", meta.gen_indent()));
+        meta.stmt_queue = value_queue;
+        result.join("\n")
+    }
+
+    fn to_frag(self) -> FragmentKind {
+        FragmentKind::Case(self)
+    }
+}
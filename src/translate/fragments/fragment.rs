@@ -1,6 +1,8 @@
 use super::{
     block::BlockFragment,
+    case::CaseFragment,
     comment::CommentFragment,
+    function::FunctionFragment,
     interpolable::InterpolableFragment,
     list::ListFragment,
     raw::RawFragment,
@@ -21,11 +23,13 @@ pub enum FragmentKind {
     VarExpr(VarExprFragment),
     VarStmt(VarStmtFragment),
     Block(BlockFragment),
+    Case(CaseFragment),
     Interpolable(InterpolableFragment),
     List(ListFragment),
     Subprocess(SubprocessFragment),
     Arithmetic(ArithmeticFragment),
     Comment(CommentFragment),
+    Function(FunctionFragment),
     #[default] Empty
 }
 
@@ -84,11 +88,13 @@ impl FragmentRenderable for FragmentKind {
             FragmentKind::VarExpr(var) => var.to_string(meta),
             FragmentKind::VarStmt(var) => var.to_string(meta),
             FragmentKind::Block(block) => block.to_string(meta),
+            FragmentKind::Case(case) => case.to_string(meta),
             FragmentKind::Interpolable(interpolable) => interpolable.to_string(meta),
             FragmentKind::List(list) => list.to_string(meta),
             FragmentKind::Subprocess(subprocess) => subprocess.to_string(meta),
             FragmentKind::Arithmetic(arithmetic) => arithmetic.to_string(meta),
             FragmentKind::Comment(comment) => comment.to_string(meta),
+            FragmentKind::Function(function) => function.to_string(meta),
             FragmentKind::Empty => String::new(),
         }
     }
@@ -0,0 +1,39 @@
+use crate::utils::TranslateMetadata;
+use super::block::BlockFragment;
+use super::fragment::{FragmentKind, FragmentRenderable};
+use super::raw::RawFragment;
+
+/// Represents a definition of a Bash function.
+/// It is kept apart from other blocks so that the optimizer can find the functions.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionFragment {
+    pub name: String,
+    // Local variables holding the positional arguments
+    pub args: Option<Box<FragmentKind>>,
+    pub body: Box<FragmentKind>,
+}
+
+impl FunctionFragment {
+    pub fn new(name: &str, args: Option<FragmentKind>, body: FragmentKind) -> Self {
+        FunctionFragment {
+            name: name.to_string(),
+            args: args.map(Box::new),
+            body: Box::new(body),
+        }
+    }
+}
+
+impl FragmentRenderable for FunctionFragment {
+    fn to_string(self, meta: &mut TranslateMetadata) -> String {
+        let mut statements = vec![RawFragment::from(format!("{}() {{", self.name)).to_frag()];
+        statements.extend(self.args.map(|args| *args));
+        statements.push(*self.body);
+        statements.push(RawFragment::new("}\n").to_frag());
+        BlockFragment::new(statements, false).to_string(meta)
+    }
+
+    fn to_frag(self) -> FragmentKind {
+        FragmentKind::Function(self)
+    }
+}
@@ -1,6 +1,8 @@
 pub mod block;
+pub mod case;
 pub mod comment;
 pub mod fragment;
+pub mod function;
 pub mod interpolable;
 pub mod list;
 pub mod raw;
@@ -64,3 +66,13 @@ pub fn get_variable_name(name: &str, global_id: Option<usize>) -> String {
         None => name.to_string()
     }
 }
+
+// Returns an array index that counts negative values from the end of the array.
+// Bash older than 4.3 does not support negative indices on its own.
+pub fn get_array_index_with_length(index: &str, length: &str) -> String {
+    match index.parse::<i64>() {
+        Ok(value) if value >= 0 => index.to_string(),
+        Ok(value) => format!("{length}{value}"),
+        Err(_) => format!("({index}) < 0 ? {length} + ({index}) : ({index})"),
+    }
+}
@@ -4,7 +4,8 @@ use crate::modules::types::Type;
 use crate::modules::prelude::RawFragment;
 use crate::modules::expression::expr::{Expr, ExprType};
 use super::fragment::{FragmentKind, FragmentRenderable};
-use super::get_variable_name;
+use super::{get_array_index_with_length, get_variable_name};
+use crate::compiler::target::BashVersion;
 use super::var_stmt::VarStmtFragment;
 
 /// Represents a variable expression such as `$var` or `${var}`
@@ -19,6 +20,8 @@ pub enum VarRenderType {
 pub enum VarIndexValue {
     Index(FragmentKind),
     Range(FragmentKind, FragmentKind),
+    // Key of an associative array
+    Key(FragmentKind),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,6 +35,8 @@ pub struct VarExprFragment {
     pub is_ref: bool,
     // Bash's length getter `${#var}`
     pub is_length: bool,
+    // Bash's keys getter `${!var[@]}`
+    pub is_keys: bool,
     // Bash's default value `${var:-default}`
     pub default_value: Option<Box<FragmentKind>>,
     // Quotes around this expression
@@ -53,6 +58,7 @@ impl Default for VarExprFragment {
             kind: Type::Generic,
             is_ref: false,
             is_length: false,
+            is_keys: false,
             is_array_to_string: false,
             is_quoted: true,
             render_type: VarRenderType::BashValue,
@@ -98,6 +104,10 @@ impl VarExprFragment {
 
     pub fn with_index_by_expr<T: Into<Option<Expr>>>(mut self, meta: &mut TranslateMetadata, index: T) -> Self {
         if let Some(index) = index.into() {
+            if let Some(static_index) = index.get_static_array_index(meta) {
+                self.index = Some(Box::new(VarIndexValue::Index(static_index)));
+                return self;
+            }
             let index = match index.value {
                 Some(ExprType::Range(range)) => {
                     let (offset, length) = range.get_array_index(meta);
@@ -132,6 +142,11 @@ impl VarExprFragment {
         self
     }
 
+    pub fn with_keys_getter(mut self, value: bool) -> Self {
+        self.is_keys = value;
+        self
+    }
+
     pub fn with_render_type(mut self, render_type: VarRenderType) -> Self {
         self.render_type = render_type;
         self
@@ -149,6 +164,7 @@ impl VarExprFragment {
     pub fn get_index_typename(&self) -> String {
         match self.index.as_deref() {
             Some(VarIndexValue::Index(_)) => "index".to_string(),
+            Some(VarIndexValue::Key(_)) => "key".to_string(),
             Some(VarIndexValue::Range(_, _)) => "range".to_string(),
             None => "access".to_string(),
         }
@@ -192,6 +208,8 @@ impl VarExprFragment {
     fn get_variable_prefix(&self) -> &'static str {
         if self.is_length {
             "#"
+        } else if self.is_keys {
+            "!"
         } else {
             ""
         }
@@ -218,13 +236,30 @@ impl VarExprFragment {
                 format!("[@]:{offset}:{length}")
             }
             (_, Some(VarIndexValue::Index(index))) => {
-                let index = index.with_quotes(false).to_string(meta);
+                let mut index = index.with_quotes(false).to_string(meta);
+                if meta.is_bash_older_than(BashVersion::NEGATIVE_INDEX) {
+                    // References are resolved inside of `eval` with escaped expansions
+                    let length = if self.is_ref {
+                        format!("\\${{#${{{}}}[@]}}", self.get_name())
+                    } else {
+                        format!("{}{{#{}[@]}}", meta.gen_dollar(), self.get_name())
+                    };
+                    index = get_array_index_with_length(&index, &length);
+                }
                 format!("[{index}]{default_value}")
             }
-            (Type::Array(_), None) if self.is_array_to_string => {
+            (_, Some(VarIndexValue::Key(key))) => {
+                // Quoted keys can contain `]`, spaces and `$`. References are resolved inside of `eval`
+                let prev = meta.eval_ctx;
+                meta.eval_ctx = prev || self.is_ref;
+                let key = key.with_quotes(true).to_string(meta);
+                meta.eval_ctx = prev;
+                format!("[{key}]{default_value}")
+            }
+            (Type::Array(_) | Type::Struct(_) | Type::Map(_), None) if self.is_array_to_string => {
                 format!("[*]{default_value}")
             }
-            (Type::Array(_), None) => {
+            (Type::Array(_) | Type::Struct(_) | Type::Map(_), None) => {
                 format!("[@]{default_value}")
             }
             _ => {
@@ -234,11 +269,13 @@ impl VarExprFragment {
     }
 
     fn render_deref_variable(self, meta: &mut TranslateMetadata, prefix: &str, name: &str, suffix: &str) -> String {
-        let arr_open = if self.kind.is_array() { "(" } else { "" };
-        let arr_close = if self.kind.is_array() { ")" } else { "" };
+        let is_list = self.kind.is_bash_array() || self.kind.is_map();
+        let arr_open = if is_list { "(" } else { "" };
+        let arr_close = if is_list { ")" } else { "" };
         let quote = if self.is_quoted { meta.gen_quote() } else { "" };
         let dollar = meta.gen_dollar();
-        if prefix.is_empty() && suffix.is_empty() {
+        // Indirect expansion is not available in POSIX shell
+        if prefix.is_empty() && suffix.is_empty() && !meta.target.is_posix() {
             return format!("{quote}{dollar}{{!{name}}}{quote}");
         }
         let id = meta.gen_value_id();
@@ -248,7 +285,7 @@ impl VarExprFragment {
             format!("eval \"local {var_name}={arr_open}\\\"\\${{{eval_value}}}\\\"{arr_close}\"")
         ).to_frag());
 
-        if self.kind.is_array() {
+        if is_list {
             format!("{quote}{dollar}{{{var_name}[@]}}{quote}")
         } else {
             format!("{quote}{dollar}{{{var_name}}}{quote}")
@@ -2,13 +2,16 @@ use crate::eval_context;
 use crate::modules::types::Type;
 use crate::modules::prelude::*;
 
-use super::get_variable_name;
+use super::{get_array_index_with_length, get_variable_name};
+use crate::compiler::target::BashVersion;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VarStmtFragment {
     pub name: String,
     pub global_id: Option<usize>,
     pub index: Option<Box<FragmentKind>>,
+    // Key of an associative array like `map["key"]`
+    pub key: Option<Box<FragmentKind>>,
     pub kind: Type,
     // This variable is made only for storing a value used by a single expression
     pub is_ephemeral: bool,
@@ -28,6 +31,7 @@ impl Default for VarStmtFragment {
             name: String::new(),
             global_id: None,
             index: None,
+            key: None,
             kind: Type::Generic,
             is_ephemeral: false,
             is_ref: false,
@@ -79,6 +83,11 @@ impl VarStmtFragment {
         self
     }
 
+    pub fn with_key<T: Into<Option<FragmentKind>>>(mut self, key: T) -> Self {
+        self.key = key.into().map(Box::new);
+        self
+    }
+
     pub fn with_operator(mut self, op: &str) -> Self {
         self.operator = op.to_string();
         self
@@ -108,11 +117,20 @@ impl VarStmtFragment {
         let is_running_command = self.value.is_running_command();
         let mut assignment_parts = vec![];
         assignment_parts.push(var_name.clone());
-        assignment_parts.extend(self.index.map(|index| format!("[{}]", index.to_string(meta))));
+        assignment_parts.extend(self.index.map(|index| {
+            let index = index.to_string(meta);
+            if meta.is_bash_older_than(BashVersion::NEGATIVE_INDEX) {
+                let length = format!("{}{{#{var_name}[@]}}", meta.gen_dollar());
+                format!("[{}]", get_array_index_with_length(&index, &length))
+            } else {
+                format!("[{index}]")
+            }
+        }));
+        assignment_parts.extend(self.key.map(|key| format!("[{}]", key.to_string(meta))));
         assignment_parts.push(self.operator);
 
 
-        if self.kind.is_array() {
+        if self.kind.is_bash_array() {
             assignment_parts.push(format!("({})", self.value.to_string(meta)));
         } else {
             assignment_parts.push(self.value.to_string(meta));
@@ -129,11 +147,47 @@ impl VarStmtFragment {
             assignment
         }
     }
+
+    // Associative arrays have to be declared and cannot be assigned from another one as a whole
+    fn render_map_statement(self, meta: &mut TranslateMetadata) -> String {
+        let target = self.render_variable_name();
+        let mut lines = vec![];
+        // References point to maps that are already declared
+        if self.is_local {
+            lines.push(format!("local -A {target}"));
+        } else if !self.is_ref {
+            lines.push(format!("declare -gA {target}"));
+        }
+        match *self.value {
+            FragmentKind::VarExpr(source) if source.is_ref || self.is_ref => {
+                let source = match source.is_ref {
+                    true => format!("${{{}}}", source.get_name()),
+                    false => source.get_name(),
+                };
+                lines.push(format!("eval \"{target}=(); for __key in \\\"\\${{!{source}[@]}}\\\"; do {target}[\\\"\\$__key\\\"]=\\\"\\${{{source}[\\$__key]}}\\\"; done\""));
+            }
+            FragmentKind::VarExpr(source) => {
+                let source = source.get_name();
+                lines.push(format!("{target}=()"));
+                lines.push(format!("for __key in \"${{!{source}[@]}}\"; do {target}[\"$__key\"]=\"${{{source}[$__key]}}\"; done"));
+            }
+            value if self.is_ref => {
+                let value = eval_context!(meta, true, { value.to_string(meta) });
+                lines.push(format!("eval \"{target}=({value})\""));
+            }
+            value => {
+                lines.push(format!("{target}=({})", value.to_string(meta)));
+            }
+        }
+        lines.join(&format!("\n{}", meta.gen_indent()))
+    }
 }
 
 impl FragmentRenderable for VarStmtFragment {
     fn to_string(self, meta: &mut TranslateMetadata) -> String {
-        if self.is_ref {
+        if self.kind.is_map() && self.key.is_none() {
+            self.render_map_statement(meta)
+        } else if self.is_ref {
             let stmt = eval_context!(meta, self.is_ref, {
                 self.render_variable_statement(meta)
             });
@@ -5,6 +5,7 @@ pub mod module;
 pub mod fragments;
 pub mod compute;
 pub mod compare;
+pub mod constant;
 
 pub fn check_all_blocks(meta: &ParserMetadata) -> SyntaxResult {
     let mut stack = 0;
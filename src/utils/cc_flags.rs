@@ -1,8 +1,14 @@
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+use crate::utils::commands::is_command_name;
+use serde::{Deserialize, Serialize};
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum CCFlags {
     AllowNestedIfElse,
     AllowAbsurdCast,
     AllowCamelCase,
+    AllowUnquotedInterpolation,
+    AllowCdInCommand,
+    AllowUnsafeRm,
+    AllowBackticks,
     UndefinedFlag
 }
 
@@ -11,6 +17,10 @@ pub fn get_ccflag_by_name(flag: &str) -> CCFlags {
         "allow_nested_if_else" => CCFlags::AllowNestedIfElse,
         "allow_camel_case" => CCFlags::AllowCamelCase,
         "allow_absurd_cast" => CCFlags::AllowAbsurdCast,
+        "allow_unquoted_interpolation" => CCFlags::AllowUnquotedInterpolation,
+        "allow_cd_in_command" => CCFlags::AllowCdInCommand,
+        "allow_unsafe_rm" => CCFlags::AllowUnsafeRm,
+        "allow_backticks" => CCFlags::AllowBackticks,
         _ => CCFlags::UndefinedFlag
     }
 }
@@ -21,6 +31,30 @@ pub fn get_ccflag_name(flag: CCFlags) -> &'static str {
         CCFlags::AllowNestedIfElse => "allow_nested_if_else",
         CCFlags::AllowAbsurdCast => "allow_absurd_cast",
         CCFlags::AllowCamelCase => "allow_camel_case",
+        CCFlags::AllowUnquotedInterpolation => "allow_unquoted_interpolation",
+        CCFlags::AllowCdInCommand => "allow_cd_in_command",
+        CCFlags::AllowUnsafeRm => "allow_unsafe_rm",
+        CCFlags::AllowBackticks => "allow_backticks",
         CCFlags::UndefinedFlag => "undefined_flag"
     }
 }
+
+/// Parses the commands of the `requires` flag (eg. `requires("jq", "rsync")`)
+/// or returns `None` if it is a different flag
+pub fn parse_required_commands(flag: &str) -> Option<Result<Vec<String>, String>> {
+    let args = flag.strip_prefix("requires")?.trim();
+    let Some(args) = args.strip_prefix('(').and_then(|args| args.strip_suffix(')')) else {
+        return Some(Err(String::from("Expected the required commands in parentheses, e.g. 'requires(\"jq\")'")));
+    };
+    let commands = args.split(',')
+        .map(|arg| {
+            let arg = arg.trim();
+            match arg.strip_prefix('"').and_then(|arg| arg.strip_suffix('"')) {
+                Some(name) if is_command_name(name) => Ok(name.to_string()),
+                Some(name) => Err(format!("Invalid command name '{name}' in the 'requires' flag")),
+                None => Err(format!("Command {arg} in the 'requires' flag has to be quoted")),
+            }
+        })
+        .collect();
+    Some(commands)
+}
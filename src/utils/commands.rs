@@ -0,0 +1,52 @@
+/// Shell keywords and builtins that do not need to be installed
+const BUILTINS: [&str; 62] = [
+    "!", ".", ":", "[", "[[", "{", "}", "alias", "bg", "break", "builtin", "case", "cd",
+    "command", "continue", "declare", "do", "done", "echo", "elif", "else", "esac", "eval",
+    "exec", "exit", "export", "false", "fc", "fg", "fi", "for", "function", "getopts", "hash",
+    "if", "jobs", "kill", "let", "local", "mapfile", "printf", "pwd", "read", "readarray",
+    "readonly", "return", "select", "set", "shift", "shopt", "source", "test", "then", "time",
+    "trap", "true", "type", "typeset", "umask", "unset", "until", "wait",
+];
+
+/// Words that can precede the command name
+const PREFIXES: [&str; 12] = ["!", "{", "command", "do", "elif", "else", "exec", "if", "then", "time", "until", "while"];
+
+/// Checks if the name can be used as a command without quoting
+pub fn is_command_name(name: &str) -> bool {
+    name.starts_with(|letter: char| letter.is_ascii_alphabetic() || "_./".contains(letter))
+        && name.chars().all(|letter| letter.is_ascii_alphanumeric() || "_.+-/".contains(letter))
+}
+
+/// Lists the external commands started by a command literal.
+/// Only the commands written out are found, not the ones coming from interpolations.
+pub fn get_invoked_commands(strings: &[String]) -> Vec<String> {
+    // Interpolations are marked so that they are not mistaken for a part of a command name
+    let code = strings.join("\0");
+    code.split(|letter: char| "|;&\n()`".contains(letter))
+        .filter_map(|segment| {
+            segment.split_whitespace()
+                // Skip the variables set for the command (eg. `LANG=C sort`)
+                .find(|word| !word.contains('=') && !PREFIXES.contains(word))
+        })
+        .filter(|word| is_command_name(word) && !BUILTINS.contains(word))
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(strings: &[&str]) -> Vec<String> {
+        get_invoked_commands(&strings.iter().map(|string| string.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_invoked_commands() {
+        assert_eq!(commands(&["curl -s ", " | jq .name"]), ["curl", "jq"]);
+        assert_eq!(commands(&["LANG=C sort file && echo done"]), ["sort"]);
+        assert_eq!(commands(&["", " --version"]), Vec::<String>::new());
+        assert_eq!(commands(&["if [ -f file ]; then rsync -a src/ dst/; fi"]), ["rsync"]);
+        assert_eq!(commands(&["ls 2>&1"]), ["ls"]);
+    }
+}
@@ -5,6 +5,7 @@ use crate::modules::types::Type;
 use amber_meta::ContextHelper;
 use heraclitus_compiler::prelude::*;
 use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug)]
 pub struct FunctionDeclArg {
@@ -12,6 +13,7 @@ pub struct FunctionDeclArg {
     pub kind: Type,
     pub optional: Option<Expr>,
     pub is_ref: bool,
+    pub tok: Option<Token>,
 }
 
 #[derive(Clone, Debug)]
@@ -26,6 +28,22 @@ pub struct FunctionDecl {
 }
 
 impl FunctionDecl {
+    /// Renders the function signature, eg. `fun add(a: Int, b: Int): Int`
+    pub fn render_signature(&self) -> String {
+        let args = self.args.iter().map(|arg| {
+            let prefix = if arg.is_ref { "ref " } else { "" };
+            match arg.kind {
+                Type::Generic => format!("{prefix}{}", arg.name),
+                ref kind => format!("{prefix}{}: {kind}", arg.name),
+            }
+        }).collect::<Vec<_>>().join(", ");
+        let failable = if self.is_failable { "?" } else { "" };
+        match self.returns {
+            Type::Generic => format!("fun {}({args}){failable}", self.name),
+            ref returns => format!("fun {}({args}): {returns}{failable}", self.name),
+        }
+    }
+
     pub fn into_interface(self) -> FunctionInterface {
         let args = self
             .args
@@ -35,7 +53,7 @@ impl FunctionDecl {
                 kind: arg.kind,
                 optional: arg.optional,
                 is_ref: arg.is_ref,
-                tok: None,
+                tok: arg.tok,
             })
             .collect();
 
@@ -50,10 +68,33 @@ impl FunctionDecl {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct StructDecl {
+    pub name: String,
+    /// Fields in the declaration order which is also their index in the Bash array
+    pub fields: Vec<(String, Type)>,
+    pub is_public: bool,
+}
+
+impl StructDecl {
+    /// Returns the index and the type of the field
+    pub fn get_field(&self, name: &str) -> Option<(usize, &Type)> {
+        self.fields.iter()
+            .position(|(field, _)| field == name)
+            .map(|index| (index, &self.fields[index].1))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EnumDecl {
+    pub name: String,
+    pub variants: Vec<String>,
+    pub is_public: bool,
+}
 
 // Rule set for variable warnings
 // Unused variable warning is enabled by default
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VariableDeclWarn {
     pub pos: Option<PositionInfo>,
     pub on_unused: bool,
@@ -84,7 +125,7 @@ impl VariableDeclWarn {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VariableDecl {
     pub name: String,
     pub kind: Type,
@@ -131,6 +172,8 @@ impl VariableDecl {
 pub struct ScopeUnit {
     pub vars: HashMap<String, VariableDecl>,
     pub funs: HashMap<String, FunctionDecl>,
+    pub structs: HashMap<String, StructDecl>,
+    pub enums: HashMap<String, EnumDecl>,
 }
 
 /// Perform methods just on the scope
@@ -173,6 +216,32 @@ impl ScopeUnit {
     pub fn get_fun_names(&self) -> Vec<&String> {
         self.funs.keys().collect()
     }
+
+    /* Structs */
+
+    /// Persists a struct declaration in the scope
+    pub fn add_struct(&mut self, decl: StructDecl) -> bool {
+        let name = decl.name.clone();
+        self.structs.insert(name, decl).is_none()
+    }
+
+    /// Fetches a struct declaration from the scope
+    pub fn get_struct(&self, name: &str) -> Option<&StructDecl> {
+        self.structs.get(name)
+    }
+
+    /* Enums */
+
+    /// Persists an enum declaration in the scope
+    pub fn add_enum(&mut self, decl: EnumDecl) -> bool {
+        let name = decl.name.clone();
+        self.enums.insert(name, decl).is_none()
+    }
+
+    /// Fetches an enum declaration from the scope
+    pub fn get_enum(&self, name: &str) -> Option<&EnumDecl> {
+        self.enums.get(name)
+    }
 }
 
 #[derive(Clone, Debug, ContextHelper)]
@@ -198,6 +267,10 @@ pub struct Context {
     pub is_trust_ctx: bool,
     /// This is a list of ids of all the public functions in the file
     pub pub_funs: Vec<FunctionDecl>,
+    /// This is a list of all the public structs in the file
+    pub pub_structs: Vec<StructDecl>,
+    /// This is a list of all the public enums in the file
+    pub pub_enums: Vec<EnumDecl>,
     /// The return type of the currently parsed function
     pub fun_ret_type: Option<Type>,
     /// List of compiler flags
@@ -219,6 +292,8 @@ impl Context {
             is_main_ctx: false,
             is_trust_ctx: false,
             pub_funs: vec![],
+            pub_structs: vec![],
+            pub_enums: vec![],
             fun_ret_type: None,
             cc_flags: HashSet::new(),
         }
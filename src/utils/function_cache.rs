@@ -1,6 +1,6 @@
 use super::context::Context;
 use crate::modules::{block::Block, types::Type};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Debug)]
 /// This is a compiled function instance
@@ -12,7 +12,7 @@ pub struct FunctionInstance {
     pub block: Block,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 /// This is a cached data representing a function
 pub struct FunctionCacheEntry {
     pub instances: Vec<FunctionInstance>,
@@ -24,6 +24,8 @@ pub struct FunctionCacheEntry {
 // This is a map of all generated functions based on their invocations
 pub struct FunctionCache {
     pub funs: HashMap<usize, FunctionCacheEntry>,
+    /// Function instances (by id and variant id) that are used as function values
+    pub referenced: HashSet<(usize, usize)>,
 }
 
 impl FunctionCache {
@@ -51,6 +53,16 @@ impl FunctionCache {
         functions.instances.len() - 1
     }
 
+    /// Marks the function instance as used as a function value
+    pub fn mark_referenced(&mut self, id: usize, variant_id: usize) {
+        self.referenced.insert((id, variant_id));
+    }
+
+    /// Checks if the function instance is used as a function value
+    pub fn is_referenced(&self, id: usize, variant_id: usize) -> bool {
+        self.referenced.contains(&(id, variant_id))
+    }
+
     /// Gets all the function instances of a function declaration
     pub fn get_instances_cloned(&self, id: usize) -> Option<Vec<FunctionInstance>> {
         self.funs.get(&id).map(|f| f.instances.clone())
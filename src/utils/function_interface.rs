@@ -20,6 +20,7 @@ impl FunctionInterface {
             kind: arg.kind,
             optional: arg.optional,
             is_ref: arg.is_ref,
+            tok: arg.tok,
         }).collect();
 
         FunctionDecl {
@@ -2,8 +2,12 @@ use crate::modules::prelude::*;
 use crate::modules::types::Type;
 use crate::raw_fragment;
 use crate::utils::is_all_caps;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+/// Variable holding the return value of a function called through a function value
+pub const FUNCTION_VALUE_RETURN: &str = "__fn_ret";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionMetadata {
     name: String,
     id: usize,
@@ -26,12 +30,22 @@ impl FunctionMetadata {
         }
     }
 
+    /// Name of the Bash function that wraps this function when it is used as a value
+    pub fn reference_name(&self) -> String {
+        let prefix = if is_all_caps(&self.name) { "__" } else { "" };
+        format!("{prefix}{}__{}_v{}__ref", self.name, self.id, self.variant)
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
     pub fn get_type(&self) -> Type {
         self.returns.clone()
     }
 
     pub fn default_return(&self) -> FragmentKind {
-        if self.returns.is_array() {
+        if self.returns.is_bash_array() || self.returns.is_map() {
             raw_fragment!("")
         } else {
             raw_fragment!("''")
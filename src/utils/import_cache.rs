@@ -1,10 +1,14 @@
 use crate::modules::block::Block;
-use super::context::FunctionDecl;
+use super::context::{EnumDecl, FunctionDecl, StructDecl};
 
 #[derive(Debug, Clone)]
 pub struct FileMetadata {
+    /// Hash of the code of the file
+    pub hash: String,
     pub block: Block,
-    pub pub_funs: Vec<FunctionDecl>
+    pub pub_funs: Vec<FunctionDecl>,
+    pub pub_structs: Vec<StructDecl>,
+    pub pub_enums: Vec<EnumDecl>
 }
 
 #[derive(Debug, Clone)]
@@ -18,7 +22,9 @@ pub struct ImportCache {
     /// The paths of the imports (used to be able to resolve imports with topological sort)
     pub import_graph: Vec<Vec<usize>>,
     /// Cached imported files (always has the same length as the import graph)
-    pub files: Vec<FileCache>
+    pub files: Vec<FileCache>,
+    /// Files inlined into the generated code with 'embed'
+    pub embedded_files: Vec<String>
 }
 
 impl ImportCache {
@@ -37,7 +43,8 @@ impl ImportCache {
                 path: Self::get_path(initial_path),
                 metadata: None
             }],
-            import_graph: vec![vec![]]
+            import_graph: vec![vec![]],
+            embedded_files: vec![]
         }
     }
 
@@ -86,9 +93,15 @@ impl ImportCache {
         }
     }
 
-    pub fn add_import_metadata(&mut self, path: Option<String>, block: Block, pub_funs: Vec<FunctionDecl>) {
+    pub fn add_embedded_file(&mut self, path: String) {
+        if !self.embedded_files.contains(&path) {
+            self.embedded_files.push(path);
+        }
+    }
+
+    pub fn add_import_metadata(&mut self, path: Option<String>, metadata: FileMetadata) {
         let path_id = self.get_path_id(&Self::get_path(path)).unwrap();
-        self.files[path_id].metadata = Some(FileMetadata { block, pub_funs });
+        self.files[path_id].metadata = Some(metadata);
     }
 
     pub fn get_import_pub_funs(&mut self, path: Option<String>) -> Option<Vec<FunctionDecl>> {
@@ -96,6 +109,16 @@ impl ImportCache {
             .and_then(|path_id| self.files[path_id].metadata.as_ref().map(|meta| meta.pub_funs.clone()))
     }
 
+    pub fn get_import_pub_structs(&mut self, path: Option<String>) -> Option<Vec<StructDecl>> {
+        self.get_path_id(&Self::get_path(path))
+            .and_then(|path_id| self.files[path_id].metadata.as_ref().map(|meta| meta.pub_structs.clone()))
+    }
+
+    pub fn get_import_pub_enums(&mut self, path: Option<String>) -> Option<Vec<EnumDecl>> {
+        self.get_path_id(&Self::get_path(path))
+            .and_then(|path_id| self.files[path_id].metadata.as_ref().map(|meta| meta.pub_enums.clone()))
+    }
+
     fn topological_sort_util(&self, v: usize, visited: &mut Vec<bool>, stack: &mut Vec<usize>) {
         visited[v] = true;
         for i in self.import_graph[v].iter() {
@@ -1,13 +1,18 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use crate::compiler::target::{BashVersion, Target};
 use crate::modules::block::Block;
 use crate::modules::types::Type;
-use crate::utils::context::{Context, FunctionDecl, ScopeUnit, VariableDecl};
+use crate::utils::cc_flags::CCFlags;
+use crate::utils::context::{Context, EnumDecl, FunctionDecl, ScopeUnit, StructDecl, VariableDecl};
 use crate::utils::function_cache::FunctionCache;
 use crate::utils::function_interface::FunctionInterface;
-use crate::utils::import_cache::ImportCache;
+use crate::utils::import_cache::{FileCache, FileMetadata, ImportCache};
+use crate::utils::module_cache::{CheckedModule, ModuleCache, SharedModuleCache, StateChanges};
+use crate::utils::symbol_table::{SymbolDefinition, SymbolId, SymbolTable};
 use amber_meta::ContextManager;
 use heraclitus_compiler::prelude::*;
+use itertools::Itertools;
 
 #[derive(Debug, ContextManager)]
 pub struct ParserMetadata {
@@ -32,12 +37,205 @@ pub struct ParserMetadata {
     pub doc_usage: bool,
     /// List of functions that are currently being parsed
     pub parsing_functions: HashMap<(usize, Vec<Type>), usize>,
+    /// Symbols collected for editor tooling (disabled unless requested)
+    pub symbol_table: Option<SymbolTable>,
+    /// Shell dialect of the generated code
+    pub target: Target,
+    /// Oldest Bash version that the generated code has to run on
+    pub bash_version: Option<BashVersion>,
+    /// Modules parsed by the previous compilations
+    pub module_cache: Option<SharedModuleCache>,
+    /// Global ids of the constants that can change through a reference
+    pub mutable_constants: HashSet<usize>,
+    /// Global ids of the variables holding text that the shell splits into words
+    pub splitting_vars: HashSet<usize>,
+    /// Command lints that were already reported (each function variant checks the same commands)
+    pub reported_lints: HashSet<(Option<String>, usize, CCFlags)>,
 }
 
 impl ParserMetadata {
     pub fn add_message(&mut self, message: Message) {
         self.messages.push(message);
     }
+
+    /// Records where a symbol was defined if symbol collection is enabled
+    pub fn add_symbol_definition(&mut self, id: SymbolId, name: &str, signature: String, tok: Option<Token>) {
+        if self.symbol_table.is_some() && tok.is_some() {
+            let position = PositionInfo::from_token(self, tok);
+            let definition = SymbolDefinition { name: name.to_string(), signature, position };
+            if let Some(table) = self.symbol_table.as_mut() {
+                table.add_definition(id, definition);
+            }
+        }
+    }
+
+    /// Records a usage of a symbol if symbol collection is enabled
+    pub fn add_symbol_reference(&mut self, id: SymbolId, tok: Option<Token>) {
+        if self.symbol_table.is_some() && tok.is_some() {
+            let position = PositionInfo::from_token(self, tok);
+            if let Some(table) = self.symbol_table.as_mut() {
+                table.add_reference(id, position);
+            }
+        }
+    }
+}
+
+impl ParserMetadata {
+    /// Identifies the code of the current file together with everything its parsing depends on
+    fn module_key(&self, code: &str) -> Option<String> {
+        let path = self.context.path.as_ref()?;
+        // Imported files skip their main and test blocks
+        let is_imported = !self.context.trace.is_empty();
+        let context = format!("{}\0{path}\0{is_imported}", crate::get_version());
+        Some(ModuleCache::key(&context, code))
+    }
+
+    /// Reuses the parsed block of the current file if its code did not change
+    pub fn get_cached_module(&self, code: &str) -> Option<Block> {
+        let path = self.context.path.as_ref()?;
+        let cache = self.module_cache.as_ref()?;
+        let key = self.module_key(code)?;
+        cache.borrow_mut().get(path, &key)
+    }
+
+    /// Keeps the parsed block of the current file for the next compilation
+    pub fn cache_module(&self, code: &str, block: &Block) {
+        if let (Some(path), Some(cache), Some(key)) = (&self.context.path, &self.module_cache, self.module_key(code)) {
+            cache.borrow_mut().insert(path.clone(), key, block.clone());
+        }
+    }
+
+    /// Identifies the imported code together with the state of the parser that its type checking depends on
+    pub fn checked_module_key(&self, path: &str, hash: &str) -> Option<String> {
+        // Symbols are collected while type checking so the module has to be checked again
+        if self.module_cache.is_none() || self.symbol_table.is_some() {
+            return None
+        }
+        let files = self.import_cache.files.iter()
+            .map(|file| format!("{}:{}", file.path, file.metadata.as_ref().map_or("", |metadata| &metadata.hash)))
+            .join(",");
+        let instances = self.fun_cache.funs.iter()
+            .sorted_by_key(|(id, _)| **id)
+            .map(|(id, fun)| format!("{id}:{:?}", fun.instances.iter().map(|instance| &instance.args).collect_vec()))
+            .join(",");
+        let context = format!(
+            "{}\0{path}\0{:?}\0{:?}\0{}\0{}\0{:?}\0{files}\0{instances}",
+            crate::get_version(), self.target, self.bash_version, self.fun_id, self.var_id, self.context.trace
+        );
+        Some(ModuleCache::key(&context, hash))
+    }
+
+    /// Reuses the type checked import unless it or the modules it imports changed
+    pub fn get_checked_module(&self, path: &str, key: &str, is_unchanged: impl Fn(&FileCache) -> bool) -> Option<CheckedModule> {
+        self.module_cache.as_ref()?.borrow_mut().get_checked(path, key, is_unchanged)
+    }
+
+    /// Keeps the type checked import for the next compilation unless it reported any messages
+    pub fn cache_checked_module(&self, path: String, key: String, metadata: FileMetadata, snapshot: &StateSnapshot) {
+        if let Some(cache) = &self.module_cache {
+            if self.messages.len() == snapshot.messages {
+                let changes = self.changes_since(snapshot);
+                cache.borrow_mut().insert_checked(path, CheckedModule { key, metadata, changes });
+            }
+        }
+    }
+
+    /// Captures the state of the parser to find out what type checking of a module changes in it
+    pub fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            instances: self.fun_cache.funs.iter().map(|(id, fun)| (*id, fun.instances.len())).collect(),
+            referenced: self.fun_cache.referenced.clone(),
+            files: self.import_cache.files.len(),
+            import_edges: self.import_cache.import_graph.iter().map(Vec::len).collect(),
+            embedded_files: self.import_cache.embedded_files.len(),
+            mutable_constants: self.mutable_constants.clone(),
+            splitting_vars: self.splitting_vars.clone(),
+            messages: self.messages.len(),
+        }
+    }
+
+    fn changes_since(&self, snapshot: &StateSnapshot) -> StateChanges {
+        let mut funs = vec![];
+        let mut instances = vec![];
+        for (id, fun) in self.fun_cache.funs.iter() {
+            match snapshot.instances.get(id) {
+                Some(&count) if count < fun.instances.len() => instances.push((*id, fun.instances[count..].to_vec())),
+                Some(_) => {}
+                None => funs.push((*id, fun.clone())),
+            }
+        }
+        let import_edges = self.import_cache.import_graph.iter().enumerate()
+            .map(|(index, edges)| edges[snapshot.import_edges.get(index).copied().unwrap_or_default()..].to_vec())
+            .collect();
+        StateChanges {
+            fun_id: self.fun_id,
+            var_id: self.var_id,
+            funs,
+            instances,
+            referenced: self.fun_cache.referenced.difference(&snapshot.referenced).copied().collect(),
+            files: self.import_cache.files[snapshot.files..].to_vec(),
+            import_edges,
+            embedded_files: self.import_cache.embedded_files[snapshot.embedded_files..].to_vec(),
+            mutable_constants: self.mutable_constants.difference(&snapshot.mutable_constants).copied().collect(),
+            splitting_vars: self.splitting_vars.difference(&snapshot.splitting_vars).copied().collect(),
+        }
+    }
+
+    /// Replays the changes that type checking of a cached module made to the state of the parser
+    pub fn apply_changes(&mut self, changes: StateChanges) {
+        self.fun_id = changes.fun_id;
+        self.var_id = changes.var_id;
+        self.fun_cache.funs.extend(changes.funs);
+        for (id, instances) in changes.instances {
+            if let Some(fun) = self.fun_cache.funs.get_mut(&id) {
+                fun.instances.extend(instances);
+            }
+        }
+        self.fun_cache.referenced.extend(changes.referenced);
+        self.import_cache.files.extend(changes.files);
+        self.import_cache.import_graph.resize(changes.import_edges.len(), vec![]);
+        for (edges, added) in self.import_cache.import_graph.iter_mut().zip(changes.import_edges) {
+            edges.extend(added);
+        }
+        self.import_cache.embedded_files.extend(changes.embedded_files);
+        self.mutable_constants.extend(changes.mutable_constants);
+        self.splitting_vars.extend(changes.splitting_vars);
+    }
+}
+
+/// State of the parser before an imported module is type checked
+pub struct StateSnapshot {
+    instances: HashMap<usize, usize>,
+    referenced: HashSet<(usize, usize)>,
+    files: usize,
+    import_edges: Vec<usize>,
+    embedded_files: usize,
+    mutable_constants: HashSet<usize>,
+    splitting_vars: HashSet<usize>,
+    messages: usize,
+}
+
+impl ParserMetadata {
+    /// Fails when the feature cannot be expressed in the POSIX shell target
+    pub fn require_bash(&self, pos: PositionInfo, feature: &str) -> SyntaxResult {
+        if self.target.is_posix() {
+            let message = format!("{feature} are not supported by the POSIX shell target");
+            return error_pos!(self, pos, message, "Use the Bash target to compile this code");
+        }
+        Ok(())
+    }
+
+    /// Fails when the feature needs a newer Bash than the one requested with `--bash-version`
+    pub fn require_bash_version(&self, pos: PositionInfo, feature: &str, version: BashVersion) -> SyntaxResult {
+        self.require_bash(pos.clone(), feature)?;
+        match self.bash_version {
+            Some(oldest) if oldest < version => {
+                let message = format!("{feature} require Bash {version} or newer");
+                error_pos!(self, pos, message, format!("The generated code has to run on Bash {oldest}"))
+            }
+            _ => Ok(())
+        }
+    }
 }
 
 // Implement context methods
@@ -132,11 +330,24 @@ impl ParserMetadata {
         }
     }
 
+    /// Changes the type of a variable, e.g. when the element type of an empty array is inferred
+    pub fn set_var_type(&mut self, name: &str, kind: Type) {
+        for scope in self.context.scopes.iter_mut().rev() {
+            if let Some(var) = scope.vars.get_mut(name) {
+                var.kind = kind;
+                return;
+            }
+        }
+    }
+
     /// Marks a variable as modified
     pub fn mark_var_modified(&mut self, name: &str) {
         for scope in self.context.scopes.iter_mut().rev() {
             if let Some(var) = scope.vars.get_mut(name) {
                 var.is_modified = true;
+                if let (true, Some(id)) = (var.is_const, var.global_id) {
+                    self.mutable_constants.insert(id);
+                }
                 return;
             }
         }
@@ -211,6 +422,48 @@ impl ParserMetadata {
             .flat_map(|scope| scope.get_fun_names())
             .collect()
     }
+
+    /* Structs */
+
+    /// Adds a struct declaration to the current scope
+    pub fn add_struct_declaration(&mut self, decl: StructDecl) -> bool {
+        // Add the struct to the public struct list
+        if decl.is_public {
+            self.context.pub_structs.push(decl.clone());
+        }
+        let scope = self.context.scopes.last_mut().unwrap();
+        scope.add_struct(decl)
+    }
+
+    /// Gets a struct declaration from the current scope or any parent scope
+    pub fn get_struct_declaration(&self, name: &str) -> Option<&StructDecl> {
+        self.context
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get_struct(name))
+    }
+
+    /* Enums */
+
+    /// Adds an enum declaration to the current scope
+    pub fn add_enum_declaration(&mut self, decl: EnumDecl) -> bool {
+        // Add the enum to the public enum list
+        if decl.is_public {
+            self.context.pub_enums.push(decl.clone());
+        }
+        let scope = self.context.scopes.last_mut().unwrap();
+        scope.add_enum(decl)
+    }
+
+    /// Gets an enum declaration from the current scope or any parent scope
+    pub fn get_enum_declaration(&self, name: &str) -> Option<&EnumDecl> {
+        self.context
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get_enum(name))
+    }
 }
 
 impl Metadata for ParserMetadata {
@@ -226,6 +479,13 @@ impl Metadata for ParserMetadata {
             messages: Vec::new(),
             doc_usage: false,
             parsing_functions: HashMap::new(),
+            symbol_table: None,
+            target: Target::Bash,
+            bash_version: None,
+            module_cache: None,
+            mutable_constants: HashSet::new(),
+            splitting_vars: HashSet::new(),
+            reported_lints: HashSet::new(),
         }
     }
 
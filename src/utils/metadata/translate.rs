@@ -1,12 +1,14 @@
 use std::cmp;
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 use super::ParserMetadata;
+use crate::compiler::target::{BashVersion, Target};
 use crate::compiler::CompilerOptions;
 use crate::modules::prelude::*;
 use crate::modules::types::Type;
 use crate::raw_fragment;
 use crate::translate::compute::ArithType;
+use crate::translate::constant::Constant;
 use crate::utils::function_cache::FunctionCache;
 use crate::utils::function_metadata::FunctionMetadata;
 use crate::utils::is_all_caps;
@@ -39,15 +41,37 @@ pub struct TranslateMetadata {
     pub indent: i64,
     /// Determines if minify flag was set.
     pub minify: bool,
+    /// The shell dialect of the generated code.
+    pub target: Target,
+    /// The oldest Bash version that the generated code has to support.
+    pub bash_version: Option<BashVersion>,
     /// Determines whether the current context is an expression context.
     #[context]
     pub expr_ctx: bool,
+    /// Determines if the origin of statements should be marked for the source map.
+    pub source_map: bool,
+    /// Index of the source file that is currently being translated.
+    pub source_index: usize,
+    /// Determines if failures should record the Amber stack trace.
+    pub trace_failures: bool,
+    /// Determines if test blocks should be translated instead of the main block.
+    pub test_mode: bool,
+    /// Determines if the expressions of constant values should be evaluated at compile time.
+    pub optimize: bool,
+    /// Values of the global constants that are known at compile time.
+    pub constants: HashMap<usize, Constant>,
+    /// Global ids of the constants that can change through a reference.
+    pub mutable_constants: HashSet<usize>,
+    /// External commands that the generated code invokes.
+    pub commands: BTreeSet<String>,
+    /// Commands declared with the `requires` flag that are checked when the script starts.
+    pub required_commands: BTreeSet<String>,
 }
 
 impl TranslateMetadata {
     pub fn new(meta: ParserMetadata, options: &CompilerOptions) -> Self {
         TranslateMetadata {
-            arith_module: ArithType::BcSed,
+            arith_module: options.num_backend,
             fun_cache: meta.fun_cache,
             fun_meta: None,
             stmt_queue: VecDeque::new(),
@@ -57,14 +81,40 @@ impl TranslateMetadata {
             sudoed: false,
             indent: -1,
             minify: options.minify,
+            target: options.target,
+            bash_version: options.bash_version,
             expr_ctx: false,
+            source_map: options.source_map,
+            source_index: 0,
+            trace_failures: options.trace_failures,
+            test_mode: options.test_mode,
+            optimize: options.optimize,
+            constants: HashMap::new(),
+            mutable_constants: meta.mutable_constants,
+            commands: BTreeSet::new(),
+            required_commands: BTreeSet::new(),
         }
     }
 
+    /// Returns the value of the constant if it cannot change at runtime
+    pub fn get_constant(&self, global_id: Option<usize>) -> Option<Constant> {
+        self.constants.get(&global_id?).cloned()
+    }
+
+    /// Records an external command that the generated code invokes
+    pub fn use_command(&mut self, name: &str) {
+        self.commands.insert(name.to_string());
+    }
+
     pub fn single_indent() -> String {
         INDENT_SPACES.to_string()
     }
 
+    /// Determines if the generated code has to run on Bash older than the given version
+    pub fn is_bash_older_than(&self, version: BashVersion) -> bool {
+        self.bash_version.is_some_and(|bash_version| bash_version < version)
+    }
+
     pub fn gen_indent(&self) -> String {
         INDENT_SPACES.repeat(cmp::max(self.indent, 0) as usize)
     }
@@ -1,10 +1,13 @@
 pub mod cc_flags;
+pub mod commands;
 pub mod context;
 pub mod function_cache;
 pub mod function_interface;
 pub mod function_metadata;
 pub mod import_cache;
 pub mod metadata;
+pub mod module_cache;
+pub mod symbol_table;
 
 pub use metadata::*;
 
@@ -0,0 +1,173 @@
+use crate::modules::block::Block;
+use crate::utils::function_cache::{FunctionCacheEntry, FunctionInstance};
+use crate::utils::import_cache::{FileCache, FileMetadata};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+/// Syntax tree of a module before type checking
+pub struct ParsedModule {
+    /// Identifies the code of the module and everything its parsing depends on
+    pub key: String,
+    pub block: Block,
+}
+
+#[derive(Debug, Clone)]
+/// Changes that type checking a module made to the state of the parser
+pub struct StateChanges {
+    pub fun_id: usize,
+    pub var_id: usize,
+    /// Functions declared by the module and its imports
+    pub funs: Vec<(usize, FunctionCacheEntry)>,
+    /// Instances of the already declared functions that the module called
+    pub instances: Vec<(usize, Vec<FunctionInstance>)>,
+    pub referenced: Vec<(usize, usize)>,
+    /// Modules imported for the first time
+    pub files: Vec<FileCache>,
+    /// Edges added to each node of the import graph
+    pub import_edges: Vec<Vec<usize>>,
+    pub embedded_files: Vec<String>,
+    pub mutable_constants: Vec<usize>,
+    pub splitting_vars: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+/// Imported module after type checking
+pub struct CheckedModule {
+    /// Identifies the code of the module and the state of the parser that it was type checked in
+    pub key: String,
+    pub metadata: FileMetadata,
+    pub changes: StateChanges,
+}
+
+#[derive(Debug, Default)]
+/// Parsed modules kept between compilations so that only the changed ones are parsed again
+pub struct ModuleCache {
+    pub modules: HashMap<String, ParsedModule>,
+    /// Type checked imports (kept only in memory as they refer to the state of the parser)
+    pub checked: HashMap<String, CheckedModule>,
+    /// Directory where the parsed modules are stored between runs of the compiler
+    pub directory: Option<PathBuf>,
+    /// Number of modules reused from the cache
+    pub hits: usize,
+    /// Number of modules that had to be parsed
+    pub misses: usize,
+    /// Number of imports that did not have to be type checked again
+    pub checked_hits: usize,
+}
+
+pub type SharedModuleCache = Rc<RefCell<ModuleCache>>;
+
+impl ModuleCache {
+    pub fn new_shared() -> SharedModuleCache {
+        Rc::new(RefCell::new(ModuleCache::default()))
+    }
+
+    /// Cache of the command line kept on disk unless 'AMBER_NO_CACHE' is set
+    pub fn new_shared_on_disk() -> SharedModuleCache {
+        let disabled = env::var("AMBER_NO_CACHE").is_ok_and(|value| value == "1" || value == "true");
+        let cache = Self::new_shared();
+        if !disabled {
+            cache.borrow_mut().directory = Self::default_directory();
+        }
+        cache
+    }
+
+    /// Directory of the on-disk cache ('AMBER_CACHE_DIR' or the user cache directory)
+    pub fn default_directory() -> Option<PathBuf> {
+        if let Some(dir) = env::var_os("AMBER_CACHE_DIR") {
+            return Some(PathBuf::from(dir));
+        }
+        let cache = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(cache.join("amber"))
+    }
+
+    /// Hashes the code together with the context that its parsing depends on
+    pub fn key(context: &str, content: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(context.as_bytes());
+        hasher.update([0]);
+        hasher.update(content.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Files of the same module share the prefix so that only the latest one of them is kept
+    fn file_prefix(path: &str) -> String {
+        format!("{}-", &Self::key("", path)[..16])
+    }
+
+    fn file(&self, path: &str, key: &str) -> Option<PathBuf> {
+        self.directory.as_ref().map(|dir| dir.join(format!("{}{key}.json", Self::file_prefix(path))))
+    }
+
+    /// Removes the previous versions of the module from the disk
+    fn prune(&self, path: &str, file: &Path) {
+        let (Some(dir), Some(name)) = (&self.directory, file.file_name()) else {
+            return
+        };
+        let prefix = Self::file_prefix(path);
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            let entry_name = entry.file_name();
+            if entry_name != name && entry_name.to_string_lossy().starts_with(&prefix) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// Returns the parsed module if its code did not change since it was cached
+    pub fn get(&mut self, path: &str, key: &str) -> Option<Block> {
+        let block = match self.modules.get(path) {
+            Some(module) if module.key == key => Some(module.block.clone()),
+            // Entries that cannot be read are parsed again and overwritten
+            _ => self.file(path, key)
+                .and_then(|file| fs::read_to_string(file).ok())
+                .and_then(|json| serde_json::from_str::<Block>(&json).ok())
+                .inspect(|block| {
+                    let module = ParsedModule { key: key.to_string(), block: block.clone() };
+                    self.modules.insert(path.to_string(), module);
+                }),
+        };
+        if block.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        block
+    }
+
+    pub fn insert(&mut self, path: String, key: String, block: Block) {
+        // The cache only speeds up the compilation so it is fine if it cannot be written
+        if let (Some(file), Ok(json)) = (self.file(&path, &key), serde_json::to_string(&block)) {
+            let written = file.parent().map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&file, json));
+            if written.is_err() {
+                let _ = fs::remove_file(&file);
+            }
+            self.prune(&path, &file);
+        }
+        self.modules.insert(path, ParsedModule { key, block });
+    }
+
+    /// Returns the type checked import if it was checked in the same state of the parser
+    /// and none of the modules that it imported for the first time changed since then
+    pub fn get_checked(&mut self, path: &str, key: &str, is_unchanged: impl Fn(&FileCache) -> bool) -> Option<CheckedModule> {
+        let module = self.checked.get(path)
+            .filter(|module| module.key == key && module.changes.files.iter().all(&is_unchanged))
+            .cloned();
+        if module.is_some() {
+            self.checked_hits += 1;
+        }
+        module
+    }
+
+    pub fn insert_checked(&mut self, path: String, module: CheckedModule) {
+        self.checked.insert(path, module);
+    }
+}
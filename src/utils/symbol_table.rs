@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use heraclitus_compiler::prelude::*;
+use crate::modules::types::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolId {
+    Variable(usize),
+    Function(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolDefinition {
+    pub name: String,
+    /// Human readable signature used when hovering over the symbol
+    pub signature: String,
+    pub position: PositionInfo,
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolReference {
+    pub id: SymbolId,
+    pub position: PositionInfo,
+}
+
+/// Variables and functions visible at the end of a block
+#[derive(Debug, Clone)]
+pub struct ScopeSnapshot {
+    pub path: Option<String>,
+    /// Range of the block (row, column) - `None` when spanning the whole file
+    pub range: Option<((usize, usize), (usize, usize))>,
+    pub vars: Vec<(String, Type)>,
+    pub funs: Vec<String>,
+}
+
+/// Symbols collected during type checking for editor tooling
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    pub definitions: HashMap<SymbolId, SymbolDefinition>,
+    pub references: Vec<SymbolReference>,
+    pub scopes: Vec<ScopeSnapshot>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_definition(&mut self, id: SymbolId, definition: SymbolDefinition) {
+        self.references.push(SymbolReference { id, position: definition.position.clone() });
+        self.definitions.insert(id, definition);
+    }
+
+    pub fn add_reference(&mut self, id: SymbolId, position: PositionInfo) {
+        self.references.push(SymbolReference { id, position });
+    }
+
+    pub fn add_scope(&mut self, scope: ScopeSnapshot) {
+        self.scopes.push(scope);
+    }
+
+    /// Finds the symbol occurrence under the given (row, column) position
+    pub fn find_at(&self, path: &str, (row, col): (usize, usize)) -> Option<(&SymbolReference, &SymbolDefinition)> {
+        self.references.iter()
+            .filter(|reference| reference.position.path.as_deref() == Some(path))
+            .find(|reference| match reference.position.position {
+                Position::Pos(ref_row, ref_col) => {
+                    ref_row == row && (ref_col..ref_col + reference.position.len).contains(&col)
+                }
+                Position::EOF => false,
+            })
+            .and_then(|reference| {
+                self.definitions.get(&reference.id).map(|definition| (reference, definition))
+            })
+    }
+
+    /// Finds the innermost scope containing the given (row, column) position
+    pub fn scope_at(&self, path: &str, pos: (usize, usize)) -> Option<&ScopeSnapshot> {
+        self.scopes.iter()
+            .filter(|scope| scope.path.as_deref() == Some(path))
+            .filter(|scope| scope.range.map_or(true, |(begin, end)| begin <= pos && pos <= end))
+            .min_by_key(|scope| match scope.range {
+                Some(((begin_row, _), (end_row, _))) => end_row - begin_row,
+                None => usize::MAX,
+            })
+    }
+}
@@ -0,0 +1,68 @@
+use colored::Colorize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Time between two checks of the watched files
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// State of a file that changes whenever the file is written, created or removed
+type FileState = Option<(SystemTime, u64)>;
+
+/// Polls the files for changes
+pub struct Watcher {
+    files: Vec<(PathBuf, FileState)>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let files = paths.into_iter()
+            .map(|path| {
+                let state = Self::state(&path);
+                (path, state)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    fn state(path: &PathBuf) -> FileState {
+        let metadata = fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    pub fn has_changed(&self) -> bool {
+        self.files.iter().any(|(path, state)| Self::state(path) != *state)
+    }
+
+    /// Blocks until any of the files changes
+    pub fn wait(&self) {
+        while !self.has_changed() {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Compiles the input again whenever one of the files it was compiled from changes.
+/// The step returns the files to watch or nothing if the compilation failed.
+pub fn watch<F>(input: PathBuf, mut step: F)
+where
+    F: FnMut() -> Option<Vec<PathBuf>>,
+{
+    let mut files = vec![input.clone()];
+    loop {
+        // Clear the screen and move the cursor to the top
+        print!("\x1b[2J\x1b[H");
+        let _ = io::stdout().flush();
+        // The files of the failed compilation are unknown so the previous ones are kept
+        if let Some(compiled) = step() {
+            files = compiled;
+        }
+        if !files.contains(&input) {
+            files.push(input.clone());
+        }
+        println!("{}", "Waiting for changes...".dimmed());
+        Watcher::new(files.clone()).wait();
+    }
+}